@@ -0,0 +1,44 @@
+//! Switching the highlighted scale on a full-size fretboard, as done by the scale page and
+//! the trainers. Run with `cargo bench --bench scale_switching`.
+
+use std::hint::black_box;
+use std::str::FromStr;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use leptos::prelude::*;
+use rust_guitar_app::models::fretboard::{FretboardModelBuilder, FretboardModelExt};
+use rust_guitar_app::music::{Note, Scale, ScaleType};
+
+fn scale_switching(c: &mut Criterion) {
+  let owner = Owner::new();
+  owner.with(|| {
+    let model = FretboardModelBuilder::new()
+      .start_fret_val(0)
+      .end_fret_val(24)
+      .build()
+      .unwrap();
+    let scales = [
+      Scale::new(Note::A, ScaleType::from_str("Minor").unwrap()),
+      Scale::new(Note::C, ScaleType::from_str("Major").unwrap()),
+      Scale::new(Note::E, ScaleType::from_str("Chromatic").unwrap()),
+    ];
+
+    let mut scale_idx = 0;
+    c.bench_function("switch scale, 6 strings, frets 0-24", |b| {
+      b.iter(|| {
+        scale_idx = (scale_idx + 1) % scales.len();
+        model.update_from_scale(black_box(scales[scale_idx]));
+      })
+    });
+
+    c.bench_function("hide all frets", |b| {
+      b.iter(|| {
+        model.update_from_scale(scales[0]);
+        model.hide_all_frets();
+      })
+    });
+  });
+}
+
+criterion_group!(benches, scale_switching);
+criterion_main!(benches);
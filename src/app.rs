@@ -3,12 +3,27 @@ use leptos_meta::*;
 use leptos_router::{components::*, path};
 
 use crate::{
-  components::navbar::Navbar,
+  audio::FretSoundPreference,
+  components::{
+    fretboard::{
+      FretboardOrientation, FretboardOrientationPreference, FretboardTheme,
+      FretboardThemePreference,
+    },
+    navbar::Navbar,
+  },
+  models::{
+    fretboard::{Instrument, InstrumentPreference},
+    storage::{
+      load_fret_sound, load_fretboard_orientation, load_fretboard_theme, load_instrument,
+      persist_signal, save_fret_sound, save_fretboard_orientation, save_fretboard_theme,
+      save_instrument,
+    },
+  },
   pages::{
     exercise_detail::ExerciseDetailPage, exercises::ExercisesPage,
     fretboard_config_examples::FretboardConfigExamples, fretboard_dev::FretboardDevPage,
     fretboard_scale::FretboardScalePage, fretboard_trainer::FretboardTrainerPage, home::Home,
-    not_found::NotFound,
+    not_found::NotFound, tuner::TunerPage,
   },
 };
 
@@ -36,6 +51,37 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
 pub fn App() -> impl IntoView {
   provide_meta_context();
 
+  // Fretboard orientation preference (e.g. left-handed), persisted in the browser
+  let orientation = RwSignal::new(FretboardOrientation::default());
+  provide_context(FretboardOrientationPreference(orientation));
+  persist_signal(
+    "fretboard orientation",
+    orientation,
+    load_fretboard_orientation,
+    |orientation| save_fretboard_orientation(*orientation),
+  );
+
+  // Fretboard colours, e.g. a colour-blind safe palette or a dark board
+  let theme = RwSignal::new(FretboardTheme::default());
+  provide_context(FretboardThemePreference(theme));
+  persist_signal("fretboard theme", theme, load_fretboard_theme, |theme| {
+    save_fretboard_theme(*theme)
+  });
+
+  // Instrument shown on all fretboards, e.g. bass or ukulele
+  let instrument = RwSignal::new(Instrument::default());
+  provide_context(InstrumentPreference(instrument));
+  persist_signal("instrument", instrument, load_instrument, |instrument| {
+    save_instrument(*instrument)
+  });
+
+  // Clicked frets play their note
+  let fret_sound = RwSignal::new(false);
+  provide_context(FretSoundPreference(fret_sound));
+  persist_signal("fret sound", fret_sound, load_fret_sound, |fret_sound| {
+    save_fret_sound(*fret_sound)
+  });
+
   view! {
     <Router>
       <Title text="♫ Rust Guitar App ♫" />
@@ -46,6 +92,7 @@ pub fn App() -> impl IntoView {
           <Route path=path!("/exercises") view=ExercisesPage />
           <Route path=path!("/exercises/:id") view=ExerciseDetailPage />
           <Route path=path!("/fretboard_trainer") view=FretboardTrainerPage />
+          <Route path=path!("/tuner") view=TunerPage />
           <Route path=path!("/fretboard_dev") view=FretboardDevPage />
           <Route path=path!("/fretboard_scale") view=FretboardScalePage />
           <Route path=path!("/fretboard_config_examples") view=FretboardConfigExamples />
@@ -1,10 +1,12 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use web_sys::{AudioContext, AudioContextState};
 
 thread_local! {
   /// Global audio manager instance using thread-local storage
   /// This is appropriate for WASM's single-threaded environment
   static GLOBAL_AUDIO_MANAGER: RefCell<Option<AudioContext>> = const { RefCell::new(None) };
+  /// Context time in seconds until which the app plays sound
+  static SOUNDING_UNTIL: Cell<f64> = const { Cell::new(0.0) };
 }
 
 /// Shared audio manager for the application
@@ -81,6 +83,19 @@ impl AudioManager {
     Self::get_context().map(|ctx| ctx.current_time())
   }
 
+  /// Notes that the app plays sound until `end` seconds of context time
+  pub fn mark_sounding_until(end: f64) {
+    SOUNDING_UNTIL.with(|until| until.set(until.get().max(end)));
+  }
+
+  /// Whether sound played by the app is still ringing, e.g. so the microphone doesn't take
+  /// it for a played note. Doesn't create a context if there is none yet.
+  pub fn is_sounding() -> bool {
+    let now =
+      GLOBAL_AUDIO_MANAGER.with(|manager| manager.borrow().as_ref().map(|ctx| ctx.current_time()));
+    now.is_some_and(|now| now < SOUNDING_UNTIL.with(Cell::get))
+  }
+
   /// Resume AudioContext if suspended (typically called on user interaction)
   pub fn resume() -> Result<(), String> {
     if let Some(ctx) = Self::get_context() {
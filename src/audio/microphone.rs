@@ -0,0 +1,44 @@
+use leptos::wasm_bindgen::JsValue;
+use web_sys::{AnalyserNode, AudioContext, MediaStream, MediaStreamAudioSourceNode};
+
+/// Samples analysed for each pitch, two periods of the low E of a bass at 44.1 kHz
+pub const FRAME_SIZE: u32 = 4096;
+
+/// Latest samples of a microphone stream, read on demand for the pitch detection
+pub struct MicrophoneAnalyser {
+  audio_ctx: AudioContext,
+  source: MediaStreamAudioSourceNode,
+  analyser: AnalyserNode,
+}
+
+impl MicrophoneAnalyser {
+  pub fn new(audio_ctx: AudioContext, stream: &MediaStream) -> Result<Self, JsValue> {
+    let source = audio_ctx.create_media_stream_source(stream)?;
+    let analyser = audio_ctx.create_analyser()?;
+    analyser.set_fft_size(FRAME_SIZE);
+    // The analyser isn't connected to the speakers, so nothing is played back
+    source.connect_with_audio_node(&analyser)?;
+    Ok(Self {
+      audio_ctx,
+      source,
+      analyser,
+    })
+  }
+
+  pub fn sample_rate(&self) -> f32 {
+    self.audio_ctx.sample_rate()
+  }
+
+  /// The last `FRAME_SIZE` samples
+  pub fn read_frame(&self) -> Vec<f32> {
+    let mut frame = vec![0.0; FRAME_SIZE as usize];
+    self.analyser.get_float_time_domain_data(&mut frame);
+    frame
+  }
+}
+
+impl Drop for MicrophoneAnalyser {
+  fn drop(&mut self) {
+    self.source.disconnect().ok();
+  }
+}
@@ -1,3 +1,11 @@
 pub mod audio_manager;
+pub mod microphone;
+pub mod pitch;
+pub mod pluck;
+pub mod tones;
 
 pub use audio_manager::AudioManager;
+pub use microphone::MicrophoneAnalyser;
+pub use pitch::{detect_pitch, NoteStabilizer, PlayedNote};
+pub use pluck::{play_sequence, pluck_note, use_fret_sound, FretSoundPreference};
+pub use tones::{play_tones, Tone};
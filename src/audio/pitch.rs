@@ -0,0 +1,251 @@
+//! Monophonic pitch detection with the YIN algorithm, independent of the Web Audio capture
+//! so it can be tested with synthetic buffers
+
+use super::tones::{frequency_to_midi, A4_FREQUENCY};
+use crate::music::notes::Note;
+
+/// Lowest detected frequency in Hz, a little below the low E of a 4-string bass
+pub const MIN_FREQUENCY: f64 = 38.0;
+/// Highest detected frequency in Hz, above the 24th fret of a high E string
+pub const MAX_FREQUENCY: f64 = 1400.0;
+/// Dips of the normalized difference below this count as a period
+const YIN_THRESHOLD: f32 = 0.15;
+/// Quieter frames are silence, e.g. between notes
+const MIN_RMS: f32 = 0.01;
+/// Frames in a row with the same note before it counts as played
+const STABLE_FRAMES: u32 = 3;
+
+/// Fundamental frequency of the frame in Hz, `None` for silence, noise or chords.
+///
+/// The frame should hold at least two periods of the lowest note, e.g. 4096 samples at
+/// 44.1 kHz for the low E of a bass.
+pub fn detect_pitch(samples: &[f32], sample_rate: f32) -> Option<f64> {
+  let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len().max(1) as f32).sqrt();
+  if rms < MIN_RMS {
+    return None;
+  }
+  let sample_rate = f64::from(sample_rate);
+  let min_lag = ((sample_rate / MAX_FREQUENCY) as usize).max(2);
+  // Too short for two periods of the highest note
+  if samples.len() < 2 * min_lag {
+    return None;
+  }
+  let window = samples.len() / 2;
+  let max_lag = ((sample_rate / MIN_FREQUENCY) as usize).min(window - 1);
+  if min_lag >= max_lag {
+    return None;
+  }
+
+  // Difference of the frame with itself shifted by each lag
+  let difference: Vec<f32> = (0..=max_lag)
+    .map(|lag| {
+      (0..window)
+        .map(|idx| {
+          let delta = samples[idx] - samples[idx + lag];
+          delta * delta
+        })
+        .sum()
+    })
+    .collect();
+
+  // Cumulative mean normalized difference, 1 at lag 0 so short lags don't win
+  let mut normalized = vec![1.0_f32; max_lag + 1];
+  let mut running_sum = 0.0;
+  for lag in 1..=max_lag {
+    running_sum += difference[lag];
+    normalized[lag] = if running_sum > 0.0 {
+      difference[lag] * lag as f32 / running_sum
+    } else {
+      1.0
+    };
+  }
+
+  // The first dip below the threshold, followed down to its bottom
+  let mut lag = (min_lag..max_lag).find(|lag| normalized[*lag] < YIN_THRESHOLD)?;
+  while lag + 1 < max_lag && normalized[lag + 1] < normalized[lag] {
+    lag += 1;
+  }
+
+  // Parabolic interpolation between the neighbouring lags
+  let (left, center, right) = (
+    f64::from(normalized[lag - 1]),
+    f64::from(normalized[lag]),
+    f64::from(normalized[lag + 1]),
+  );
+  let curvature = left + right - 2.0 * center;
+  let shift = if curvature.abs() > f64::EPSILON {
+    (left - right) / (2.0 * curvature)
+  } else {
+    0.0
+  };
+  Some(sample_rate / (lag as f64 + shift.clamp(-1.0, 1.0)))
+}
+
+/// A detected note with its octave and how far it is off the equal temperament pitch
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayedNote {
+  pub midi: u8,
+  /// From -50 (flat) to 50 (sharp)
+  pub cents: f64,
+}
+
+impl PlayedNote {
+  pub fn from_frequency(frequency: f64) -> Option<Self> {
+    Self::from_frequency_with_reference(frequency, A4_FREQUENCY)
+  }
+
+  /// The note of a frequency with A4 tuned to `a4_frequency`, e.g. 442 Hz for an
+  /// orchestra
+  pub fn from_frequency_with_reference(frequency: f64, a4_frequency: f64) -> Option<Self> {
+    let midi = frequency_to_midi(frequency * A4_FREQUENCY / a4_frequency);
+    let nearest = midi.round();
+    (0.0..=127.0).contains(&nearest).then_some(Self {
+      midi: nearest as u8,
+      cents: (midi - nearest) * 100.0,
+    })
+  }
+
+  pub fn note(&self) -> Note {
+    Note::all_notes()[usize::from(self.midi) % 12]
+  }
+
+  /// Scientific pitch octave, middle C is in octave 4
+  pub fn octave(&self) -> i32 {
+    i32::from(self.midi) / 12 - 1
+  }
+}
+
+impl std::fmt::Display for PlayedNote {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}{}", self.note(), self.octave())
+  }
+}
+
+/// Turns the notes of consecutive frames into played notes: a note counts once it held
+/// for a few frames, and again only after a different note or a pause
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NoteStabilizer {
+  candidate: Option<u8>,
+  frames: u32,
+  played: Option<u8>,
+}
+
+impl NoteStabilizer {
+  /// Adds the MIDI number detected in the next frame, returns it once it was played
+  pub fn push(&mut self, midi: Option<u8>) -> Option<u8> {
+    if midi == self.candidate {
+      self.frames += 1;
+    } else {
+      self.candidate = midi;
+      self.frames = 1;
+    }
+    match midi {
+      None => {
+        self.played = None;
+        None
+      }
+      Some(midi) if self.frames >= STABLE_FRAMES && self.played != Some(midi) => {
+        self.played = Some(midi);
+        Some(midi)
+      }
+      Some(_) => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::audio::pluck::karplus_strong;
+  use crate::audio::tones::midi_to_frequency;
+
+  const SAMPLE_RATE: f32 = 44_100.0;
+  const FRAME: usize = 4096;
+
+  fn sine(frequency: f64) -> Vec<f32> {
+    (0..FRAME)
+      .map(|idx| {
+        let t = idx as f64 / f64::from(SAMPLE_RATE);
+        (0.5 * (2.0 * std::f64::consts::PI * frequency * t).sin()) as f32
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_detects_sines() {
+    for frequency in [41.2, 110.0, 196.0, 440.0, 1046.5] {
+      let detected = detect_pitch(&sine(frequency), SAMPLE_RATE).unwrap();
+      let cents = 1200.0 * (detected / frequency).log2();
+      assert!(
+        cents.abs() < 5.0,
+        "{frequency} Hz detected as {detected} Hz"
+      );
+    }
+    assert_eq!(detect_pitch(&[0.0; FRAME], SAMPLE_RATE), None);
+    // Loud frames that are too short
+    assert_eq!(detect_pitch(&[0.8], SAMPLE_RATE), None);
+    assert_eq!(detect_pitch(&sine(440.0)[..40], SAMPLE_RATE), None);
+  }
+
+  #[test]
+  fn test_detects_plucks() {
+    // Low E, D string and high E
+    for midi in [40, 50, 64] {
+      let pluck = karplus_strong(midi_to_frequency(midi), SAMPLE_RATE, 0.5);
+      // Skip the attack, the noise burst needs a few periods to settle
+      let frame = &pluck[FRAME..2 * FRAME];
+      let detected = detect_pitch(frame, SAMPLE_RATE).unwrap();
+      assert_eq!(PlayedNote::from_frequency(detected).unwrap().midi, midi);
+    }
+  }
+
+  #[test]
+  fn test_high_plucks_are_in_tune() {
+    // High E string from the open string to the 12th fret
+    for midi in 76..=88 {
+      let frequency = midi_to_frequency(midi);
+      let pluck = karplus_strong(frequency, SAMPLE_RATE, 0.5);
+      // High notes die away quickly, a few dozen periods are enough to settle
+      let frame = &pluck[1024..1024 + FRAME];
+      let detected = detect_pitch(frame, SAMPLE_RATE).unwrap();
+      let cents = 1200.0 * (detected / frequency).log2();
+      assert!(cents.abs() < 5.0, "MIDI {midi} is {cents:.1} cents off");
+    }
+  }
+
+  #[test]
+  fn test_played_note() {
+    let played = PlayedNote::from_frequency(445.0).unwrap();
+    assert_eq!(played.midi, 69);
+    assert_eq!(played.note(), Note::A);
+    assert_eq!(played.to_string(), "A4");
+    assert!((played.cents - 19.6).abs() < 0.1);
+    // In tune with a 445 Hz reference
+    let played = PlayedNote::from_frequency_with_reference(445.0, 445.0).unwrap();
+    assert_eq!(played.midi, 69);
+    assert!(played.cents.abs() < 1e-9);
+  }
+
+  #[test]
+  fn test_stabilizer_plays_each_note_once() {
+    let mut stabilizer = NoteStabilizer::default();
+    let frames = [
+      Some(40),
+      Some(40),
+      Some(40),
+      Some(40),
+      Some(45),
+      Some(45),
+      Some(45),
+      None,
+      Some(45),
+      Some(45),
+      Some(45),
+    ];
+    let played: Vec<u8> = frames
+      .iter()
+      .filter_map(|midi| stabilizer.push(*midi))
+      .collect();
+    assert_eq!(played, vec![40, 45, 45]);
+  }
+}
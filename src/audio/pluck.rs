@@ -0,0 +1,122 @@
+use leptos::prelude::*;
+use leptos::wasm_bindgen::JsValue;
+use rand::Rng;
+use web_sys::AudioContext;
+
+use super::tones::midi_to_frequency;
+
+/// Length of a plucked note in seconds
+const PLUCK_SECONDS: f64 = 1.5;
+/// Share of the energy a string keeps on each round trip, lower notes ring longer
+const DECAY: f32 = 0.996;
+/// Loudness of a pluck, no sample of the note is louder
+const PLUCK_GAIN: f32 = 0.4;
+
+/// App wide preference to sound the notes of clicked frets
+#[derive(Clone, Copy, Debug)]
+pub struct FretSoundPreference(pub RwSignal<bool>);
+
+/// Whether clicked frets sound, off if there is no preference (e.g. on the server)
+pub fn use_fret_sound() -> Signal<bool> {
+  match use_context::<FretSoundPreference>() {
+    Some(FretSoundPreference(enabled)) => enabled.into(),
+    None => Signal::derive(|| false),
+  }
+}
+
+/// Samples of a plucked string with the Karplus-Strong algorithm: a burst of noise runs
+/// through a delay line of one period and is smoothed a little on every round trip.
+///
+/// The smoothing averages two samples and delays the loop by half a sample, an all-pass
+/// filter adds the fraction of a sample the delay line can't hold (Jaffe and Smith), so
+/// high notes aren't out of tune.
+pub fn karplus_strong(frequency: f64, sample_rate: f32, seconds: f64) -> Vec<f32> {
+  let loop_length = (f64::from(sample_rate) / frequency - 0.5).max(2.0);
+  // Fractions close to zero would need an all-pass coefficient close to one, which rings
+  let mut period = loop_length.floor();
+  if loop_length - period < 0.1 && period > 2.0 {
+    period -= 1.0;
+  }
+  let fraction = loop_length - period;
+  let coefficient = ((1.0 - fraction) / (1.0 + fraction)) as f32;
+  let period = period as usize;
+
+  let mut rng = rand::rng();
+  let mut delay_line: Vec<f32> = (0..period).map(|_| rng.random_range(-1.0..1.0)).collect();
+  let mut previous = 0.0;
+  let (mut allpass_in, mut allpass_out) = (0.0, 0.0);
+  let len = (f64::from(sample_rate) * seconds) as usize;
+  let samples: Vec<f32> = (0..len)
+    .map(|n| {
+      let idx = n % period;
+      let sample = delay_line[idx];
+      let smoothed = DECAY * 0.5 * (sample + previous);
+      previous = sample;
+      allpass_out = coefficient * smoothed + allpass_in - coefficient * allpass_out;
+      allpass_in = smoothed;
+      delay_line[idx] = allpass_out;
+      sample
+    })
+    .collect();
+  // The all-pass filter can overshoot the noise burst a little
+  let peak = samples.iter().fold(0.0_f32, |max, s| max.max(s.abs()));
+  let peak = peak.max(1.0);
+  // Dividing first keeps rounding from pushing the loudest sample above the gain
+  samples
+    .into_iter()
+    .map(|sample| sample / peak * PLUCK_GAIN)
+    .collect()
+}
+
+/// Plucks a note `start` seconds from now
+pub fn play_pluck(audio_ctx: &AudioContext, midi: u8, start: f64) -> Result<(), JsValue> {
+  let sample_rate = audio_ctx.sample_rate();
+  let samples = karplus_strong(midi_to_frequency(midi), sample_rate, PLUCK_SECONDS);
+  let buffer = audio_ctx.create_buffer(1, samples.len() as u32, sample_rate)?;
+  buffer.copy_to_channel(&samples, 0)?;
+  let source = audio_ctx.create_buffer_source()?;
+  source.set_buffer(Some(&buffer));
+  source.connect_with_audio_node(&audio_ctx.destination())?;
+  let start = audio_ctx.current_time() + start;
+  super::AudioManager::mark_sounding_until(start + PLUCK_SECONDS);
+  source.start_with_when(start)
+}
+
+/// Plucks the notes one after the other, one per beat at `bpm`
+pub fn play_sequence(audio_ctx: &AudioContext, midis: &[u8], bpm: u32) {
+  let beat_seconds = 60.0 / f64::from(bpm.max(1));
+  for (idx, midi) in midis.iter().enumerate() {
+    if let Err(err) = play_pluck(audio_ctx, *midi, idx as f64 * beat_seconds) {
+      leptos::logging::warn!("Failed to pluck note {}: {:?}", midi, err);
+    }
+  }
+}
+
+/// Plucks a note in the browser, nothing during server side rendering
+pub fn pluck_note(midi: u8) {
+  if cfg!(target_arch = "wasm32") {
+    let _ = super::AudioManager::resume();
+    if let Some(ctx) = super::AudioManager::get_context() {
+      if let Err(err) = play_pluck(&ctx, midi, 0.0) {
+        leptos::logging::warn!("Failed to pluck note {}: {:?}", midi, err);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_pluck_decays() {
+    let sample_rate = 44_100.0;
+    let samples = karplus_strong(110.0, sample_rate, 1.0);
+    assert_eq!(samples.len(), 44_100);
+    let peak = |samples: &[f32]| samples.iter().fold(0.0_f32, |max, s| max.max(s.abs()));
+    let start = peak(&samples[..4410]);
+    let end = peak(&samples[samples.len() - 4410..]);
+    assert!(start > 0.0 && start <= PLUCK_GAIN);
+    assert!(end < start / 2.0);
+  }
+}
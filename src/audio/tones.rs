@@ -0,0 +1,76 @@
+use leptos::wasm_bindgen::JsValue;
+use web_sys::{AudioContext, OscillatorType};
+
+/// MIDI number of A4, the reference pitch
+const A4_MIDI: u8 = 69;
+/// Concert pitch of A4 in Hz
+pub const A4_FREQUENCY: f64 = 440.0;
+/// Loudness of a single tone, harmonic intervals sound two of them at once
+const TONE_GAIN: f32 = 0.25;
+
+/// A synthesized tone, `start` and `duration` in seconds from the time it is played
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tone {
+  pub midi: u8,
+  pub start: f64,
+  pub duration: f64,
+}
+
+/// Equal temperament frequency of a MIDI note number in Hz
+pub fn midi_to_frequency(midi: u8) -> f64 {
+  A4_FREQUENCY * 2f64.powf((f64::from(midi) - f64::from(A4_MIDI)) / 12.0)
+}
+
+/// MIDI note number of a frequency, fractional between the notes
+pub fn frequency_to_midi(frequency: f64) -> f64 {
+  f64::from(A4_MIDI) + 12.0 * (frequency / A4_FREQUENCY).log2()
+}
+
+/// Plays the tones from now on
+pub fn play_tones(audio_ctx: &AudioContext, tones: &[Tone]) {
+  let now = audio_ctx.current_time();
+  for tone in tones {
+    if let Err(err) = play_tone(audio_ctx, *tone, now) {
+      leptos::logging::warn!("Failed to play tone: {:?}", err);
+    }
+  }
+}
+
+/// A soft triangle wave with a short attack and an exponential release
+fn play_tone(audio_ctx: &AudioContext, tone: Tone, now: f64) -> Result<(), JsValue> {
+  let oscillator = audio_ctx.create_oscillator()?;
+  let gain = audio_ctx.create_gain()?;
+  oscillator.connect_with_audio_node(&gain)?;
+  gain.connect_with_audio_node(&audio_ctx.destination())?;
+
+  oscillator.set_type(OscillatorType::Triangle);
+  oscillator
+    .frequency()
+    .set_value(midi_to_frequency(tone.midi) as f32);
+
+  let start = now + tone.start;
+  let end = start + tone.duration;
+  gain.gain().set_value_at_time(0.0, start)?;
+  gain
+    .gain()
+    .linear_ramp_to_value_at_time(TONE_GAIN, start + 0.02)?;
+  gain.gain().exponential_ramp_to_value_at_time(0.001, end)?;
+  oscillator.start_with_when(start)?;
+  oscillator.stop_with_when(end)?;
+  super::AudioManager::mark_sounding_until(end);
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_midi_to_frequency() {
+    assert_eq!(midi_to_frequency(A4_MIDI), 440.0);
+    assert_eq!(midi_to_frequency(A4_MIDI + 12), 880.0);
+    // Middle C
+    assert!((midi_to_frequency(60) - 261.63).abs() < 0.01);
+    assert!((frequency_to_midi(midi_to_frequency(40)) - 40.0).abs() < 1e-9);
+  }
+}
@@ -0,0 +1,47 @@
+use leptos::prelude::*;
+
+use super::constants::CHORD_PROGRESSION_TYPE;
+use crate::music::ChordProgression;
+
+/// Text input for the chords of a chord progression exercise, e.g. "Am:4 F:4 C:2 G:2"
+#[component]
+pub fn ChordProgressionFields(
+  exercise_type: ReadSignal<String>,
+  progression_text: ReadSignal<String>,
+  on_progression_text_change: Callback<String>,
+) -> impl IntoView {
+  let parse_result = Memo::new(move |_| progression_text.get().parse::<ChordProgression>());
+
+  view! {
+    <Show when=move || exercise_type.get() == CHORD_PROGRESSION_TYPE>
+      <div class="p-4 space-y-2 bg-gray-50 rounded-md">
+        <label class="block mb-1 text-sm font-medium text-gray-700">"Chords"</label>
+        <input
+          type="text"
+          class="py-2 px-3 w-full font-mono rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none"
+          prop:value=move || progression_text.get()
+          on:input=move |e| on_progression_text_change.run(event_target_value(&e))
+          placeholder="Am:4 F:4 C:4 G:4"
+        />
+        <p class="text-xs text-gray-500">
+          "Chord symbols separated by spaces, optionally followed by the number of beats (default 4)."
+        </p>
+        {move || match parse_result.get() {
+          Ok(progression) => {
+            view! {
+              <p class="text-xs text-gray-600">
+                {format!(
+                  "{} chords, {} beats per loop",
+                  progression.steps.len(),
+                  progression.total_beats(),
+                )}
+              </p>
+            }
+              .into_any()
+          }
+          Err(error) => view! { <p class="text-xs text-red-600">{error}</p> }.into_any(),
+        }}
+      </div>
+    </Show>
+  }
+}
@@ -3,6 +3,7 @@ pub const TECHNIQUE_TYPE: &str = "Technique";
 pub const SCALE_TYPE: &str = "Scale";
 pub const TRIAD_TYPE: &str = "Triad";
 pub const SONG_TYPE: &str = "Song";
+pub const CHORD_PROGRESSION_TYPE: &str = "Chord Progression";
 
 // Helper function to check if exercise type has specific settings
 pub fn has_specific_settings(exercise_type: &str) -> bool {
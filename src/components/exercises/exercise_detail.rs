@@ -18,13 +18,19 @@ pub fn ExerciseDetail(#[prop(into)] exercise_id: Signal<String>) -> impl IntoVie
     }
   });
 
+  // Only remount the detail view when another exercise gets loaded, not on every update,
+  // so a running practice session keeps its state
+  let loaded_id = Memo::new(move |_| exercise.with(|ex| ex.as_ref().map(|ex| ex.id.clone())));
+
   view! {
-    {move || match exercise.get() {
-      Some(ex) => {
+    {move || match loaded_id.get().and(exercise.get_untracked()) {
+      Some(loaded) => {
+        let current = RwSignal::new(loaded);
         view! {
           <ExerciseDetailChecked
-            exercise=ex
+            exercise=current
             on_exercise_change=Callback::new(move |updated_exercise: Exercise| {
+              current.set(updated_exercise.clone());
               set_exercise.set(Some(updated_exercise));
             })
           />
@@ -103,6 +109,17 @@ fn ExerciseDetailChecked(
                 <div class="flex-1 mr-4">
                   <Title exercise=exercise on_title_change=handle_title_change />
                 </div>
+                // Fretboard drills in the tuning of this exercise
+                <a
+                  href=move || {
+                    let query = serde_urlencoded::to_string([("exercise", exercise.get().id)])
+                      .unwrap_or_default();
+                    format!("/fretboard_trainer?{query}")
+                  }
+                  class="py-1 px-3 text-sm text-blue-600 rounded border border-blue-600 hover:bg-blue-50"
+                >
+                  "Train Fretboard"
+                </a>
               </div>
 
               // Description section with inline editing
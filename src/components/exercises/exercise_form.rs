@@ -1,18 +1,26 @@
 use crate::components::exercises::{
-  constants::*, ExerciseTypeChangeConfirmation, ExerciseTypeSpecificFields,
+  constants::*, ChordProgressionFields, ExerciseTypeChangeConfirmation, ExerciseTypeSpecificFields,
+  SongFields, TechniqueFields,
 };
+use crate::components::fretboard::definitions::MAX_FRETS;
+use crate::components::tuning_selector::TuningSelector;
 use crate::models::{
-  exercise::{Exercise, ExerciseType},
+  exercise::{Exercise, ExerciseType, MAX_CAPO_FRET},
+  fretboard::use_instrument,
   repository::{get_exercise_repository, ExerciseRepository},
+  song::Song,
+  technique::Technique,
+};
+use crate::music::{
+  heptatonic_scales::HeptaScaleType, notes::Note, scales::ScaleType, ChordProgression,
 };
-use crate::music::{heptatonic_scales::HeptaScaleType, notes::Note, scales::ScaleType};
 use leptos::prelude::*;
 use web_sys::console;
 
 #[derive(Clone)]
 pub enum FormMode {
   Create,
-  Edit(Exercise),
+  Edit(Box<Exercise>),
 }
 
 #[component]
@@ -24,7 +32,7 @@ pub fn ExerciseForm(
   // Initialize form fields based on mode
   let (original_exercise, form_title) = match &mode {
     FormMode::Create => (None, "Create Exercise"),
-    FormMode::Edit(exercise) => (Some(exercise.clone()), "Edit Exercise"),
+    FormMode::Edit(exercise) => (Some((**exercise).clone()), "Edit Exercise"),
   };
 
   // Clone mode for use in closures
@@ -47,6 +55,15 @@ pub fn ExerciseForm(
       .unwrap_or_default(),
   );
 
+  let tuning = RwSignal::new(
+    original_exercise
+      .as_ref()
+      .map(|ex| ex.tuning.clone())
+      .unwrap_or_else(|| use_instrument().get_untracked().profile().default_tuning()),
+  );
+
+  let capo = RwSignal::new(original_exercise.as_ref().map_or(0, |ex| ex.capo));
+
   let (exercise_type_str, set_exercise_type_str) = signal(
     original_exercise
       .as_ref()
@@ -78,6 +95,30 @@ pub fn ExerciseForm(
   let (min_fret, set_min_fret) = signal(initial_fret_range.map(|(min, _)| min).unwrap_or(0));
   let (max_fret, set_max_fret) = signal(initial_fret_range.map(|(_, max)| max).unwrap_or(12));
 
+  let (progression_text, set_progression_text) = signal(
+    original_exercise
+      .as_ref()
+      .and_then(|ex| ex.exercise_type.get_chord_progression())
+      .map(|progression| progression.to_string())
+      .unwrap_or_default(),
+  );
+
+  let song = RwSignal::new(
+    original_exercise
+      .as_ref()
+      .and_then(|ex| ex.exercise_type.get_song())
+      .cloned()
+      .unwrap_or_default(),
+  );
+
+  let technique = RwSignal::new(
+    original_exercise
+      .as_ref()
+      .and_then(|ex| ex.exercise_type.get_technique())
+      .cloned()
+      .unwrap_or_default(),
+  );
+
   // Validation and error states
   let (errors, set_errors) = signal(Vec::<String>::new());
   let (show_type_change_warning, set_show_type_change_warning) = signal(false);
@@ -124,11 +165,46 @@ pub fn ExerciseForm(
         if min > max {
           validation_errors.push("Minimum fret cannot be greater than maximum fret".to_string());
         }
-        if min > 24 || max > 24 {
-          validation_errors.push("Fret numbers cannot exceed 24".to_string());
+        if min as usize >= MAX_FRETS || max as usize >= MAX_FRETS {
+          validation_errors.push(format!("Fret numbers cannot exceed {}", MAX_FRETS - 1));
         }
       }
 
+      if exercise_type == CHORD_PROGRESSION_TYPE {
+        if let Err(e) = progression_text.get().parse::<ChordProgression>() {
+          validation_errors.push(e);
+        }
+      }
+
+      if exercise_type == TECHNIQUE_TYPE {
+        technique.with(|technique: &Technique| {
+          if technique.start_bpm == 0 || technique.bpm_step == 0 {
+            validation_errors.push("Start BPM and BPM step must be greater than 0".to_string());
+          }
+          if technique.start_bpm > technique.target_bpm {
+            validation_errors.push("Start BPM cannot be greater than target BPM".to_string());
+          }
+          if technique.clean_reps_per_step == 0 {
+            validation_errors.push("Clean repetitions per step must be greater than 0".to_string());
+          }
+        });
+      }
+
+      if exercise_type == SONG_TYPE {
+        song.with(|song: &Song| {
+          if song
+            .sections
+            .iter()
+            .any(|section| section.name.trim().is_empty())
+          {
+            validation_errors.push("Every song section needs a name".to_string());
+          }
+          if song.sections.iter().any(|section| section.target_bpm == 0) {
+            validation_errors.push("Target BPM must be greater than 0".to_string());
+          }
+        });
+      }
+
       validation_errors
     }
   };
@@ -171,8 +247,11 @@ pub fn ExerciseForm(
         scale_type: scale_type.get(),
         fret_range: (min_fret.get(), max_fret.get()),
       },
-      SONG_TYPE => ExerciseType::Song,
-      _ => ExerciseType::Technique,
+      CHORD_PROGRESSION_TYPE => ExerciseType::ChordProgression {
+        progression: progression_text.get().parse().unwrap_or_default(),
+      },
+      SONG_TYPE => ExerciseType::Song(song.get()),
+      _ => ExerciseType::Technique(technique.get()),
     }
   };
 
@@ -188,11 +267,15 @@ pub fn ExerciseForm(
 
     let exercise = match &mode_for_save {
       FormMode::Create => Exercise::new(name.get().trim().to_string(), create_exercise_type())
-        .with_description(description.get().trim().to_string()),
+        .with_description(description.get().trim().to_string())
+        .with_tuning(tuning.get())
+        .with_capo(capo.get()),
       FormMode::Edit(original) => {
-        let mut updated = original.clone();
+        let mut updated = (**original).clone();
         updated.name = name.get().trim().to_string();
         updated.exercise_type = create_exercise_type();
+        updated.tuning = tuning.get();
+        updated.capo = capo.get();
         updated.description = if description.get().trim().is_empty() {
           None
         } else {
@@ -297,12 +380,45 @@ pub fn ExerciseForm(
             <option value=TRIAD_TYPE selected=move || exercise_type_str.get() == TRIAD_TYPE>
               Triad
             </option>
+            <option
+              value=CHORD_PROGRESSION_TYPE
+              selected=move || exercise_type_str.get() == CHORD_PROGRESSION_TYPE
+            >
+              "Chord Progression"
+            </option>
             <option value=SONG_TYPE selected=move || exercise_type_str.get() == SONG_TYPE>
               Song
             </option>
           </select>
         </div>
 
+        <div class="grid grid-cols-1 gap-4 md:grid-cols-4">
+          <div class="md:col-span-3">
+            <TuningSelector value=tuning on_tuning_change=Callback::new(move |t| tuning.set(t)) />
+          </div>
+          <div class="space-y-2">
+            <label class="block text-sm font-medium">"Capo"</label>
+            <select
+              class="p-2 w-full rounded-md border"
+              on:change=move |e| {
+                if let Ok(fret) = event_target_value(&e).parse::<u8>() {
+                  capo.set(fret);
+                }
+              }
+            >
+              {(0..=MAX_CAPO_FRET)
+                .map(|fret| {
+                  view! {
+                    <option value=fret.to_string() selected=move || capo.get() == fret>
+                      {if fret == 0 { "No capo".to_string() } else { format!("Fret {fret}") }}
+                    </option>
+                  }
+                })
+                .collect_view()}
+            </select>
+          </div>
+        </div>
+
         // Type-specific fields - kept as separate component (complex conditional logic)
         <ExerciseTypeSpecificFields
           exercise_type=exercise_type_str
@@ -315,6 +431,16 @@ pub fn ExerciseForm(
           max_fret=max_fret
           on_max_fret_change=Callback::new(move |fret| set_max_fret.set(fret))
         />
+
+        <ChordProgressionFields
+          exercise_type=exercise_type_str
+          progression_text
+          on_progression_text_change=Callback::new(move |text| set_progression_text.set(text))
+        />
+
+        <TechniqueFields exercise_type=exercise_type_str technique />
+
+        <SongFields exercise_type=exercise_type_str song />
       </div>
 
       // Action buttons - inline (just 2 buttons, simpler than separate component)
@@ -1,5 +1,6 @@
 use crate::{
-  components::fretboard::FretboardModelAdapter, models::fretboard::FretboardModelBuilder,
+  components::fretboard::{definitions::MAX_FRETS, FretboardModelAdapter},
+  models::fretboard::FretboardModelBuilder,
 };
 
 use super::{constants::*, PositionPresetButtons};
@@ -105,12 +106,12 @@ pub fn ExerciseTypeSpecificFields(
                 <input
                   type="number"
                   min="0"
-                  max="24"
+                  max=(MAX_FRETS - 1).to_string()
                   class="py-2 px-3 w-full rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none"
                   prop:value=move || min_fret.get().to_string()
                   on:input=move |e| {
                     if let Ok(val) = event_target_value(&e).parse::<u8>() {
-                      on_min_fret_change.run(val.min(24));
+                      on_min_fret_change.run(val.min(MAX_FRETS as u8 - 1));
                     }
                   }
                 />
@@ -120,12 +121,12 @@ pub fn ExerciseTypeSpecificFields(
                 <input
                   type="number"
                   min="0"
-                  max="24"
+                  max=(MAX_FRETS - 1).to_string()
                   class="py-2 px-3 w-full rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none"
                   prop:value=move || max_fret.get().to_string()
                   on:input=move |e| {
                     if let Ok(val) = event_target_value(&e).parse::<u8>() {
-                      on_max_fret_change.run(val.min(24));
+                      on_max_fret_change.run(val.min(MAX_FRETS as u8 - 1));
                     }
                   }
                 />
@@ -145,6 +146,7 @@ pub fn ExerciseTypeSpecificFields(
                     .start_fret(Signal::derive(move || min_fret.get() as usize))
                     .end_fret(Signal::derive(move || max_fret.get() as usize))
                     .build()
+                    .unwrap_or_default()
                 }) />
               </div>
             </div>
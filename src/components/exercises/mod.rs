@@ -1,3 +1,4 @@
+pub mod chord_progression_fields;
 pub mod constants;
 pub mod exercise_detail;
 pub mod exercise_form;
@@ -7,12 +8,17 @@ pub mod position_preset_buttons;
 mod practice_configuration_header;
 pub mod practice_session;
 pub mod practice_timer;
+pub mod song_fields;
+pub mod technique_fields;
 
 pub use practice_configuration_header::ConfigurationHeader;
 
+pub use chord_progression_fields::ChordProgressionFields;
 pub use constants::*;
 pub use exercise_detail::ExerciseDetail;
 pub use exercise_form::{ExerciseForm, FormMode};
 pub use exercise_type_change_confirmation::ExerciseTypeChangeConfirmation;
 pub use exercise_type_specific_fields::ExerciseTypeSpecificFields;
 pub use position_preset_buttons::PositionPresetButtons;
+pub use song_fields::SongFields;
+pub use technique_fields::TechniqueFields;
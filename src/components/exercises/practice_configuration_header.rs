@@ -21,11 +21,11 @@ pub fn ConfigurationHeader(
         <div class="flex gap-2 items-center">
           <span class="font-medium text-gray-700">"Type:"</span>
           <span class="py-1 px-2 text-xs font-medium text-blue-800 bg-blue-100 rounded">
-            {exercise.get().exercise_type.type_name()}
+            {move || exercise.get().exercise_type.type_name()}
           </span>
         </div>
 
-        {match exercise.get().exercise_type {
+        {move || match exercise.get().exercise_type {
           ExerciseType::Scale { root_note, scale_type, fret_range }
           | ExerciseType::Triad { root_note, scale_type, fret_range } => {
             view! {
@@ -64,7 +64,7 @@ pub fn ConfigurationHeader(
 
         <div class="flex gap-2 items-center">
           <span class="font-medium text-gray-700">"Details:"</span>
-          <span class="text-xs text-gray-600">{exercise.get().exercise_type.to_string()}</span>
+          <span class="text-xs text-gray-600">{move || exercise.get().exercise_type.to_string()}</span>
         </div>
 
       </div>
@@ -292,12 +292,12 @@ fn FretRangeSelection(
                   <input
                     type="number"
                     min="0"
-                    max="24"
+                    max=(MAX_FRETS - 1).to_string()
                     class="rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none"
                     prop:value=move || min_fret().to_string()
                     on:input=move |e| {
                       if let Ok(val) = event_target_value(&e).parse::<u8>() {
-                        fret_range.set((val, max_fret()));
+                        fret_range.set((val.min(MAX_FRETS as u8 - 1), max_fret()));
                       }
                     }
                   />
@@ -307,12 +307,12 @@ fn FretRangeSelection(
                   <input
                     type="number"
                     min="0"
-                    max="24"
+                    max=(MAX_FRETS - 1).to_string()
                     class="rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none"
                     prop:value=move || max_fret().to_string()
                     on:input=move |e| {
                       if let Ok(val) = event_target_value(&e).parse::<u8>() {
-                        fret_range.set((min_fret(), val.min(MAX_FRETS as u8)));
+                        fret_range.set((min_fret(), val.min(MAX_FRETS as u8 - 1)));
                       }
                     }
                   />
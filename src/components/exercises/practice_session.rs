@@ -1,14 +1,24 @@
-use crate::music::Scale;
+use crate::music::voicings::ChordVoicing;
+use crate::music::{ChordProgression, Scale, Tuning};
 use leptos::prelude::*;
 use leptos_use::use_interval_fn;
 use leptos_use::utils::Pausable;
 use std::time::Duration;
+use strum::IntoEnumIterator;
 
 use super::ConfigurationHeader;
+use crate::audio::{play_sequence, AudioManager};
+use crate::components::fretboard::definitions::MAX_FRETS;
 use crate::components::fretboard::FretboardModelAdapter;
 use crate::components::metronome::Metronome;
+use crate::components::music_selectors::FretLabelModeSelector;
 use crate::models::exercise::{Exercise, ExerciseType};
-use crate::models::fretboard::{FretboardModelBuilder, FretboardModelExt};
+use crate::models::fretboard::{
+  use_instrument, FretLabelMode, FretboardModel, FretboardModelBuilder, FretboardModelExt,
+  Instrument,
+};
+use crate::models::song::{MasteryLevel, Song};
+use crate::models::technique::Technique;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TimerState {
@@ -31,23 +41,53 @@ pub fn PracticeSession(
 ) -> impl IntoView {
   let elapsed_seconds = RwSignal::new(0u64);
   let timer_state = RwSignal::new(TimerState::Stopped);
+  let metronome_beat = RwSignal::new(None::<u64>);
+  let bpm = RwSignal::new(120u32);
+  let on_exercise_update = on_exercise_update.unwrap_or_else(|| Callback::new(|_| {}));
+  // The metronome and the tempo ladder both change the tempo through here
+  let set_bpm = Callback::new(move |new_bpm: u32| {
+    bpm.set(new_bpm);
+    if let Some(callback) = on_bpm_change {
+      callback.run(new_bpm);
+    }
+  });
+  let is_song =
+    Memo::new(move |_| exercise.with(|ex| matches!(ex.exercise_type, ExerciseType::Song(_))));
+  let is_technique =
+    Memo::new(move |_| exercise.with(|ex| matches!(ex.exercise_type, ExerciseType::Technique(_))));
 
   view! {
     <div class="p-6 bg-white rounded-lg border border-gray-200">
       <h3 class="mb-4 text-lg font-semibold text-gray-800">"Practice Session"</h3>
 
-      <ConfigurationHeader
-        exercise
-        on_exercise_update=on_exercise_update.unwrap_or_else(|| Callback::new(|_| {}))
-      />
+      <ConfigurationHeader exercise on_exercise_update />
 
       <div class="grid grid-cols-1 gap-6 lg:grid-cols-2">
         <TimerSection elapsed_seconds target_time timer_state />
 
-        <MetronomeSection on_bpm_change />
+        <MetronomeSection
+          bpm
+          on_bpm_change=set_bpm
+          on_beat=Callback::new(move |beat| metronome_beat.set(Some(beat)))
+        />
       </div>
 
-      <FretboardSection exercise />
+      <Show when=move || is_song.get()>
+        <SongPractice
+          exercise
+          elapsed_seconds
+          timer_state
+          on_bpm_change=set_bpm
+          metronome_beat
+          on_exercise_update
+        />
+      </Show>
+
+      <Show when=move || is_technique.get()>
+        <TempoLadder exercise bpm on_bpm_change=set_bpm on_exercise_update />
+      </Show>
+
+      <FretboardSection exercise metronome_beat bpm />
     </div>
   }
 }
@@ -204,17 +244,12 @@ fn TimerSection(
 }
 
 #[component]
-fn MetronomeSection(on_bpm_change: Option<Callback<u32>>) -> impl IntoView {
-  let (bpm, set_bpm) = signal(120u32); // Default 120 BPM
+fn MetronomeSection(
+  bpm: RwSignal<u32>,
+  on_bpm_change: Callback<u32>,
+  on_beat: Callback<u64>,
+) -> impl IntoView {
   let (show_metronome, set_show_metronome) = signal(true);
-  // Handle BPM changes from metronome
-  let bpm_change_callback = Callback::new(move |new_bpm: u32| {
-    set_bpm.set(new_bpm);
-    // Propagate BPM change to parent component if callback provided
-    if let Some(callback) = on_bpm_change {
-      callback.run(new_bpm);
-    }
-  });
   view! {
     <div>
       // Toggle metronome visibility
@@ -230,7 +265,8 @@ fn MetronomeSection(on_bpm_change: Option<Callback<u32>>) -> impl IntoView {
 
       {move || {
         if show_metronome.get() {
-          view! { <Metronome bpm=bpm on_bpm_change=bpm_change_callback /> }.into_any()
+          view! { <Metronome bpm=bpm.read_only() on_bpm_change on_beat /> }
+            .into_any()
         } else {
           view! {
             <div class="py-8 text-center text-gray-500">
@@ -245,65 +281,671 @@ fn MetronomeSection(on_bpm_change: Option<Callback<u32>>) -> impl IntoView {
   }
 }
 
+/// What the fretboard of the practice session shows for an exercise
+#[derive(Clone, PartialEq)]
+enum FretboardContent {
+  Scale,
+  ChordProgression {
+    progression: ChordProgression,
+    tuning: Tuning,
+    capo: usize,
+  },
+  None,
+}
+
+/// Scale overlay of scale and triad exercises, the fret range is relative to the capo
+#[derive(Clone, PartialEq)]
+struct ScaleOverlay {
+  scale: Scale,
+  fret_range: (u8, u8),
+  tuning: Tuning,
+  capo: usize,
+}
+
 #[component]
-fn FretboardSection(exercise: Signal<Exercise>) -> impl IntoView {
+fn FretboardSection(
+  exercise: Signal<Exercise>,
+  metronome_beat: RwSignal<Option<u64>>,
+  bpm: RwSignal<u32>,
+) -> impl IntoView {
   let (show_fretboard, set_show_fretboard) = signal(true);
+  let label_mode = RwSignal::new(FretLabelMode::default());
+
+  let content = Memo::new(move |_| {
+    exercise.with(|exercise| match &exercise.exercise_type {
+      ExerciseType::Scale { .. } | ExerciseType::Triad { .. } => FretboardContent::Scale,
+      ExerciseType::ChordProgression { progression } => FretboardContent::ChordProgression {
+        progression: progression.clone(),
+        tuning: exercise.tuning.clone(),
+        capo: exercise.capo as usize,
+      },
+      _ => FretboardContent::None,
+    })
+  });
+  let scale_overlay = Memo::new(move |_| {
+    exercise.with(|exercise| match exercise.exercise_type {
+      ExerciseType::Scale {
+        root_note,
+        scale_type,
+        fret_range,
+      }
+      | ExerciseType::Triad {
+        root_note,
+        scale_type,
+        fret_range,
+      } => Some(ScaleOverlay {
+        scale: Scale::new(root_note, scale_type),
+        fret_range,
+        tuning: exercise.tuning.clone(),
+        capo: exercise.capo as usize,
+      }),
+      _ => None,
+    })
+  });
+
+  // One model for the whole session, reconfigured in place so switching keys keeps the SVG
+  let fretboard_model = RwSignal::new(FretboardModel::default());
+  let instrument = use_instrument();
+  let apply_overlay =
+    move |overlay: Option<ScaleOverlay>, label_mode: FretLabelMode, instrument: Instrument| {
+      let Some(ScaleOverlay {
+        scale,
+        fret_range,
+        tuning,
+        capo,
+      }) = overlay
+      else {
+        return;
+      };
+      let drone = instrument.profile().drone_for(&tuning.notes);
+      fretboard_model.with_untracked(|model| {
+        let reconfigured = model
+          .set_drone(drone)
+          .and_then(|_| model.set_tuning(tuning.notes))
+          .and_then(|_| model.set_capo(capo))
+          .and_then(|_| {
+            model.set_fret_range(fret_range.0 as usize + capo, fret_range.1 as usize + capo)
+          });
+        match reconfigured {
+          Ok(()) => model.update_from_scale_with_labels(scale, label_mode),
+          Err(err) => {
+            leptos::logging::warn!("Can't show the exercise on the fretboard: {}", err);
+            model.hide_all_frets();
+          }
+        }
+      });
+    };
+  apply_overlay(
+    scale_overlay.get_untracked(),
+    label_mode.get_untracked(),
+    instrument.get_untracked(),
+  );
+  Effect::new(move |_| apply_overlay(scale_overlay.get(), label_mode.get(), instrument.get()));
+
+  // Plucks the scale, or the triad of a triad exercise, in the shown position up and back
+  // down, one note per metronome beat
+  let play_overlay = move || {
+    let Some(overlay) = scale_overlay.get_untracked() else {
+      return;
+    };
+    let arpeggio = exercise
+      .with_untracked(|exercise| matches!(exercise.exercise_type, ExerciseType::Triad { .. }));
+    let open_midis = instrument
+      .get_untracked()
+      .profile()
+      .open_string_midis(&overlay.tuning.notes);
+    let midis = fretboard_model.with_untracked(|model| {
+      model.playback_midis(&open_midis, |note| {
+        overlay.scale.contains_note(note)
+          && (!arpeggio || matches!(overlay.scale.degree_of(note), Some(1 | 3 | 5)))
+      })
+    });
+    let sequence: Vec<u8> = midis
+      .iter()
+      .chain(midis.iter().rev().skip(1))
+      .copied()
+      .collect();
+    let _ = AudioManager::resume();
+    if let Some(ctx) = AudioManager::get_context() {
+      play_sequence(&ctx, &sequence, bpm.get_untracked());
+    }
+  };
+
+  move || match content.get() {
+    FretboardContent::Scale => {
+      view! {
+        <div class="mt-6">
+          // Toggle fretboard visibility
+          <div class="flex justify-between items-center mb-3">
+            <h4 class="font-semibold text-gray-700 text-md">"Fretboard"</h4>
+            <div class="flex-1"></div>
+            <div class="mr-2 w-40">
+              <FretLabelModeSelector
+                value=label_mode
+                on_label_mode_changed=Callback::new(move |mode| label_mode.set(mode))
+                label=""
+                class="p-1 w-full text-xs rounded border"
+              />
+            </div>
+            <button
+              class="py-1 px-2 mr-2 text-xs bg-gray-200 rounded hover:bg-gray-300"
+              title="Play at the metronome tempo"
+              on:click=move |_| play_overlay()
+            >
+              "Play"
+            </button>
+            <button
+              class="py-1 px-2 text-xs bg-gray-200 rounded hover:bg-gray-300"
+              on:click=move |_| set_show_fretboard.update(|show| *show = !*show)
+            >
+              {move || if show_fretboard.get() { "Hide" } else { "Show" }}
+            </button>
+          </div>
+
+          <Show when=move || show_fretboard.get()>
+            <div class="p-4 bg-gray-50 rounded-lg">
+              <FretboardModelAdapter model=fretboard_model />
+            </div>
+          </Show>
+        </div>
+      }
+      .into_any()
+    }
+    FretboardContent::ChordProgression {
+      progression,
+      tuning,
+      capo,
+    } => view! { <ChordProgressionSection progression tuning capo metronome_beat /> }.into_any(),
+    FretboardContent::None => ().into_any(),
+  }
+}
+
+/// Shows the current and upcoming chord of a progression, driven by the metronome beats.
+/// With a capo the chords are shapes relative to the capo, as usual in songbooks.
+#[component]
+fn ChordProgressionSection(
+  progression: ChordProgression,
+  tuning: Tuning,
+  capo: usize,
+  metronome_beat: RwSignal<Option<u64>>,
+) -> impl IntoView {
+  let voicings = progression
+    .steps
+    .iter()
+    .map(|step| {
+      ChordVoicing::find(&step.chord, &tuning.notes)
+        .map(|v| v.with_capo(capo))
+        // A shape pushed past the last fret by the capo can't be shown either
+        .filter(|v| v.max_fret() < MAX_FRETS)
+    })
+    .collect::<Vec<_>>();
+  let highest_fret = voicings
+    .iter()
+    .flatten()
+    .map(ChordVoicing::max_fret)
+    .max()
+    .unwrap_or(0);
+
+  let fretboard_model = RwSignal::new(
+    FretboardModelBuilder::new()
+      .start_fret_val(0)
+      .end_fret_val(highest_fret.clamp(5, MAX_FRETS - 1))
+      .tuning(Signal::derive(move || tuning.notes.clone()))
+      .capo_val(capo)
+      .build()
+      .unwrap_or_else(|err| {
+        leptos::logging::warn!("Can't show the progression on the fretboard: {}", err);
+        FretboardModel::default()
+      }),
+  );
+
+  let current_idx = {
+    let progression = progression.clone();
+    Memo::new(move |_| {
+      progression
+        .step_index_at_beat(metronome_beat.get().unwrap_or(0))
+        .unwrap_or(0)
+    })
+  };
+  let next_idx = {
+    let progression = progression.clone();
+    Memo::new(move |_| progression.next_step_index(current_idx.get()).unwrap_or(0))
+  };
 
   {
-    move || {
-      match exercise.get().exercise_type {
-        ExerciseType::Scale {
-          root_note,
-          scale_type,
-          fret_range,
+    let voicings = voicings.clone();
+    Effect::new(move |_| {
+      let current = voicings.get(current_idx.get()).and_then(Option::as_ref);
+      let next = voicings.get(next_idx.get()).and_then(Option::as_ref);
+      fretboard_model.with_untracked(|model| match current {
+        Some(current) => {
+          model.update_from_voicings(current, next.filter(|_| next != Some(current)))
         }
-        | ExerciseType::Triad {
-          root_note,
-          scale_type,
-          fret_range,
-        } => {
+        None => model.hide_all_frets(),
+      });
+    });
+  }
+
+  let chord_name = {
+    let progression = progression.clone();
+    move |idx: usize| {
+      progression
+        .steps
+        .get(idx)
+        .map(|step| step.chord.to_string())
+        .unwrap_or_default()
+    }
+  };
+  let sounding_chord_name = {
+    let progression = progression.clone();
+    move || {
+      progression
+        .steps
+        .get(current_idx.get())
+        .map(|step| format!("Capo {capo} · sounds as {}", step.chord.transposed(capo)))
+    }
+  };
+  let current_chord_name = {
+    let chord_name = chord_name.clone();
+    move || chord_name(current_idx.get())
+  };
+  let missing_voicing = {
+    let chord_name = chord_name.clone();
+    let voicings = voicings.clone();
+    move || {
+      let idx = current_idx.get();
+      voicings
+        .get(idx)
+        .is_some_and(Option::is_none)
+        .then(|| format!("No voicing found for {} in this tuning", chord_name(idx)))
+    }
+  };
+  let next_chord_name = move || chord_name(next_idx.get());
+  let beat_progress = move || match metronome_beat.get() {
+    Some(beat) => {
+      let beat_in_step = progression.beat_within_step(beat).unwrap_or(0) + 1;
+      let beats = progression
+        .steps
+        .get(current_idx.get())
+        .map(|step| step.beats)
+        .unwrap_or(0);
+      format!("Beat {beat_in_step}/{beats}")
+    }
+    None => "Start the metronome to begin the chord changes".to_string(),
+  };
+
+  view! {
+    <div class="mt-6">
+      <h4 class="mb-3 font-semibold text-gray-700 text-md">"Chord Changes"</h4>
+      <div class="flex gap-8 justify-center items-end mb-3">
+        <div class="text-center">
+          <div class="text-xs text-gray-500">"Current"</div>
+          <div class="text-4xl font-bold text-green-700">{current_chord_name}</div>
+          <Show when=move || { capo > 0 }>
+            <div class="text-xs text-gray-500">{sounding_chord_name.clone()}</div>
+          </Show>
+        </div>
+        <div class="text-center">
+          <div class="text-xs text-gray-500">"Next"</div>
+          <div class="text-2xl font-semibold text-blue-700">{next_chord_name}</div>
+        </div>
+      </div>
+      <p class="mb-3 text-sm text-center text-gray-600">{beat_progress}</p>
+      {move || {
+        missing_voicing()
+          .map(|notice| view! { <p class="mb-3 text-sm text-center text-red-600">{notice}</p> })
+      }}
+      <div class="p-4 bg-gray-50 rounded-lg">
+        <FretboardModelAdapter model=fretboard_model />
+      </div>
+    </div>
+  }
+}
+
+/// Formats a practice duration, e.g. "4:05" or "1h 20m"
+fn format_practice_time(seconds: u64) -> String {
+  let hours = seconds / 3600;
+  let minutes = (seconds % 3600) / 60;
+  if hours > 0 {
+    format!("{hours}h {minutes:02}m")
+  } else {
+    format!("{minutes}:{:02}", seconds % 60)
+  }
+}
+
+/// Section picker for song exercises. Timer time is logged to the selected section
+/// whenever the timer stops or another section is picked.
+#[component]
+fn SongPractice(
+  exercise: Signal<Exercise>,
+  elapsed_seconds: RwSignal<u64>,
+  timer_state: RwSignal<TimerState>,
+  on_bpm_change: Callback<u32>,
+  metronome_beat: RwSignal<Option<u64>>,
+  on_exercise_update: Callback<Exercise>,
+) -> impl IntoView {
+  let song = Memo::new(move |_| {
+    exercise.with(|ex| ex.exercise_type.get_song().cloned().unwrap_or_default())
+  });
+  let selected_section = RwSignal::new(0usize);
+  let unlogged_seconds = RwSignal::new(0u64);
+  let last_elapsed = StoredValue::new(elapsed_seconds.get_untracked());
+
+  Effect::new(move |_| {
+    let elapsed = elapsed_seconds.get();
+    let previous = last_elapsed.get_value();
+    last_elapsed.set_value(elapsed);
+    if elapsed > previous && timer_state.get_untracked() == TimerState::Running {
+      unlogged_seconds.update(|seconds| *seconds += elapsed - previous);
+    }
+  });
+
+  let update_song = move |f: &dyn Fn(&mut Song)| {
+    let Some(mut ex) = exercise.try_get_untracked() else {
+      return;
+    };
+    if let ExerciseType::Song(song) = &mut ex.exercise_type {
+      f(song);
+      on_exercise_update.run(ex);
+    }
+  };
+
+  let log_unlogged_time = move || {
+    // Uses the `try_` accessors as this also runs on cleanup
+    let seconds = unlogged_seconds.try_get_untracked().unwrap_or_default();
+    if seconds > 0 {
+      unlogged_seconds.set(0);
+      let section_idx = selected_section.try_get_untracked().unwrap_or_default();
+      update_song(&|song| song.log_practice_time(section_idx, seconds));
+    }
+  };
+
+  Effect::new(move |_| {
+    if timer_state.get() != TimerState::Running {
+      log_unlogged_time();
+    }
+  });
+  on_cleanup(log_unlogged_time);
+
+  let select_section = move |section_idx: usize| {
+    log_unlogged_time();
+    selected_section.set(section_idx);
+    if let Some(section) = song.with_untracked(|song| song.sections.get(section_idx).cloned()) {
+      on_bpm_change.run(section.target_bpm);
+    }
+  };
+
+  let current_section =
+    Memo::new(move |_| song.with(|song| song.sections.get(selected_section.get()).cloned()));
+  let current_progression = Memo::new(move |_| {
+    current_section.with(|section| section.as_ref().and_then(|s| s.progression.clone()))
+  });
+
+  view! {
+    <div class="mt-6">
+      <h4 class="mb-3 font-semibold text-gray-700 text-md">"Song Sections"</h4>
+      {move || {
+        if song.with(|song| song.sections.is_empty()) {
           view! {
-            <div class="mt-6">
-              // Toggle fretboard visibility
-              <div class="flex justify-between items-center mb-3">
-                <h4 class="font-semibold text-gray-700 text-md">"Fretboard"</h4>
+            <p class="text-sm text-gray-500">"This song has no sections yet. Edit the exercise to add some."</p>
+          }
+            .into_any()
+        } else {
+          song
+            .get()
+            .sections
+            .into_iter()
+            .enumerate()
+            .map(|(section_idx, section)| {
+              view! {
+                <button
+                  class=move || {
+                    if selected_section.get() == section_idx {
+                      "m-1 py-1 px-3 text-sm rounded border-2 border-indigo-600 bg-indigo-600 text-white"
+                    } else {
+                      "m-1 py-1 px-3 text-sm rounded border border-gray-300 bg-gray-100 text-gray-700 hover:bg-gray-200"
+                    }
+                  }
+                  on:click=move |_| select_section(section_idx)
+                >
+                  {section.name}
+                  <span class="ml-2 text-xs opacity-75">
+                    {format!(
+                      "{} · {}",
+                      section.mastery,
+                      format_practice_time(section.practiced_seconds),
+                    )}
+                  </span>
+                </button>
+              }
+            })
+            .collect_view()
+            .into_any()
+        }
+      }}
+
+      {move || {
+        current_section
+          .get()
+          .map(|section| {
+            let section_idx = selected_section.get_untracked();
+            view! {
+              <div class="flex flex-wrap gap-4 items-center p-3 mt-3 text-sm bg-gray-50 rounded-lg">
+                <span>
+                  <span class="font-medium text-gray-700">"Target: "</span>
+                  {format!("{} BPM", section.target_bpm)}
+                </span>
                 <button
                   class="py-1 px-2 text-xs bg-gray-200 rounded hover:bg-gray-300"
-                  on:click=move |_| set_show_fretboard.update(|show| *show = !*show)
+                  on:click=move |_| on_bpm_change.run(section.target_bpm)
                 >
-                  {move || if show_fretboard.get() { "Hide" } else { "Show" }}
+                  "Use target BPM"
                 </button>
+                <label class="flex gap-2 items-center">
+                  <span class="font-medium text-gray-700">"Mastery:"</span>
+                  <select
+                    class="py-1 px-2 text-xs rounded border border-gray-300"
+                    on:change=move |e| {
+                      let value = event_target_value(&e);
+                      if let Some(level) = MasteryLevel::iter()
+                        .find(|level| level.to_string() == value)
+                      {
+                        update_song(
+                          &|song| {
+                            if let Some(section) = song.sections.get_mut(section_idx) {
+                              section.mastery = level;
+                            }
+                          },
+                        );
+                      }
+                    }
+                  >
+                    {MasteryLevel::iter()
+                      .map(|level| {
+                        view! {
+                          <option value=level.to_string() selected=level == section.mastery>
+                            {level.to_string()}
+                          </option>
+                        }
+                      })
+                      .collect_view()}
+                  </select>
+                </label>
+                <span>
+                  <span class="font-medium text-gray-700">"Practiced: "</span>
+                  {move || format_practice_time(
+                    section.practiced_seconds + unlogged_seconds.get(),
+                  )}
+                </span>
               </div>
+            }
+          })
+      }}
 
-              {move || {
-                if show_fretboard.get() {
-                  let fretboard_model = Memo::new(move |_| {
-                    let model = FretboardModelBuilder::new()
-                      .start_fret_val(fret_range.0 as usize)
-                      .end_fret_val(fret_range.1 as usize)
-                      .build();
-                    let current_scale = Scale::new(root_note, scale_type);
-                    model.update_from_scale(current_scale);
-                    model
-                  });
-                  // TODO bad for performance: initialize model once and just update
-
-                  view! {
-                    <div class="p-4 bg-gray-50 rounded-lg">
-                      <FretboardModelAdapter model=fretboard_model />
-                    </div>
-                  }
-                    .into_any()
-                } else {
-                  ().into_any()
-                }
-              }}
-            </div>
-          }
-          .into_any()
-        }
-        _ => ().into_any(),
+      {move || {
+        current_progression
+          .get()
+          .map(|progression| {
+            let (tuning, capo) = exercise.with_untracked(|ex| (ex.tuning.clone(), ex.capo as usize));
+            view! { <ChordProgressionSection progression tuning capo metronome_beat /> }
+          })
+      }}
+
+      {move || {
+        let song = song.get();
+        let has_details = !song.links.is_empty() || song.notes.is_some();
+        has_details
+          .then(|| {
+            view! {
+              <div class="p-3 mt-3 space-y-1 text-sm bg-gray-50 rounded-lg">
+                {song
+                  .notes
+                  .map(|notes| view! { <p class="text-gray-700 whitespace-pre-line">{notes}</p> })}
+                <ul>
+                  {song
+                    .links
+                    .into_iter()
+                    .map(|link| {
+                      view! {
+                        <li>
+                          <a
+                            href=link.clone()
+                            target="_blank"
+                            rel="noopener noreferrer"
+                            class="text-blue-600 underline"
+                          >
+                            {link.clone()}
+                          </a>
+                        </li>
+                      }
+                    })
+                    .collect_view()}
+                </ul>
+              </div>
+            }
+          })
+      }}
+    </div>
+  }
+}
+
+/// Tempo ladder for technique exercises: after enough clean repetitions the metronome
+/// moves up one step, and the highest cleanly played BPM is stored as best BPM.
+#[component]
+fn TempoLadder(
+  exercise: Signal<Exercise>,
+  bpm: RwSignal<u32>,
+  on_bpm_change: Callback<u32>,
+  on_exercise_update: Callback<Exercise>,
+) -> impl IntoView {
+  let technique = Memo::new(move |_| {
+    exercise.with(|ex| {
+      ex.exercise_type
+        .get_technique()
+        .cloned()
+        .unwrap_or_default()
+    })
+  });
+  let clean_reps = RwSignal::new(0u32);
+
+  let start_ladder = move || {
+    clean_reps.set(0);
+    on_bpm_change.run(technique.with_untracked(|technique| technique.start_bpm));
+  };
+  start_ladder();
+
+  let record_clean_bpm = move |clean_bpm: u32| {
+    let mut ex = exercise.get_untracked();
+    if let ExerciseType::Technique(technique) = &mut ex.exercise_type {
+      if technique.record_clean_bpm(clean_bpm) {
+        on_exercise_update.run(ex);
       }
     }
+  };
+
+  let on_clean_rep = move |_| {
+    clean_reps.update(|reps| *reps += 1);
+    let Technique {
+      clean_reps_per_step,
+      ..
+    } = technique.get_untracked();
+    if clean_reps.get_untracked() < clean_reps_per_step {
+      return;
+    }
+    clean_reps.set(0);
+    let current_bpm = bpm.get_untracked();
+    record_clean_bpm(current_bpm);
+    if let Some(next_bpm) =
+      technique.with_untracked(|technique| technique.next_ladder_bpm(current_bpm))
+    {
+      on_bpm_change.run(next_bpm);
+    }
+  };
+
+  let target_reached = move || technique.with(|technique| bpm.get() >= technique.target_bpm);
+
+  view! {
+    <div class="p-4 mt-6 bg-gray-50 rounded-lg">
+      <div class="flex justify-between items-center mb-3">
+        <h4 class="font-semibold text-gray-700 text-md">"Tempo Ladder"</h4>
+        <span class="text-sm text-gray-600">
+          {move || {
+            technique
+              .with(|technique| format!("{} · {}", technique.category, technique.subdivision))
+          }}
+        </span>
+      </div>
+      <div class="flex flex-wrap gap-4 items-center text-sm">
+        <span>
+          <span class="font-medium text-gray-700">"Current: "</span>
+          {move || format!("{} BPM", bpm.get())}
+        </span>
+        <span>
+          <span class="font-medium text-gray-700">"Target: "</span>
+          {move || format!("{} BPM", technique.with(|technique| technique.target_bpm))}
+        </span>
+        <span>
+          <span class="font-medium text-gray-700">"Best: "</span>
+          {move || {
+            technique
+              .with(|technique| technique.best_bpm)
+              .map_or("-".to_string(), |best| format!("{best} BPM"))
+          }}
+        </span>
+        <span>
+          <span class="font-medium text-gray-700">"Clean reps: "</span>
+          {move || {
+            format!(
+              "{}/{}",
+              clean_reps.get(),
+              technique.with(|technique| technique.clean_reps_per_step),
+            )
+          }}
+        </span>
+      </div>
+      <div class="flex gap-2 mt-3">
+        <button
+          class="py-1 px-3 text-sm text-white bg-green-600 rounded hover:bg-green-700"
+          on:click=on_clean_rep
+        >
+          "Clean Rep"
+        </button>
+        <button
+          class="py-1 px-3 text-sm text-white bg-red-500 rounded hover:bg-red-600"
+          on:click=move |_| clean_reps.set(0)
+        >
+          "Mistake"
+        </button>
+        <button
+          class="py-1 px-3 text-sm bg-gray-200 rounded hover:bg-gray-300"
+          on:click=move |_| start_ladder()
+        >
+          "Restart Ladder"
+        </button>
+      </div>
+      <Show when=target_reached>
+        <p class="mt-2 text-sm text-green-700">"Target tempo reached!"</p>
+      </Show>
+    </div>
   }
 }
@@ -0,0 +1,217 @@
+use leptos::prelude::*;
+use strum::IntoEnumIterator;
+
+use super::constants::SONG_TYPE;
+use crate::models::song::{MasteryLevel, Song, SongSection};
+use crate::music::ChordProgression;
+
+const DEFAULT_SECTION_BPM: u32 = 100;
+
+/// Form fields for the artist, sections, links and notes of a song exercise
+#[component]
+pub fn SongFields(exercise_type: ReadSignal<String>, song: RwSignal<Song>) -> impl IntoView {
+  let section_count = Memo::new(move |_| song.with(|song| song.sections.len()));
+
+  let add_section = move |_| {
+    song.update(|song| {
+      let name = format!("Section {}", song.sections.len() + 1);
+      song
+        .sections
+        .push(SongSection::new(name, DEFAULT_SECTION_BPM));
+    });
+  };
+
+  view! {
+    <Show when=move || exercise_type.get() == SONG_TYPE>
+      <div class="p-4 space-y-4 bg-gray-50 rounded-md">
+        <div>
+          <label class="block mb-1 text-sm font-medium text-gray-700">"Artist"</label>
+          <input
+            type="text"
+            class="py-2 px-3 w-full rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none"
+            prop:value=move || song.with(|song| song.artist.clone())
+            on:input=move |e| song.update(|song| song.artist = event_target_value(&e))
+            placeholder="Artist"
+          />
+        </div>
+
+        <div class="space-y-2">
+          <div class="flex justify-between items-center">
+            <label class="text-sm font-medium text-gray-700">"Sections"</label>
+            <button
+              type="button"
+              class="py-1 px-2 text-xs text-white bg-blue-500 rounded hover:bg-blue-600"
+              on:click=add_section
+            >
+              "+ Add Section"
+            </button>
+          </div>
+          {move || {
+            (0..section_count.get())
+              .map(|section_idx| view! { <SongSectionFields song section_idx /> })
+              .collect_view()
+          }}
+          <Show when=move || section_count.get() == 0>
+            <p class="text-xs text-gray-500">"Add sections like intro, verse or solo."</p>
+          </Show>
+        </div>
+
+        <div>
+          <label class="block mb-1 text-sm font-medium text-gray-700">"Links (one per line)"</label>
+          <textarea
+            class="py-2 px-3 w-full rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none"
+            prop:value=move || song.with(|song| song.links.join("\n"))
+            on:change=move |e| {
+              let links = event_target_value(&e)
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect();
+              song.update(|song| song.links = links);
+            }
+            placeholder="https://..."
+            rows="2"
+          />
+        </div>
+
+        <div>
+          <label class="block mb-1 text-sm font-medium text-gray-700">"Notes"</label>
+          <textarea
+            class="py-2 px-3 w-full rounded-md border border-gray-300 focus:ring-2 focus:ring-blue-500 focus:outline-none"
+            prop:value=move || song.with(|song| song.notes.clone().unwrap_or_default())
+            on:input=move |e| {
+              let notes = event_target_value(&e);
+              song.update(|song| song.notes = (!notes.trim().is_empty()).then_some(notes));
+            }
+            placeholder="Capo, tuning, tricky parts..."
+            rows="2"
+          />
+        </div>
+      </div>
+    </Show>
+  }
+}
+
+/// One editable row of the song section list
+#[component]
+fn SongSectionFields(song: RwSignal<Song>, section_idx: usize) -> impl IntoView {
+  let section = move || song.with(|song| song.sections.get(section_idx).cloned());
+  let update_section = move |f: &dyn Fn(&mut SongSection)| {
+    song.update(|song| {
+      if let Some(section) = song.sections.get_mut(section_idx) {
+        f(section);
+      }
+    });
+  };
+
+  let progression_text = RwSignal::new(
+    section()
+      .and_then(|section| section.progression)
+      .map(|progression| progression.to_string())
+      .unwrap_or_default(),
+  );
+  let progression_error = RwSignal::new(None::<String>);
+
+  let on_progression_input = move |text: String| {
+    progression_text.set(text.clone());
+    if text.trim().is_empty() {
+      progression_error.set(None);
+      update_section(&|section| section.progression = None);
+      return;
+    }
+    match text.parse::<ChordProgression>() {
+      Ok(progression) => {
+        progression_error.set(None);
+        update_section(&|section| section.progression = Some(progression.clone()));
+      }
+      Err(err) => progression_error.set(Some(err)),
+    }
+  };
+
+  view! {
+    <div class="p-2 space-y-2 bg-white rounded border border-gray-200">
+      <div class="flex flex-wrap gap-2 items-end">
+        <label class="flex flex-col flex-1 text-xs text-gray-600">
+          "Name"
+          <input
+            type="text"
+            class="py-1 px-2 text-sm rounded border border-gray-300"
+            prop:value=move || section().map(|section| section.name).unwrap_or_default()
+            on:input=move |e| {
+              let name = event_target_value(&e);
+              update_section(&|section| section.name = name.clone());
+            }
+          />
+        </label>
+        <label class="flex flex-col w-24 text-xs text-gray-600">
+          "Target BPM"
+          <input
+            type="number"
+            min="30"
+            max="250"
+            class="py-1 px-2 text-sm rounded border border-gray-300"
+            prop:value=move || section().map(|section| section.target_bpm).unwrap_or_default()
+            on:input=move |e| {
+              if let Ok(bpm) = event_target_value(&e).parse::<u32>() {
+                update_section(&|section| section.target_bpm = bpm);
+              }
+            }
+          />
+        </label>
+        <label class="flex flex-col text-xs text-gray-600">
+          "Mastery"
+          <select
+            class="py-1 px-2 text-sm rounded border border-gray-300"
+            on:change=move |e| {
+              let value = event_target_value(&e);
+              if let Some(level) = MasteryLevel::iter().find(|level| level.to_string() == value) {
+                update_section(&|section| section.mastery = level);
+              }
+            }
+          >
+            {MasteryLevel::iter()
+              .map(|level| {
+                view! {
+                  <option
+                    value=level.to_string()
+                    selected=move || section().map(|section| section.mastery) == Some(level)
+                  >
+                    {level.to_string()}
+                  </option>
+                }
+              })
+              .collect_view()}
+          </select>
+        </label>
+        <button
+          type="button"
+          class="py-1 px-2 text-xs text-red-500 hover:text-red-700"
+          on:click=move |_| {
+            song
+              .update(|song| {
+                if section_idx < song.sections.len() {
+                  song.sections.remove(section_idx);
+                }
+              })
+          }
+        >
+          "Remove"
+        </button>
+      </div>
+      <label class="flex flex-col text-xs text-gray-600">
+        "Chords (optional)"
+        <input
+          type="text"
+          class="py-1 px-2 font-mono text-sm rounded border border-gray-300"
+          prop:value=move || progression_text.get()
+          on:input=move |e| on_progression_input(event_target_value(&e))
+          placeholder="G:4 D:4 Em:4 C:4"
+        />
+      </label>
+      {move || {
+        progression_error.get().map(|err| view! { <p class="text-xs text-red-600">{err}</p> })
+      }}
+    </div>
+  }
+}
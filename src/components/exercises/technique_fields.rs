@@ -0,0 +1,107 @@
+use leptos::prelude::*;
+use strum::IntoEnumIterator;
+
+use super::constants::TECHNIQUE_TYPE;
+use crate::models::technique::{Subdivision, Technique, TechniqueCategory};
+
+/// Form fields for the category, tempo ladder and subdivision of a technique exercise
+#[component]
+pub fn TechniqueFields(
+  exercise_type: ReadSignal<String>,
+  technique: RwSignal<Technique>,
+) -> impl IntoView {
+  let number_input =
+    move |label: &'static str, get: fn(&Technique) -> u32, set: fn(&mut Technique, u32)| {
+      view! {
+        <label class="flex flex-col text-xs text-gray-600">
+          {label}
+          <input
+            type="number"
+            min="1"
+            class="py-1 px-2 text-sm rounded border border-gray-300"
+            prop:value=move || technique.with(get)
+            on:input=move |e| {
+              if let Ok(value) = event_target_value(&e).parse::<u32>() {
+                technique.update(|technique| set(technique, value));
+              }
+            }
+          />
+        </label>
+      }
+    };
+
+  view! {
+    <Show when=move || exercise_type.get() == TECHNIQUE_TYPE>
+      <div class="p-4 space-y-4 bg-gray-50 rounded-md">
+        <div class="grid grid-cols-2 gap-4">
+          <label class="flex flex-col text-xs text-gray-600">
+            "Category"
+            <select
+              class="py-1 px-2 text-sm rounded border border-gray-300"
+              on:change=move |e| {
+                let value = event_target_value(&e);
+                if let Some(category) = TechniqueCategory::iter()
+                  .find(|category| category.to_string() == value)
+                {
+                  technique.update(|technique| technique.category = category);
+                }
+              }
+            >
+              {TechniqueCategory::iter()
+                .map(|category| {
+                  view! {
+                    <option
+                      value=category.to_string()
+                      selected=move || technique.with(|technique| technique.category == category)
+                    >
+                      {category.to_string()}
+                    </option>
+                  }
+                })
+                .collect_view()}
+            </select>
+          </label>
+          <label class="flex flex-col text-xs text-gray-600">
+            "Subdivision"
+            <select
+              class="py-1 px-2 text-sm rounded border border-gray-300"
+              on:change=move |e| {
+                let value = event_target_value(&e);
+                if let Some(subdivision) = Subdivision::iter()
+                  .find(|subdivision| subdivision.to_string() == value)
+                {
+                  technique.update(|technique| technique.subdivision = subdivision);
+                }
+              }
+            >
+              {Subdivision::iter()
+                .map(|subdivision| {
+                  view! {
+                    <option
+                      value=subdivision.to_string()
+                      selected=move || {
+                        technique.with(|technique| technique.subdivision == subdivision)
+                      }
+                    >
+                      {subdivision.to_string()}
+                    </option>
+                  }
+                })
+                .collect_view()}
+            </select>
+          </label>
+        </div>
+
+        <div class="grid grid-cols-2 gap-4 md:grid-cols-4">
+          {number_input("Start BPM", |t| t.start_bpm, |t, v| t.start_bpm = v)}
+          {number_input("Target BPM", |t| t.target_bpm, |t, v| t.target_bpm = v)}
+          {number_input("BPM Step", |t| t.bpm_step, |t, v| t.bpm_step = v)}
+          {number_input("Clean Reps per Step", |t| t.clean_reps_per_step, |t, v| t.clean_reps_per_step = v)}
+        </div>
+        <p class="text-xs text-gray-500">
+          "The tempo ladder raises the metronome by the BPM step after the given number of clean repetitions."
+        </p>
+      </div>
+    </Show>
+  }
+}
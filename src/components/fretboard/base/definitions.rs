@@ -1,37 +1,153 @@
-use std::collections::HashMap;
+use std::fmt;
+
+use strum_macros::EnumIter;
 
 use crate::music::Note;
-use leptos::prelude::RwSignal;
 
-// Upper bounds used for preallocation; keeps per-cell signals stable (never created inside Effects).
+// Upper bounds of the preallocated fret state grid; keeps per-cell signals stable (never created inside Effects).
 // Adjust if you need more strings/frets; existing UI sliders should clamp within these maxima.
-pub const MAX_STRINGS: usize = 8; // supports up to 8-string instruments
-pub const MAX_FRETS: usize = 25; // frets 0..=24
-
-// todo consider performance
-pub type FretStateSignals = HashMap<FretCoord, RwSignal<FretState>>;
+pub const MAX_STRINGS: usize = 12; // supports up to 12-string instruments
+pub const MAX_FRETS: usize = 28; // frets 0..=27, for extended-range guitars
+/// Number of colour steps of [`FretRole::Heat`]
+pub const HEAT_LEVELS: u8 = 5;
 
-#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct FretCoord {
   pub string_idx: u8,
   pub fret_idx: u8,
 }
 
+/// A string that starts at a fret instead of the nut, like the short fifth string of a
+/// 5-string banjo. Its open note sounds at `first_fret`, there are no frets below it.
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+pub struct DroneString {
+  pub string_idx: u8,
+  pub first_fret: usize,
+}
+
+impl DroneString {
+  /// Semitones above the open string at a position, `None` on the drone below its first
+  /// fret. Other strings are fretted all the way from the nut.
+  pub fn steps_at(drone: Option<DroneString>, coord: FretCoord) -> Option<usize> {
+    let fret_idx = coord.fret_idx as usize;
+    match drone {
+      Some(drone) if drone.string_idx == coord.string_idx => fret_idx.checked_sub(drone.first_fret),
+      _ => Some(fret_idx),
+    }
+  }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct FretClickEvent {
   pub note: Note,
   pub coord: FretCoord,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
-pub enum FretStateColor {
-  Red,
-  Green,
-  Blue,
+/// What a shown note means. The colour of a role is picked by the fretboard theme.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, EnumIter)]
+pub enum FretRole {
+  Root,
+  ChordTone,
+  ScaleTone,
+  /// The note the player is asked to find
+  Target,
+  /// Feedback for a wrong answer
+  Wrong,
+  /// Faint preview, e.g. the next chord
+  Ghost,
+  /// Step of a heatmap, from 0 (weak) to `HEAT_LEVELS - 1` (strong)
+  Heat(u8),
 }
 
+impl fmt::Display for FretRole {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FretRole::Root => write!(f, "Root"),
+      FretRole::ChordTone => write!(f, "Chord Tone"),
+      FretRole::ScaleTone => write!(f, "Scale Tone"),
+      FretRole::Target => write!(f, "Target"),
+      FretRole::Wrong => write!(f, "Wrong"),
+      FretRole::Ghost => write!(f, "Ghost"),
+      FretRole::Heat(level) => write!(f, "Heat {level}"),
+    }
+  }
+}
+
+/// What is drawn at a fret. Shapes carry the role that picks their colour and a label.
 #[derive(Clone, PartialEq, Debug)]
 pub enum FretState {
   Hidden,
-  Normal(FretStateColor, String),
+  /// Filled circle
+  Normal(FretRole, String),
+  /// Outlined circle, e.g. the next chord next to the current one
+  Hollow(FretRole, String),
+  /// Filled square, e.g. roots
+  Square(FretRole, String),
+  /// Diamond, e.g. natural harmonics
+  Diamond(FretRole, String),
+  /// Semi-transparent circle with a dashed outline
+  Ghost(FretRole, String),
+  /// "x" for a string that is not played
+  Muted,
+}
+
+impl FretState {
+  pub fn role(&self) -> Option<FretRole> {
+    match self {
+      FretState::Normal(role, _)
+      | FretState::Hollow(role, _)
+      | FretState::Square(role, _)
+      | FretState::Diamond(role, _)
+      | FretState::Ghost(role, _) => Some(*role),
+      FretState::Hidden | FretState::Muted => None,
+    }
+  }
+
+  pub fn label(&self) -> Option<&str> {
+    match self {
+      FretState::Normal(_, label)
+      | FretState::Hollow(_, label)
+      | FretState::Square(_, label)
+      | FretState::Diamond(_, label)
+      | FretState::Ghost(_, label) => Some(label),
+      FretState::Hidden | FretState::Muted => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_fret_state_role_and_label() {
+    let shapes: [fn(FretRole, String) -> FretState; 5] = [
+      FretState::Normal,
+      FretState::Hollow,
+      FretState::Square,
+      FretState::Diamond,
+      FretState::Ghost,
+    ];
+    for (shape, role) in shapes.into_iter().zip([
+      FretRole::Root,
+      FretRole::ChordTone,
+      FretRole::ScaleTone,
+      FretRole::Target,
+      FretRole::Heat(2),
+    ]) {
+      let state = shape(role, "b3".to_string());
+      assert_eq!(state.role(), Some(role), "{state:?}");
+      assert_eq!(state.label(), Some("b3"), "{state:?}");
+    }
+
+    assert_eq!(
+      FretState::Square(FretRole::Root, String::new()).label(),
+      Some("")
+    );
+
+    for state in [FretState::Hidden, FretState::Muted] {
+      assert_eq!(state.role(), None, "{state:?}");
+      assert_eq!(state.label(), None, "{state:?}");
+    }
+  }
 }
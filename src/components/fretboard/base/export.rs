@@ -0,0 +1,381 @@
+use leptos::prelude::*;
+
+use super::definitions::{DroneString, FretCoord, FretState};
+use super::helper::{calculate_string_spacing, string_thickness, DRONE_PEG_RADIUS, SVG_WIDTH};
+use super::layout::{
+  polygon_points, string_y, BoardLayout, CapoShape, FretGeometry, OrientationTransform,
+  ViewboxMapping,
+};
+use super::shapes::{FretWireStyle, NoteDrawing, NoteShape};
+use crate::components::fretboard::theme::FretboardTheme;
+use crate::components::fretboard::visual_config::{
+  FretboardOrientation, FretboardVisualConfig, Multiscale,
+};
+
+/// Plain snapshot of a fretboard and its fret states.
+///
+/// Renders the same drawing as the [`super::Fretboard`] component into a standalone SVG,
+/// without the reactive runtime or a browser.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FretboardDiagram {
+  pub start_fret: usize,
+  pub end_fret: usize,
+  pub num_strings: u8,
+  pub capo: usize,
+  pub drone: Option<DroneString>,
+  /// Shown frets, hidden frets can be left out
+  pub fret_states: Vec<(FretCoord, FretState)>,
+  pub svg_aspect_ratio: f64,
+  pub fret_margin_percentage: f64,
+  pub nut_width: f64,
+  pub extra_frets: usize,
+  pub marker_positions: Vec<usize>,
+  pub orientation: FretboardOrientation,
+  pub theme: FretboardTheme,
+  pub multiscale: Option<Multiscale>,
+}
+
+impl FretboardDiagram {
+  /// Takes the display settings from the current values of `config`
+  pub fn new(
+    start_fret: usize,
+    end_fret: usize,
+    num_strings: u8,
+    capo: usize,
+    drone: Option<DroneString>,
+    fret_states: Vec<(FretCoord, FretState)>,
+    config: &FretboardVisualConfig,
+  ) -> Self {
+    Self {
+      start_fret,
+      end_fret,
+      num_strings,
+      capo,
+      drone,
+      fret_states,
+      svg_aspect_ratio: config.svg_aspect_ratio.get_untracked(),
+      fret_margin_percentage: config.fret_margin_percentage.get_untracked(),
+      nut_width: config.nut_width.get_untracked(),
+      extra_frets: config.extra_frets.get_untracked(),
+      marker_positions: config.marker_positions.get_untracked(),
+      orientation: config.orientation.get_untracked(),
+      theme: config.theme.get_untracked(),
+      multiscale: config.multiscale.get_untracked(),
+    }
+  }
+
+  pub fn to_svg(&self) -> String {
+    let width = SVG_WIDTH;
+    let height = width / self.svg_aspect_ratio;
+    let margin = height * self.fret_margin_percentage;
+    let min_visible_fret = self.start_fret.saturating_sub(self.extra_frets + 1);
+    let max_visible_fret = self.end_fret + self.extra_frets;
+    let geometry = FretGeometry::new(
+      width,
+      max_visible_fret as u8 + 2,
+      self.num_strings,
+      self.multiscale,
+    );
+    let has_nut = min_visible_fret == 0;
+    let mapping = ViewboxMapping::new(
+      &geometry,
+      min_visible_fret,
+      max_visible_fret,
+      has_nut,
+      width,
+      self.nut_width,
+    );
+    let board_layout = BoardLayout {
+      geometry,
+      mapping,
+      string_spacing: calculate_string_spacing(self.num_strings, height),
+      top: margin,
+      bottom: height - margin,
+    };
+    let orientation = OrientationTransform::new(self.orientation, width, height);
+    let (viewbox_width, viewbox_height) = orientation.viewbox_size(width, height);
+    let board = self.theme.board_colors();
+
+    let mut svg = String::new();
+    let mut w = |s: String| svg.push_str(&s);
+
+    let gradient_end = if orientation.is_vertical() {
+      r#"x2="0" y2="1""#
+    } else {
+      r#"x2="1" y2="0""#
+    };
+    w(format!(
+      r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {viewbox_width} {viewbox_height}" width="{viewbox_width}" height="{viewbox_height}">"#
+    ));
+    w(format!(
+      r#"<defs><linearGradient id="board" x1="0" y1="0" {gradient_end}><stop offset="0" stop-color="{}"/><stop offset="1" stop-color="{}"/></linearGradient></defs>"#,
+      board.background_start, board.background_end
+    ));
+    w(format!(
+      r#"<rect width="{viewbox_width}" height="{viewbox_height}" rx="8" fill="url(#board)"/>"#
+    ));
+    w(format!(
+      r#"<g transform="{}">"#,
+      orientation.svg_transform()
+    ));
+
+    if let Some(nut) = board_layout.nut() {
+      w(format!(
+        r#"<polygon points="{}" fill="{}" stroke="{}" stroke-width="5" stroke-linejoin="round"/>"#,
+        polygon_points(&nut),
+        board.nut_fill,
+        board.nut_stroke
+      ));
+    }
+
+    for fret_no in min_visible_fret..max_visible_fret {
+      let Some(((x1, y1), (x2, y2))) = board_layout.fret_line(fret_no) else {
+        continue;
+      };
+      let style = FretWireStyle::new(fret_no, self.start_fret, self.end_fret, board);
+      w(format!(
+        r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{}" stroke-width="{}" opacity="{}"/>"#,
+        style.color, style.width, style.opacity
+      ));
+    }
+
+    for string_no in 0..self.num_strings {
+      let Some(x1) = board_layout.string_start_x(string_no, self.drone) else {
+        continue;
+      };
+      let y = string_y(string_no, board_layout.string_spacing);
+      w(format!(
+        r#"<line x1="{x1}" y1="{y}" x2="{width}" y2="{y}" stroke="{}" stroke-width="{}"/>"#,
+        board.string,
+        string_thickness(string_no, self.num_strings)
+      ));
+      if x1 > 0.0 {
+        w(format!(
+          r#"<circle cx="{x1}" cy="{y}" r="{DRONE_PEG_RADIUS}" fill="{}"/>"#,
+          board.string
+        ));
+      }
+    }
+
+    for fret in
+      (min_visible_fret..=max_visible_fret).filter(|fret| self.marker_positions.contains(fret))
+    {
+      for dot in board_layout.marker_dots(fret).into_iter().flatten() {
+        w(format!(
+          r#"<circle cx="{}" cy="{}" r="{}" fill="{}" opacity="{}"/>"#,
+          dot.cx, dot.cy, dot.r, board.marker, dot.opacity
+        ));
+      }
+    }
+
+    for area in board_layout.inactive_areas(
+      self.start_fret,
+      self.end_fret,
+      min_visible_fret,
+      max_visible_fret,
+      width,
+    ) {
+      w(format!(
+        r#"<polygon points="{}" fill="{}" opacity="0.5"/>"#,
+        polygon_points(&area),
+        board.overlay
+      ));
+    }
+
+    if let Some(CapoShape { shade, bar }) =
+      board_layout.capo(self.capo, min_visible_fret, max_visible_fret)
+    {
+      w(format!(
+        r#"<polygon points="{}" fill="{}" opacity="0.45"/><polygon points="{}" fill="{bar_color}" stroke="{bar_color}" stroke-width="4" stroke-linejoin="round" opacity="0.9"/>"#,
+        polygon_points(&shade),
+        board.capo_shade,
+        polygon_points(&bar),
+        bar_color = board.capo_bar,
+      ));
+    }
+
+    let mut fret_states: Vec<_> = self
+      .fret_states
+      .iter()
+      .filter(|(coord, _)| {
+        let fret = coord.fret_idx as usize;
+        coord.string_idx < self.num_strings && fret >= min_visible_fret && fret < max_visible_fret
+      })
+      .collect();
+    fret_states.sort_by_key(|(coord, _)| (coord.fret_idx, coord.string_idx));
+    for (coord, state) in fret_states {
+      let Some((x, y)) = board_layout.note_position(*coord) else {
+        continue;
+      };
+      if let Some(note) = NoteDrawing::new(state, self.theme, x, y) {
+        w(note_svg(x, y, note, &orientation));
+      }
+    }
+
+    svg.push_str("</g></svg>");
+    svg
+  }
+}
+
+/// Markup of a note, drawn like `FretboardNote`
+fn note_svg(x: f64, y: f64, note: NoteDrawing, orientation: &OrientationTransform) -> String {
+  let attribute = |name: &str, value: Option<String>| {
+    value.map_or(String::new(), |value| format!(r#" {name}="{value}""#))
+  };
+  let shape = match note.shape {
+    NoteShape::Circle {
+      cx,
+      cy,
+      r,
+      fill,
+      opacity,
+      stroke,
+      stroke_width,
+      stroke_dasharray,
+    } => format!(
+      r#"<circle cx="{cx}" cy="{cy}" r="{r}" fill="{fill}"{}{}{}{}/>"#,
+      attribute("opacity", opacity.map(|opacity| opacity.to_string())),
+      attribute("stroke", stroke.map(str::to_string)),
+      attribute("stroke-width", stroke_width.map(str::to_string)),
+      attribute("stroke-dasharray", stroke_dasharray.map(str::to_string)),
+    ),
+    NoteShape::Square {
+      x,
+      y,
+      size,
+      fill,
+      opacity,
+    } => format!(
+      r#"<rect x="{x}" y="{y}" width="{size}" height="{size}" rx="2" fill="{fill}" opacity="{opacity}"/>"#
+    ),
+    NoteShape::Diamond {
+      points,
+      fill,
+      opacity,
+    } => format!(r#"<polygon points="{points}" fill="{fill}" opacity="{opacity}"/>"#),
+    NoteShape::Cross { path, halo, color } => format!(
+      r#"<path d="{path}" stroke="{halo}" stroke-width="6" stroke-linecap="round"/><path d="{path}" stroke="{color}" stroke-width="3" stroke-linecap="round"/>"#
+    ),
+  };
+
+  let label = note.label.map_or(String::new(), |(text, text_color)| {
+    format!(
+      r#"<text x="{x}" y="{y}" transform="{}" text-anchor="middle" dominant-baseline="central" fill="{text_color}" font-family="sans-serif" font-size="8" font-weight="bold">{}</text>"#,
+      orientation.upright_at(x, y),
+      escape_xml(&text)
+    )
+  });
+  shape + &label
+}
+
+fn escape_xml(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::components::fretboard::{FretRole, FretboardDirection};
+
+  fn diagram(fret_states: Vec<(FretCoord, FretState)>) -> FretboardDiagram {
+    FretboardDiagram {
+      start_fret: 0,
+      end_fret: 5,
+      num_strings: 6,
+      capo: 0,
+      drone: None,
+      fret_states,
+      svg_aspect_ratio: 3.0,
+      fret_margin_percentage: 0.05,
+      nut_width: 14.0,
+      extra_frets: 1,
+      marker_positions: vec![3, 5],
+      orientation: FretboardOrientation::default(),
+      theme: FretboardTheme::default(),
+      multiscale: None,
+    }
+  }
+
+  fn coord(string_idx: u8, fret_idx: u8) -> FretCoord {
+    FretCoord {
+      string_idx,
+      fret_idx,
+    }
+  }
+
+  #[test]
+  fn test_svg_contains_board_and_notes() {
+    let svg = diagram(vec![
+      (coord(0, 3), FretState::Square(FretRole::Root, "G".into())),
+      (
+        coord(1, 5),
+        FretState::Normal(FretRole::ScaleTone, "E".into()),
+      ),
+      // Outside of the visible frets
+      (
+        coord(2, 20),
+        FretState::Normal(FretRole::ScaleTone, "X".into()),
+      ),
+    ])
+    .to_svg();
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 800 "));
+    assert!(svg.ends_with("</svg>"));
+    // 6 strings and the frets up to the last visible one
+    assert_eq!(svg.matches("<line ").count(), 6 + 6);
+    assert_eq!(svg.matches("<text").count(), 2);
+    assert!(svg.contains(">G</text>"));
+    assert!(!svg.contains(">X</text>"));
+  }
+
+  #[test]
+  fn test_vertical_svg_swaps_size_and_escapes_labels() {
+    let mut vertical = diagram(vec![(
+      coord(0, 1),
+      FretState::Normal(FretRole::Target, "<b&>".into()),
+    )]);
+    vertical.orientation.direction = FretboardDirection::Vertical;
+    let svg = vertical.to_svg();
+    assert!(svg.contains("width=\"266.6666666666667\" height=\"800\""));
+    assert!(svg.contains(">&lt;b&amp;&gt;</text>"));
+  }
+
+  /// Fret wires whose ends don't share an x coordinate, strings are the horizontal lines
+  fn slanted_frets(svg: &str) -> usize {
+    let attribute = |line: &str, name: &str| -> String {
+      let start = line.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+      line[start..].split('"').next().unwrap().to_string()
+    };
+    svg
+      .split("<line ")
+      .skip(1)
+      .map(|line| format!(" {line}"))
+      .filter(|line| attribute(line, "y1") != attribute(line, "y2"))
+      .filter(|line| attribute(line, "x1") != attribute(line, "x2"))
+      .count()
+  }
+
+  #[test]
+  fn test_multiscale_svg_has_fanned_frets() {
+    let straight = diagram(vec![]);
+    assert_eq!(slanted_frets(&straight.to_svg()), 0);
+
+    let fanned = FretboardDiagram {
+      num_strings: 8,
+      multiscale: Some(Multiscale {
+        bass_scale_length: 28.0,
+        treble_scale_length: 25.5,
+        perpendicular_fret: 3,
+      }),
+      ..diagram(vec![])
+    };
+    let svg = fanned.to_svg();
+    // Only the perpendicular fret stays upright
+    assert_eq!(svg.matches("<line ").count(), 8 + 6);
+    assert_eq!(slanted_frets(&svg), 5);
+  }
+}
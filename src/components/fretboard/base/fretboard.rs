@@ -1,20 +1,21 @@
 use crate::components::fretboard::{
   base::{
-    helper::{calculate_fret_positions, calculate_string_spacing},
-    layout::LayoutSnapshot,
+    helper::{calculate_string_spacing, SVG_WIDTH},
+    layout::{FretGeometry, LayoutSnapshot, OrientationTransform},
     parts::{
-      FretboardFrets, FretboardGrid, FretboardMarkers, FretboardNut, FretboardOverlays,
-      FretboardStrings,
+      FretboardCapo, FretboardFrets, FretboardGrid, FretboardMarkers, FretboardNut,
+      FretboardOverlays, FretboardStrings,
     },
   },
+  theme::BoardColors,
   visual_config::FretboardVisualConfig,
 };
 
-use super::{FretClickEvent, FretStateSignals};
+use super::{DroneString, FretClickEvent, FretStateGrid};
 use crate::music::Note;
 use leptos::prelude::*;
 
-/// The FretStateSignals have to be carefully managed
+/// The FretStateGrid has to be carefully managed
 /// Use FretboardViewModel by passing a FretboardModel to ensure proper updates
 #[component]
 pub fn Fretboard(
@@ -27,6 +28,12 @@ pub fn Fretboard(
   /// Number of guitar strings (default: 6)
   #[prop(into)]
   tuning: Signal<Vec<Note>>,
+  /// Fret the capo is placed on, 0 means no capo
+  #[prop(optional, into)]
+  capo: Signal<usize>,
+  /// Short string that starts up the neck, e.g. the fifth string of a banjo
+  #[prop(optional, into)]
+  drone: Signal<Option<DroneString>>,
   /// Visual configuration for fretboard display properties
   #[prop(into)]
   config: Signal<FretboardVisualConfig>,
@@ -34,16 +41,16 @@ pub fn Fretboard(
   #[prop(into)]
   on_note_clicked: Signal<Option<Callback<FretClickEvent>>>,
 
-  /// The fret states must be prallocated. Use [`crate::models::fretboard_model::get_preallocated_fret_states()`] to create initial fret_states
+  /// Fret states of all cells, see [`FretStateGrid`]
   #[prop(into)]
-  fret_states: Signal<FretStateSignals>,
+  fret_states: Signal<FretStateGrid>,
 ) -> impl IntoView {
   // Create reactive signals from config values - using clone since Signal is Copy
 
   let num_strings: Memo<u8> = Memo::new(move |_| tuning.get().len() as u8);
 
   // Use a fixed base width for calculations, SVG will be scaled by CSS
-  let svg_width = 800.0; // Fixed base width for consistent calculations
+  let svg_width = SVG_WIDTH; // Fixed base width for consistent calculations
   let svg_height = Memo::new(move |_| svg_width / config.get().svg_aspect_ratio.get());
   let fret_margin =
     Memo::new(move |_| svg_height.get() * config.get().fret_margin_percentage.get());
@@ -54,8 +61,14 @@ pub fn Fretboard(
   let min_visible_fret = Memo::new(move |_| start_fret.get().saturating_sub(extra_frets.get() + 1));
   let max_visible_fret = Memo::new(move |_| end_fret.get() + extra_frets.get());
 
-  let full_fret_positions =
-    Memo::new(move |_| calculate_fret_positions(svg_width, max_visible_fret.get() as u8 + 2));
+  let fret_geometry = Memo::new(move |_| {
+    FretGeometry::new(
+      svg_width,
+      max_visible_fret.get() as u8 + 2,
+      num_strings.get(),
+      config.get().multiscale.get(),
+    )
+  });
 
   let string_spacing =
     Memo::new(move |_| calculate_string_spacing(num_strings.get(), svg_height.get()));
@@ -64,10 +77,15 @@ pub fn Fretboard(
 
   let has_nut = Memo::new(move |_| min_visible_fret.get() == 0);
 
+  let orientation = Memo::new(move |_| {
+    OrientationTransform::new(config.get().orientation.get(), svg_width, svg_height.get())
+  });
+
+  let theme = Memo::new(move |_| config.get().theme.get());
+  let board_colors: Signal<BoardColors> = Signal::derive(move || theme.get().board_colors());
+
   let layout = LayoutSnapshot::new(
-    full_fret_positions.into(),
-    // start_fret.into(),
-    // end_fret.into(),
+    fret_geometry.into(),
     min_visible_fret.into(),
     max_visible_fret.into(),
     num_strings.into(),
@@ -77,59 +95,60 @@ pub fn Fretboard(
     fret_margin.into(),
     nut_width.into(),
     has_nut.into(),
+    orientation.into(),
+    theme.into(),
   );
 
-  let viewbox_positions = Signal::derive(move || {
-    layout
-      .absolute_positions
-      .get()
-      .iter()
-      .map(|&x| layout.abs_to_viewbox_x(x))
-      .collect::<Vec<f64>>()
-  });
-
   view! {
     <div class="flex justify-center items-center w-full">
       <svg
         viewBox=move || {
-          let current_svg_height = svg_height.get();
-          format!("0 0 {svg_width} {current_svg_height}")
+          let (width, height) = orientation.get().viewbox_size(svg_width, svg_height.get());
+          format!("0 0 {width} {height}")
         }
         class="w-full max-w-full h-auto fretboard-svg"
-        style="background: linear-gradient(90deg, #deb887 0%, #f5deb3 100%); border-radius: 8px; box-shadow: 0 2px 8px #0002; border: 1px solid #c00;"
+        style=move || {
+          let (gradient_angle, max_width) = if orientation.get().is_vertical() {
+            ("180deg", "max-width: 320px;")
+          } else {
+            ("90deg", "")
+          };
+          let colors = board_colors.get();
+          format!(
+            "background: linear-gradient({gradient_angle}, {} 0%, {} 100%); border-radius: 8px; box-shadow: 0 2px 8px #0002; border: 1px solid #c00; {max_width}",
+            colors.background_start,
+            colors.background_end,
+          )
+        }
       >
+        <g transform=move || orientation.get().svg_transform()>
         <Show when=move || has_nut.get()>
-          <FretboardNut nut_width fret_margin svg_height />
+          <FretboardNut layout board_colors />
         </Show>
 
-        <FretboardFrets
-          start_fret
-          end_fret
-          min_visible_fret
-          max_visible_fret
-          viewbox_positions
-          fret_margin
-          svg_height
-        />
+        <FretboardFrets layout start_fret end_fret min_visible_fret max_visible_fret board_colors />
 
-        <FretboardStrings num_strings string_spacing viewbox_width=svg_width />
+        <FretboardStrings layout viewbox_width=svg_width drone board_colors />
         <FretboardMarkers
-          svg_height
-          viewbox_positions
+          layout
           marker_positions
           min_visible_fret
           max_visible_fret
+          board_colors
         />
 
         <FretboardOverlays layout start_fret end_fret min_visible_fret max_visible_fret />
+        <FretboardCapo layout capo min_visible_fret max_visible_fret />
         <FretboardGrid
           layout
           min_visible_fret
           max_visible_fret
           tuning
+          drone
           click_cb=on_note_clicked
           fret_states
         />
+        </g>
       </svg>
     </div>
   }
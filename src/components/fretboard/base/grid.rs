@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use leptos::prelude::*;
+
+use super::definitions::{FretCoord, FretState, MAX_FRETS, MAX_STRINGS};
+
+/// Fret states of the largest supported instrument, one signal per cell stored densely by
+/// string and fret.
+///
+/// All signals are created up front, so no signal is ever created inside an Effect, and
+/// cloning the grid only clones a pointer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FretStateGrid {
+  cells: Arc<[RwSignal<FretState>]>,
+}
+
+impl Default for FretStateGrid {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl FretStateGrid {
+  pub fn new() -> Self {
+    Self {
+      cells: (0..MAX_STRINGS * MAX_FRETS)
+        .map(|_| RwSignal::new(FretState::Hidden))
+        .collect(),
+    }
+  }
+
+  fn index(coord: FretCoord) -> Option<usize> {
+    let (string_idx, fret_idx) = (coord.string_idx as usize, coord.fret_idx as usize);
+    (string_idx < MAX_STRINGS && fret_idx < MAX_FRETS).then_some(string_idx * MAX_FRETS + fret_idx)
+  }
+
+  fn coord(index: usize) -> FretCoord {
+    FretCoord {
+      string_idx: (index / MAX_FRETS) as u8,
+      fret_idx: (index % MAX_FRETS) as u8,
+    }
+  }
+
+  /// Signal of a cell, `None` outside the grid
+  pub fn get(&self, coord: FretCoord) -> Option<RwSignal<FretState>> {
+    Self::index(coord).map(|index| self.cells[index])
+  }
+
+  /// Sets a single cell. Coordinates outside the grid are ignored.
+  pub fn set(&self, coord: FretCoord, state: FretState) {
+    if let Some(cell) = self.get(coord) {
+      set_if_changed(cell, state);
+    }
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (FretCoord, RwSignal<FretState>)> + '_ {
+    self
+      .cells
+      .iter()
+      .enumerate()
+      .map(|(index, cell)| (Self::coord(index), *cell))
+  }
+
+  /// Batched update: visits every cell once and only notifies the cells whose state changed.
+  /// All writes happen before any effect runs, so the fretboard re-renders once.
+  pub fn update_all(&self, mut state_at: impl FnMut(FretCoord) -> FretState) {
+    for (coord, cell) in self.iter() {
+      set_if_changed(cell, state_at(coord));
+    }
+  }
+
+  pub fn hide_all(&self) {
+    self.update_all(|_| FretState::Hidden);
+  }
+
+  /// Copies the states of another grid into this one
+  pub fn copy_from(&self, other: &FretStateGrid) {
+    for (cell, source) in self.cells.iter().zip(other.cells.iter()) {
+      set_if_changed(*cell, source.get_untracked());
+    }
+  }
+
+  /// Current states of all cells that show something
+  pub fn shown_states(&self) -> Vec<(FretCoord, FretState)> {
+    self
+      .iter()
+      .map(|(coord, cell)| (coord, cell.get_untracked()))
+      .filter(|(_, state)| *state != FretState::Hidden)
+      .collect()
+  }
+}
+
+fn set_if_changed(cell: RwSignal<FretState>, state: FretState) {
+  if cell.with_untracked(|current| *current != state) {
+    cell.set(state);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::components::fretboard::FretRole;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  #[test]
+  fn test_grid_covers_supported_frets_only() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let grid = FretStateGrid::new();
+      assert_eq!(grid.iter().count(), MAX_STRINGS * MAX_FRETS);
+      let last = FretCoord {
+        string_idx: MAX_STRINGS as u8 - 1,
+        fret_idx: MAX_FRETS as u8 - 1,
+      };
+      assert!(grid.get(last).is_some());
+      assert!(grid
+        .get(FretCoord {
+          fret_idx: MAX_FRETS as u8,
+          ..last
+        })
+        .is_none());
+      assert!(grid
+        .get(FretCoord {
+          string_idx: MAX_STRINGS as u8,
+          ..last
+        })
+        .is_none());
+      assert!(grid
+        .iter()
+        .all(|(coord, cell)| grid.get(coord) == Some(cell)));
+    });
+  }
+
+  #[test]
+  fn test_update_all_only_writes_changed_cells() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let grid = FretStateGrid::new();
+      let root = FretCoord {
+        string_idx: 5,
+        fret_idx: 5,
+      };
+      let root_state = || FretState::Square(FretRole::Root, "A".into());
+      grid.set(root, root_state());
+      assert_eq!(grid.shown_states(), vec![(root, root_state())]);
+
+      // Re-applying the same state must not notify subscribers of the cell
+      let cell = grid.get(root).unwrap();
+      let evaluations = Arc::new(AtomicUsize::new(0));
+      let counter = evaluations.clone();
+      let memo = Memo::new(move |_| {
+        cell.track();
+        counter.fetch_add(1, Ordering::Relaxed);
+      });
+      memo.get_untracked();
+      grid.update_all(|coord| {
+        if coord == root {
+          root_state()
+        } else {
+          FretState::Hidden
+        }
+      });
+      memo.get_untracked();
+      assert_eq!(evaluations.load(Ordering::Relaxed), 1);
+
+      grid.set(root, FretState::Muted);
+      memo.get_untracked();
+      assert_eq!(evaluations.load(Ordering::Relaxed), 2);
+
+      grid.hide_all();
+      assert!(grid.shown_states().is_empty());
+    });
+  }
+}
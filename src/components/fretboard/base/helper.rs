@@ -1,32 +1,3 @@
-use leptos::prelude::RwSignal;
-
-use super::definitions::*;
-
-// TODO add a proper to css color string trait
-impl FretStateColor {
-  pub fn as_str(&self) -> &str {
-    match self {
-      FretStateColor::Red => "red",
-      FretStateColor::Green => "green",
-      FretStateColor::Blue => "blue",
-    }
-  }
-}
-
-pub fn get_preallocated_fret_states() -> FretStateSignals {
-  let mut map = FretStateSignals::with_capacity(MAX_STRINGS * MAX_FRETS);
-  for string_idx in 0..=MAX_STRINGS {
-    for fret_idx in 0..=MAX_FRETS {
-      let coord = FretCoord {
-        string_idx: string_idx as u8,
-        fret_idx: fret_idx as u8,
-      };
-      map.insert(coord, RwSignal::new(FretState::Hidden));
-    }
-  }
-  map
-}
-
 /// Important: The `scale_length` is the length of the vibrating string (e.g., 648mm for a Stratocaster).
 /// This length must be converted into the coordinates of your `viewBox`.
 /// For example, if your `viewBox` is 800 units wide and you want to display 24 frets,
@@ -45,7 +16,42 @@ pub(super) fn calculate_fret_positions(scale_length: f64, num_frets: u8) -> Vec<
   positions
 }
 
+/// Stroke width of a string, from 1 for the highest to 6 for the lowest string
+pub(super) fn string_thickness(string_idx: u8, num_strings: u8) -> f64 {
+  let steps = num_strings.saturating_sub(1).max(1) as f64;
+  1.0 + 5.0 * string_idx as f64 / steps
+}
+
 /// Calculate string spacing for the given number of strings and SVG height
 pub(super) fn calculate_string_spacing(num_strings: u8, svg_height: f64) -> f64 {
   svg_height / (num_strings as f64 + 1.0)
 }
+
+/// Fixed base width of the drawing, the SVG is scaled by CSS
+pub(super) const SVG_WIDTH: f64 = 800.0;
+
+/// Half the size of a note shape in SVG units
+pub(super) const NOTE_RADIUS: f64 = 12.0;
+
+/// Tuning peg where a drone string starts
+pub(super) const DRONE_PEG_RADIUS: f64 = 4.0;
+
+/// SVG path of the "x" drawn for a muted string, `d` is half the size of the cross
+pub(super) fn muted_cross_path(x: f64, y: f64, d: f64) -> String {
+  format!(
+    "M {} {} L {} {} M {} {} L {} {}",
+    x - d,
+    y - d,
+    x + d,
+    y + d,
+    x - d,
+    y + d,
+    x + d,
+    y - d
+  )
+}
+
+/// SVG polygon points of a diamond centered at `(x, y)`
+pub(super) fn diamond_points(x: f64, y: f64, d: f64) -> String {
+  format!("{x},{} {},{y} {x},{} {},{y}", y - d, x + d, y + d, x - d)
+}
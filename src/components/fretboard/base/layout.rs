@@ -1,12 +1,454 @@
 use leptos::prelude::*;
 
-use super::definitions::FretCoord;
+use super::definitions::{DroneString, FretCoord};
+use super::helper::calculate_fret_positions;
+use crate::components::fretboard::theme::FretboardTheme;
+use crate::components::fretboard::visual_config::{
+  FretboardDirection, FretboardOrientation, Handedness, Multiscale, StringOrder,
+};
+
+/// Maps the canonical layout (right-handed, horizontal, high string on top) to the
+/// configured orientation, as an SVG `matrix(a b c d e f)` transform.
+///
+/// The fretboard is always laid out canonically and the whole drawing is transformed,
+/// so click areas stay on top of the notes they belong to in every orientation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrientationTransform {
+  a: f64,
+  b: f64,
+  c: f64,
+  d: f64,
+  e: f64,
+  f: f64,
+  is_vertical: bool,
+}
+
+impl OrientationTransform {
+  pub fn new(orientation: FretboardOrientation, width: f64, height: f64) -> Self {
+    let is_vertical = orientation.direction == FretboardDirection::Vertical;
+    let is_left_handed = orientation.handedness == Handedness::LeftHanded;
+    // Mirroring happens along the axis that ends up horizontal on screen. A vertical board
+    // shows the low string on the left by default, like a chord chart.
+    let flip_frets = is_left_handed && !is_vertical;
+    let flip_strings = (orientation.string_order == StringOrder::LowOnTop)
+      ^ is_vertical
+      ^ (is_left_handed && is_vertical);
+
+    let (sx, ex) = if flip_frets {
+      (-1.0, width)
+    } else {
+      (1.0, 0.0)
+    };
+    let (sy, ey) = if flip_strings {
+      (-1.0, height)
+    } else {
+      (1.0, 0.0)
+    };
+
+    if is_vertical {
+      Self {
+        a: 0.0,
+        b: sx,
+        c: sy,
+        d: 0.0,
+        e: ey,
+        f: ex,
+        is_vertical,
+      }
+    } else {
+      Self {
+        a: sx,
+        b: 0.0,
+        c: 0.0,
+        d: sy,
+        e: ex,
+        f: ey,
+        is_vertical,
+      }
+    }
+  }
+
+  pub fn is_vertical(&self) -> bool {
+    self.is_vertical
+  }
+
+  /// Size of the transformed drawing for a canonical layout of `width` x `height`
+  pub fn viewbox_size(&self, width: f64, height: f64) -> (f64, f64) {
+    if self.is_vertical {
+      (height, width)
+    } else {
+      (width, height)
+    }
+  }
+
+  /// Applies the transform to a canonical point
+  #[cfg(test)]
+  pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+    (
+      self.a * x + self.c * y + self.e,
+      self.b * x + self.d * y + self.f,
+    )
+  }
+
+  pub fn svg_transform(&self) -> String {
+    format!(
+      "matrix({} {} {} {} {} {})",
+      self.a, self.b, self.c, self.d, self.e, self.f
+    )
+  }
+
+  /// Transform for content at canonical `(x, y)` that has to stay upright and unmirrored,
+  /// such as labels. Undoes the rotation and mirroring around that point.
+  pub fn upright_at(&self, x: f64, y: f64) -> String {
+    // The linear part only swaps axes and flips signs, so its inverse is its transpose
+    format!(
+      "translate({x} {y}) matrix({} {} {} {} 0 0) translate({} {})",
+      self.a, self.c, self.b, self.d, -x, -y
+    )
+  }
+}
+
+/// Absolute fret wire positions along every string, the first index is the string.
+///
+/// On a straight board all strings share the positions of the `scale_length`. With fanned
+/// frets the lowest string has the `scale_length`, the others are shorter in proportion and
+/// shifted so that all strings meet at the perpendicular fret.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FretGeometry {
+  strings: Vec<Vec<f64>>,
+}
+
+impl FretGeometry {
+  pub fn new(
+    scale_length: f64,
+    num_frets: u8,
+    num_strings: u8,
+    multiscale: Option<Multiscale>,
+  ) -> Self {
+    let num_strings = num_strings.max(1) as usize;
+    let bass_positions = calculate_fret_positions(scale_length, num_frets);
+    let strings = (0..num_strings)
+      .map(|string_idx| match multiscale {
+        None => bass_positions.clone(),
+        Some(multiscale) => {
+          let length = scale_length * multiscale.relative_scale_length(string_idx, num_strings);
+          let positions = calculate_fret_positions(length, num_frets);
+          let perpendicular = multiscale.perpendicular_fret.min(num_frets as usize);
+          let shift = bass_positions[perpendicular] - positions[perpendicular];
+          positions.into_iter().map(|x| x + shift).collect()
+        }
+      })
+      .collect();
+    Self { strings }
+  }
+
+  pub fn num_strings(&self) -> usize {
+    self.strings.len()
+  }
+
+  /// Number of fret wires per string, including the nut
+  pub fn num_fret_wires(&self) -> usize {
+    self.strings[0].len()
+  }
+
+  pub fn position(&self, string_idx: u8, fret: usize) -> Option<f64> {
+    self.strings.get(string_idx as usize)?.get(fret).copied()
+  }
+
+  /// Lowest and highest position of a fret wire over all strings
+  pub fn span(&self, fret: usize) -> Option<(f64, f64)> {
+    self
+      .strings
+      .iter()
+      .filter_map(|positions| positions.get(fret))
+      .fold(None, |span, &x| match span {
+        None => Some((x, x)),
+        Some((min, max)) => Some((f64::min(min, x), f64::max(max, x))),
+      })
+  }
+}
+
+/// Maps absolute fret positions to x coordinates of the visible, zoomed in range
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewboxMapping {
+  has_nut: bool,
+  nut_width: f64,
+  range_start: f64,
+  scale_factor: f64,
+}
+
+impl ViewboxMapping {
+  pub fn new(
+    geometry: &FretGeometry,
+    min_visible_fret: usize,
+    max_visible_fret: usize,
+    has_nut: bool,
+    svg_width: f64,
+    nut_width: f64,
+  ) -> Self {
+    let last_fret = geometry.num_fret_wires() - 1;
+    let range_start = if has_nut {
+      0.0
+    } else {
+      geometry
+        .span(min_visible_fret.min(last_fret))
+        .map_or(0.0, |(min, _)| min)
+    };
+    let range_end = geometry
+      .span(max_visible_fret.min(last_fret))
+      .map_or(svg_width, |(_, max)| max);
+    let available_width = if has_nut {
+      svg_width - nut_width
+    } else {
+      svg_width
+    };
+    Self {
+      has_nut,
+      nut_width,
+      range_start,
+      scale_factor: available_width / (range_end - range_start),
+    }
+  }
+
+  // Transform an absolute (unscaled) x coordinate into the current viewbox space
+  // For the "zoomed in" functionality
+  pub fn abs_to_viewbox_x(&self, absolute_x: f64) -> f64 {
+    let offset = if self.has_nut { self.nut_width } else { 0.0 };
+    offset + (absolute_x - self.range_start) * self.scale_factor
+  }
+}
+
+/// y coordinate of a string, strings are evenly spaced with a gap at the top and bottom
+pub fn string_y(string_idx: u8, string_spacing: f64) -> f64 {
+  (string_idx as f64 + 1.0) * string_spacing
+}
+
+/// Formats points for the `points` attribute of an SVG polygon
+pub fn polygon_points(points: &[(f64, f64)]) -> String {
+  points
+    .iter()
+    .map(|(x, y)| format!("{x},{y}"))
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// Dot of a fret marker
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MarkerDot {
+  pub cx: f64,
+  pub cy: f64,
+  pub r: f64,
+  pub opacity: f64,
+}
+
+/// Frets greyed out behind a capo and the capo bar
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapoShape {
+  pub shade: Vec<(f64, f64)>,
+  pub bar: Vec<(f64, f64)>,
+}
+
+/// Canonical geometry of the visible board, shared by the component and the SVG export
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoardLayout {
+  pub geometry: FretGeometry,
+  pub mapping: ViewboxMapping,
+  pub string_spacing: f64,
+  /// y where the fret wires start and end
+  pub top: f64,
+  pub bottom: f64,
+}
+
+impl BoardLayout {
+  /// Fret wire of `fret` on a string, in viewbox coordinates
+  pub fn fret_x(&self, string_idx: u8, fret: usize) -> Option<f64> {
+    self
+      .geometry
+      .position(string_idx, fret)
+      .map(|x| self.mapping.abs_to_viewbox_x(x))
+  }
+
+  /// x of a fret wire at height `y`. Fanned fret wires are straight lines through the
+  /// positions on the highest and the lowest string.
+  pub fn fret_x_at(&self, fret: usize, y: f64) -> Option<f64> {
+    let last_string = self.geometry.num_strings() as u8 - 1;
+    let x_high = self.fret_x(0, fret)?;
+    if last_string == 0 {
+      return Some(x_high);
+    }
+    let x_low = self.fret_x(last_string, fret)?;
+    let y_high = string_y(0, self.string_spacing);
+    let y_low = string_y(last_string, self.string_spacing);
+    Some(x_high + (x_low - x_high) * (y - y_high) / (y_low - y_high))
+  }
+
+  /// Top and bottom end of a fret wire
+  pub fn fret_line(&self, fret: usize) -> Option<((f64, f64), (f64, f64))> {
+    Some((
+      (self.fret_x_at(fret, self.top)?, self.top),
+      (self.fret_x_at(fret, self.bottom)?, self.bottom),
+    ))
+  }
+
+  /// Board between the left edge and the fret wire of `fret`
+  pub fn area_before(&self, fret: usize) -> Option<Vec<(f64, f64)>> {
+    let ((top_x, top), (bottom_x, bottom)) = self.fret_line(fret)?;
+    Some(vec![
+      (0.0, top),
+      (top_x.max(0.0), top),
+      (bottom_x.max(0.0), bottom),
+      (0.0, bottom),
+    ])
+  }
+
+  /// Board between the fret wire of `fret` and the right edge at `width`
+  pub fn area_after(&self, fret: usize, width: f64) -> Option<Vec<(f64, f64)>> {
+    let ((top_x, top), (bottom_x, bottom)) = self.fret_line(fret)?;
+    Some(vec![
+      (top_x, top),
+      (width, top),
+      (width, bottom),
+      (bottom_x, bottom),
+    ])
+  }
+
+  /// Strip along a fret wire from `offset` to `offset + width` in front of it,
+  /// `overhang` beyond the top and bottom, e.g. the nut or a capo bar
+  pub fn strip_along(
+    &self,
+    fret: usize,
+    offset: f64,
+    width: f64,
+    overhang: f64,
+  ) -> Option<Vec<(f64, f64)>> {
+    let (top, bottom) = (self.top - overhang, self.bottom + overhang);
+    let top_x = self.fret_x_at(fret, top)? + offset;
+    let bottom_x = self.fret_x_at(fret, bottom)? + offset;
+    Some(vec![
+      (top_x, top),
+      (top_x + width, top),
+      (bottom_x + width, bottom),
+      (bottom_x, bottom),
+    ])
+  }
+
+  /// Center between two fret wires of the string, or the middle of the nut for open strings
+  pub fn note_position(&self, coord: FretCoord) -> Option<(f64, f64)> {
+    let fret = coord.fret_idx as usize;
+    let x = if fret == 0 {
+      if !self.mapping.has_nut {
+        return None;
+      }
+      self.fret_x(coord.string_idx, 0)? - self.mapping.nut_width / 2.0
+    } else {
+      let prev = self.fret_x(coord.string_idx, fret - 1)?;
+      let curr = self.fret_x(coord.string_idx, fret)?;
+      (prev + curr) / 2.0
+    };
+    Some((x, string_y(coord.string_idx, self.string_spacing)))
+  }
+
+  /// Where a string starts: the left edge, or the peg of a drone string in the middle of its
+  /// first fret. `None` if the drone starts after the visible frets.
+  pub fn string_start_x(&self, string_idx: u8, drone: Option<DroneString>) -> Option<f64> {
+    match drone {
+      Some(drone) if drone.string_idx == string_idx => {
+        let peg = FretCoord {
+          string_idx,
+          fret_idx: drone.first_fret as u8,
+        };
+        self.note_position(peg).map(|(x, _)| x.max(0.0))
+      }
+      _ => Some(0.0),
+    }
+  }
+
+  /// Center of a fret marker at height `y`
+  pub fn marker_x(&self, fret: usize, y: f64) -> Option<f64> {
+    let prev = self.fret_x_at(fret.saturating_sub(1), y)?;
+    let curr = self.fret_x_at(fret, y)?;
+    Some((prev + curr) / 2.0)
+  }
+
+  /// Nut in front of the zero fret, `None` if the nut isn't visible
+  pub fn nut(&self) -> Option<Vec<(f64, f64)>> {
+    let nut_width = self.mapping.nut_width;
+    self
+      .mapping
+      .has_nut
+      .then(|| self.strip_along(0, -nut_width, nut_width, 0.0))
+      .flatten()
+  }
+
+  /// The two dots of a fret marker. Octave frets have a double dot, the second dot of the
+  /// other markers is transparent.
+  pub fn marker_dots(&self, fret: usize) -> Option<[MarkerDot; 2]> {
+    let is_double = fret == 12 || fret == 24;
+    let r = if is_double { 8.0 } else { 6.0 };
+    let y_offset = 28.0;
+    let center = (self.top + self.bottom) / 2.0;
+    let (cy1, cy2, opacity2) = if is_double {
+      (center - y_offset, center + y_offset, 0.25)
+    } else {
+      (center, center + y_offset, 0.0)
+    };
+    Some([
+      MarkerDot {
+        cx: self.marker_x(fret, cy1)?,
+        cy: cy1,
+        r,
+        opacity: 0.25,
+      },
+      MarkerDot {
+        cx: self.marker_x(fret, cy2)?,
+        cy: cy2,
+        r,
+        opacity: opacity2,
+      },
+    ])
+  }
+
+  /// Visible areas before and after the playable frets `start_fret..=end_fret`
+  pub fn inactive_areas(
+    &self,
+    start_fret: usize,
+    end_fret: usize,
+    min_visible_fret: usize,
+    max_visible_fret: usize,
+    width: f64,
+  ) -> Vec<Vec<(f64, f64)>> {
+    let before = (start_fret > min_visible_fret)
+      .then(|| self.area_before(start_fret.saturating_sub(1)))
+      .flatten();
+    let after = (end_fret < max_visible_fret)
+      .then(|| self.area_after(end_fret, width))
+      .flatten();
+    before.into_iter().chain(after).collect()
+  }
+
+  /// Capo on fret `capo`, `None` without a capo or when it is outside the visible frets
+  pub fn capo(
+    &self,
+    capo: usize,
+    min_visible_fret: usize,
+    max_visible_fret: usize,
+  ) -> Option<CapoShape> {
+    if capo == 0 || capo <= min_visible_fret || capo > max_visible_fret {
+      return None;
+    }
+    // The capo sits close behind the fret wire of the capo fret
+    let bar_width = 10.0;
+    Some(CapoShape {
+      shade: self.area_before(capo - 1)?,
+      bar: self.strip_along(capo, -bar_width * 2.0, bar_width, 4.0)?,
+    })
+  }
+}
 
 /// Struct containing all kinds of signals that are interesting for rendering
 #[derive(Clone, Debug, PartialEq, Copy)]
 pub struct LayoutSnapshot {
-  pub absolute_positions: Signal<Vec<f64>>,
-  // pub viewbox_positions: Signal<Vec<f64>>,
+  pub board: Signal<BoardLayout>,
   pub num_strings: Signal<u8>,
   pub string_spacing: Signal<f64>,
   pub svg_width: Signal<f64>,
@@ -14,14 +456,14 @@ pub struct LayoutSnapshot {
   pub fret_margin: Signal<f64>,
   pub nut_width: Signal<f64>,
   pub has_nut: Signal<bool>,
-  range_start: Signal<f64>,
-  scale_factor: Signal<f64>,
+  pub orientation: Signal<OrientationTransform>,
+  pub theme: Signal<FretboardTheme>,
 }
 
 impl LayoutSnapshot {
   #[allow(clippy::too_many_arguments)]
   pub fn new(
-    absolute_positions: Signal<Vec<f64>>,
+    geometry: Signal<FretGeometry>,
     min_visible_fret: Signal<usize>,
     max_visible_fret: Signal<usize>,
     num_strings: Signal<u8>,
@@ -31,27 +473,30 @@ impl LayoutSnapshot {
     fret_margin: Signal<f64>,
     nut_width: Signal<f64>,
     has_nut: Signal<bool>,
+    orientation: Signal<OrientationTransform>,
+    theme: Signal<FretboardTheme>,
   ) -> Self {
-    let range_start = Signal::derive(move || {
-      if has_nut.get() {
-        0.0
-      } else {
-        absolute_positions.get()[min_visible_fret.get()]
+    let board = Memo::new(move |_| {
+      let geometry = geometry.get();
+      let mapping = ViewboxMapping::new(
+        &geometry,
+        min_visible_fret.get(),
+        max_visible_fret.get(),
+        has_nut.get(),
+        svg_width.get(),
+        nut_width.get(),
+      );
+      BoardLayout {
+        geometry,
+        mapping,
+        string_spacing: string_spacing.get(),
+        top: fret_margin.get(),
+        bottom: svg_height.get() - fret_margin.get(),
       }
     });
-    let scale_factor = Signal::derive(move || {
-      let range_end = absolute_positions.get()[max_visible_fret.get()];
-      let range_width = range_end - range_start.get();
-      let available_width = if has_nut.get() {
-        svg_width.get() - nut_width.get()
-      } else {
-        svg_width.get()
-      };
-      available_width / range_width
-    });
 
     Self {
-      absolute_positions,
+      board: board.into(),
       num_strings,
       string_spacing,
       svg_width,
@@ -59,46 +504,122 @@ impl LayoutSnapshot {
       fret_margin,
       nut_width,
       has_nut,
-      range_start,
-      scale_factor,
+      orientation,
+      theme,
     }
   }
 
-  // Transform an absolute (unscaled) x coordinate into the current viewbox space
-  // For the "zoomed in" functionality
-  pub fn abs_to_viewbox_x(&self, absolute_x: f64) -> f64 {
-    let offset = if self.has_nut.get() {
-      self.nut_width.get()
-    } else {
-      0.0
-    };
-    offset + (absolute_x - self.range_start.get()) * self.scale_factor.get()
+  pub fn string_y(&self, string_idx: u8) -> f64 {
+    string_y(string_idx, self.string_spacing.get())
   }
 
-  pub fn fret_center_x(&self, fret: usize) -> Option<f64> {
-    if fret == 0 {
-      return if self.has_nut.get() {
-        Some(self.nut_width.get() / 2.0)
-      } else {
-        None
-      };
-    }
-    if fret < self.absolute_positions.get().len() {
-      let prev = self.absolute_positions.get()[fret - 1];
-      let curr = self.absolute_positions.get()[fret];
-      Some(self.abs_to_viewbox_x((prev + curr) / 2.0))
-    } else {
-      None
+  pub fn note_position(&self, coord: FretCoord) -> Option<(f64, f64)> {
+    self.board.with(|board| board.note_position(coord))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const WIDTH: f64 = 800.0;
+  const HEIGHT: f64 = 200.0;
+
+  fn transform(
+    handedness: Handedness,
+    direction: FretboardDirection,
+    string_order: StringOrder,
+  ) -> OrientationTransform {
+    OrientationTransform::new(
+      FretboardOrientation {
+        handedness,
+        direction,
+        string_order,
+      },
+      WIDTH,
+      HEIGHT,
+    )
+  }
+
+  // Canonical points: nut end of the high string, body end of the low string
+  const NUT_HIGH: (f64, f64) = (0.0, 0.0);
+  const BODY_LOW: (f64, f64) = (WIDTH, HEIGHT);
+
+  #[test]
+  fn test_horizontal_orientations() {
+    use FretboardDirection::Horizontal;
+    let default = transform(Handedness::RightHanded, Horizontal, StringOrder::HighOnTop);
+    assert_eq!(default.apply(NUT_HIGH.0, NUT_HIGH.1), (0.0, 0.0));
+
+    let left = transform(Handedness::LeftHanded, Horizontal, StringOrder::HighOnTop);
+    assert_eq!(left.apply(NUT_HIGH.0, NUT_HIGH.1), (WIDTH, 0.0));
+
+    let players_view = transform(Handedness::RightHanded, Horizontal, StringOrder::LowOnTop);
+    assert_eq!(players_view.apply(BODY_LOW.0, BODY_LOW.1), (WIDTH, 0.0));
+    assert_eq!(players_view.viewbox_size(WIDTH, HEIGHT), (WIDTH, HEIGHT));
+  }
+
+  #[test]
+  fn test_vertical_orientations() {
+    use FretboardDirection::Vertical;
+    // Headstock at the top, low string on the left
+    let chart = transform(Handedness::RightHanded, Vertical, StringOrder::HighOnTop);
+    assert_eq!(chart.viewbox_size(WIDTH, HEIGHT), (HEIGHT, WIDTH));
+    assert_eq!(chart.apply(NUT_HIGH.0, NUT_HIGH.1), (HEIGHT, 0.0));
+    assert_eq!(chart.apply(BODY_LOW.0, BODY_LOW.1), (0.0, WIDTH));
+
+    // Left-handed charts are mirrored, the low string is on the right
+    let left = transform(Handedness::LeftHanded, Vertical, StringOrder::HighOnTop);
+    assert_eq!(left.apply(NUT_HIGH.0, NUT_HIGH.1), (0.0, 0.0));
+    assert_eq!(left.apply(BODY_LOW.0, BODY_LOW.1), (HEIGHT, WIDTH));
+  }
+
+  #[test]
+  fn test_upright_labels() {
+    for direction in [FretboardDirection::Horizontal, FretboardDirection::Vertical] {
+      let t = transform(Handedness::LeftHanded, direction, StringOrder::LowOnTop);
+      // The inverse linear part composed with the transform is the identity
+      let (x0, y0) = t.apply(0.0, 0.0);
+      let (x1, y1) = t.apply(1.0, 0.0);
+      let (x2, y2) = t.apply(0.0, 1.0);
+      let columns = [(x1 - x0, y1 - y0), (x2 - x0, y2 - y0)];
+      let inverse = [(t.a, t.c), (t.b, t.d)];
+      for (i, (cx, cy)) in columns.iter().enumerate() {
+        let (ix, iy) = (
+          inverse[0].0 * cx + inverse[1].0 * cy,
+          inverse[0].1 * cx + inverse[1].1 * cy,
+        );
+        assert_eq!((ix, iy), if i == 0 { (1.0, 0.0) } else { (0.0, 1.0) });
+      }
     }
   }
 
-  pub fn string_y(&self, string_idx: u8) -> f64 {
-    (string_idx as f64 + 1.0) * self.string_spacing.get()
+  #[test]
+  fn test_straight_geometry_shares_positions() {
+    let geometry = FretGeometry::new(800.0, 25, 7, None);
+    assert_eq!(geometry.num_strings(), 7);
+    assert_eq!(geometry.num_fret_wires(), 26);
+    for fret in 0..26 {
+      let (min, max) = geometry.span(fret).unwrap();
+      assert_eq!(min, max);
+    }
   }
 
-  pub fn note_position(&self, coord: FretCoord) -> Option<(f64, f64)> {
-    let x = self.fret_center_x(coord.fret_idx as usize)?;
-    let y = self.string_y(coord.string_idx);
-    Some((x, y))
+  #[test]
+  fn test_fanned_frets_meet_at_perpendicular_fret() {
+    let multiscale = Multiscale {
+      bass_scale_length: 27.0,
+      treble_scale_length: 25.5,
+      perpendicular_fret: 7,
+    };
+    let geometry = FretGeometry::new(800.0, 24, 8, Some(multiscale));
+    let (min, max) = geometry.span(7).unwrap();
+    assert!((max - min).abs() < 1e-9);
+    // The nut slants one way and the last fret the other way
+    let (high_nut, low_nut) = (geometry.position(0, 0), geometry.position(7, 0));
+    assert!(high_nut.unwrap() > low_nut.unwrap());
+    let (high_last, low_last) = (geometry.position(0, 24), geometry.position(7, 24));
+    assert!(high_last.unwrap() < low_last.unwrap());
+    assert_eq!(geometry.position(8, 0), None);
   }
 }
@@ -1,10 +1,13 @@
 pub mod definitions;
+mod export;
 mod fretboard;
+mod grid;
 mod helper;
 mod layout;
 mod parts;
+mod shapes;
 
 pub use definitions::*;
+pub use export::FretboardDiagram;
 pub use fretboard::Fretboard;
-/// for demo page, should probably be private
-pub use helper::get_preallocated_fret_states;
+pub use grid::FretStateGrid;
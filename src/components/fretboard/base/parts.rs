@@ -1,33 +1,35 @@
+#![allow(unused_parens)]
+
 use crate::music::Note;
 use leptos::prelude::*;
 
 use crate::components::fretboard::base::layout::LayoutSnapshot;
+use crate::components::fretboard::theme::BoardColors;
 
-use super::{FretClickEvent, FretCoord, FretState, FretStateSignals};
+use super::helper::{string_thickness, DRONE_PEG_RADIUS};
+use super::layout::{polygon_points, CapoShape};
+use super::shapes::{FretWireStyle, NoteDrawing, NoteShape};
+use super::{DroneString, FretClickEvent, FretCoord, FretState, FretStateGrid};
 
 /// Renders the nut (zero fret) when visible
 #[component]
 pub(crate) fn FretboardNut(
-  /// Width of the nut in SVG units
-  #[prop(into)]
-  nut_width: Signal<f64>,
-  /// Top margin for the nut
-  #[prop(into)]
-  fret_margin: Signal<f64>,
-  /// Total SVG height (needed for nut rect height calculation)
-  #[prop(into)]
-  svg_height: Signal<f64>,
+  layout: LayoutSnapshot,
+  board_colors: Signal<BoardColors>,
 ) -> impl IntoView {
+  let points = move || {
+    layout
+      .board
+      .with(|board| board.nut())
+      .map(|points| polygon_points(&points))
+  };
   view! {
-    <rect
-      x="0"
-      y=fret_margin
-      width=nut_width
-      height=move || svg_height.get() - 2.0 * fret_margin.get()
-      fill="#f8f8f8"
-      stroke="#222"
+    <polygon
+      points=points
+      fill=move || board_colors.get().nut_fill
+      stroke=move || board_colors.get().nut_stroke
       stroke-width="5"
-      rx="3"
+      stroke-linejoin="round"
     />
   }
 }
@@ -35,13 +37,12 @@ pub(crate) fn FretboardNut(
 /// Renders all fret lines with different styles for playable vs non-playable
 #[component]
 pub(crate) fn FretboardFrets(
+  layout: LayoutSnapshot,
   start_fret: Signal<usize>,
   end_fret: Signal<usize>,
   #[prop(into)] min_visible_fret: Signal<usize>,
   #[prop(into)] max_visible_fret: Signal<usize>,
-  viewbox_positions: Signal<Vec<f64>>,
-  #[prop(into)] fret_margin: Signal<f64>,
-  #[prop(into)] svg_height: Signal<f64>,
+  board_colors: Signal<BoardColors>,
 ) -> impl IntoView {
   view! {
     <For
@@ -50,28 +51,29 @@ pub(crate) fn FretboardFrets(
       let(fret_no)
     >
       {move || {
-        let is_out_of_bounds = Memo::new(move |_| { fret_no >= viewbox_positions.get().len() });
-        if is_out_of_bounds.get() {
+        let line = Memo::new(move |_| layout.board.with(|board| board.fret_line(fret_no)));
+        if line.get().is_none() {
           leptos::logging::warn!("Skipping fret line for fret {} as out of bounds", fret_no);
           return None;
         }
-        let x_pos = Memo::new(move |_| viewbox_positions.get()[fret_no]);
-        let is_playable = Memo::new(move |_| {
-          fret_no >= start_fret.get().saturating_sub(1) && fret_no <= end_fret.get()
+        let x1 = move || line.get().map(|((x, _), _)| x);
+        let y1 = move || line.get().map(|((_, y), _)| y);
+        let x2 = move || line.get().map(|(_, (x, _))| x);
+        let y2 = move || line.get().map(|(_, (_, y))| y);
+        let style = Memo::new(move |_| {
+          FretWireStyle::new(fret_no, start_fret.get(), end_fret.get(), board_colors.get())
         });
-        let color = Signal::derive(move || if is_playable.get() { "#444" } else { "#bbb" });
-        let width = Signal::derive(move || if is_playable.get() { "5" } else { "3" });
         Some(
 
           view! {
             <line
-              x1=x_pos
-              y1=fret_margin
-              x2=x_pos
-              y2=move || svg_height.get() - fret_margin.get()
-              stroke=color
-              stroke-width=width
-              opacity=move || if is_playable.get() { "1.0" } else { "0.6" }
+              x1=x1
+              y1=y1
+              x2=x2
+              y2=y2
+              stroke=move || style.get().color
+              stroke-width=move || style.get().width
+              opacity=move || style.get().opacity
             />
           },
         )
@@ -80,37 +82,56 @@ pub(crate) fn FretboardFrets(
   }
 }
 
-/// Renders horizontal string lines
+/// Renders horizontal string lines, a drone string starts at its peg
 #[component]
 pub(crate) fn FretboardStrings(
-  /// Number of strings on the instrument
-  #[prop(into)]
-  num_strings: Signal<u8>,
-  /// Spacing between strings
-  #[prop(into)]
-  string_spacing: Signal<f64>,
+  layout: LayoutSnapshot,
   /// Total viewbox width
   viewbox_width: f64,
+  #[prop(into)] drone: Signal<Option<DroneString>>,
+  board_colors: Signal<BoardColors>,
 ) -> impl IntoView {
+  let num_strings = layout.num_strings;
+  let string_spacing = layout.string_spacing;
   view! {
     <For
       each=move || (0..num_strings.get())
-      key=move |string_no| (*string_no, string_spacing.get().round() as isize)
+      key=move |string_no| {
+        (*string_no, num_strings.get(), string_spacing.get().round() as isize)
+      }
       let(string_no)
     >
       {
         let y_pos = (string_no as f64 + 1.0) * string_spacing.get();
-        let string_thickness = 1.0 + (string_no as f64);
+        let start_x = Memo::new(move |_| {
+          layout.board.with(|board| board.string_start_x(string_no, drone.get()))
+        });
+        let peg_x = move || start_x.get().filter(|x| *x > 0.0);
 
         view! {
-          <line
-            x1="0"
-            y1=y_pos
-            x2=viewbox_width
-            y2=y_pos
-            stroke="#888"
-            stroke-width=string_thickness
-          />
+          <Show when=move || start_x.get().is_some()>
+            <line
+              x1=move || start_x.get().unwrap_or_default()
+              y1=y_pos
+              x2=viewbox_width
+              y2=y_pos
+              stroke=move || board_colors.get().string
+              stroke-width=string_thickness(string_no, num_strings.get_untracked())
+            />
+          </Show>
+          {move || {
+            peg_x()
+              .map(|x| {
+                view! {
+                  <circle
+                    cx=x
+                    cy=y_pos
+                    r=DRONE_PEG_RADIUS
+                    fill=move || board_colors.get().string
+                  />
+                }
+              })
+          }}
         }
       }
     </For>
@@ -120,12 +141,13 @@ pub(crate) fn FretboardStrings(
 /// Renders fret position markers (dots)
 #[component]
 pub(crate) fn FretboardMarkers(
-  #[prop(into)] svg_height: Signal<f64>,
-  #[prop(into)] viewbox_positions: Signal<Vec<f64>>,
+  layout: LayoutSnapshot,
   #[prop(into)] marker_positions: Signal<Vec<usize>>,
   #[prop(into)] min_visible_fret: Signal<usize>,
   #[prop(into)] max_visible_fret: Signal<usize>,
+  board_colors: Signal<BoardColors>,
 ) -> impl IntoView {
+  let marker_color = move || board_colors.get().marker;
   view! {
     <For
       each=move || {
@@ -136,36 +158,26 @@ pub(crate) fn FretboardMarkers(
       let(fret)
     >
       {move || {
-        let is_out_of_bounds = Memo::new(move |_| { fret >= viewbox_positions.get().len() });
-        if is_out_of_bounds.get() {
+        let Some(dots) = layout.board.with(|board| board.marker_dots(fret)) else {
           leptos::logging::warn!("Skipping marker for fret {} as out of bounds", fret);
           return None;
-        }
-        let x = Memo::new(move |_| {
-          let viewbox_positions = viewbox_positions.get();
-          let x_prev = viewbox_positions[fret.saturating_sub(1)];
-          let x_curr = viewbox_positions[fret];
-          (x_prev + x_curr) / 2.0
-        });
-        let r = if fret == 12 || fret == 24 { 8.0 } else { 6.0 };
-        let y_coords_and_opacity = Signal::derive(move || {
-          let y = svg_height.get() / 2.0;
-          let y_offset = 28.0;
-          if fret == 12 || fret == 24 {
-            (y - y_offset, y + y_offset, 0.25)
-          } else {
-            (y, y + y_offset, 0.0)
-          }
-        });
-        let cy1 = move || y_coords_and_opacity.get().0;
-        let cy2 = move || y_coords_and_opacity.get().1;
-        let op2 = move || y_coords_and_opacity.get().2;
+        };
         Some(
 
           view! {
             <g>
-              <circle cx=x cy=cy1 r=r fill="#444" opacity="0.25" />
-              <circle cx=x cy=cy2 r=r fill="#444" opacity=op2 />
+              {dots
+                .map(|dot| {
+                  view! {
+                    <circle
+                      cx=dot.cx
+                      cy=dot.cy
+                      r=dot.r
+                      fill=marker_color
+                      opacity=dot.opacity
+                    />
+                  }
+                })}
             </g>
           },
         )
@@ -183,49 +195,62 @@ pub(crate) fn FretboardOverlays(
   #[prop(into)] min_visible_fret: Signal<usize>,
   #[prop(into)] max_visible_fret: Signal<usize>,
 ) -> impl IntoView {
-  let overlay_left = move || {
-    if start_fret.get() > min_visible_fret.get() {
-      let playable_area_start = layout.absolute_positions.get()[start_fret.get().saturating_sub(1)];
-      let viewbox_start_x = move || layout.abs_to_viewbox_x(playable_area_start);
-      Some(view! {
-        <rect
-          x=move || { 0.0 }
-          y=layout.fret_margin
-          width=viewbox_start_x
-          height=move || layout.svg_height.get() - 2.0 * layout.fret_margin.get()
-          fill="#fff"
-          opacity="0.5"
-          style="pointer-events:none;"
-        />
-      })
-    } else {
-      None
-    }
+  let areas = move || {
+    layout.board.with(|board| {
+      board.inactive_areas(
+        start_fret.get(),
+        end_fret.get(),
+        min_visible_fret.get(),
+        max_visible_fret.get(),
+        layout.svg_width.get(),
+      )
+    })
   };
 
-  let overlay_right = move || {
-    if end_fret.get() < max_visible_fret.get() {
-      let end_x = move || layout.abs_to_viewbox_x(layout.absolute_positions.get()[end_fret.get()]);
-      let width = move || layout.svg_width.get() - end_x();
-      Some(view! {
-        <rect
-          x=end_x
-          y=layout.fret_margin.get()
-          width=width
-          height=layout.svg_height.get() - 2.0 * layout.fret_margin.get()
-          fill="#fff"
-          opacity="0.5"
-          style="pointer-events:none;"
-        />
+  move || {
+    areas()
+      .into_iter()
+      .map(|points| {
+        view! {
+          <polygon
+            points=polygon_points(&points)
+            fill=move || layout.theme.get().board_colors().overlay
+            opacity="0.5"
+            style="pointer-events:none;"
+          />
+        }
       })
-    } else {
-      None
-    }
-  };
+      .collect_view()
+  }
+}
 
-  view! {
-    {overlay_left}
-    {overlay_right}
+/// Greys out the frets behind the capo and draws the capo bar
+#[component]
+pub(crate) fn FretboardCapo(
+  layout: LayoutSnapshot,
+  capo: Signal<usize>,
+  #[prop(into)] min_visible_fret: Signal<usize>,
+  #[prop(into)] max_visible_fret: Signal<usize>,
+) -> impl IntoView {
+  move || {
+    let CapoShape { shade, bar } = layout
+      .board
+      .with(|board| board.capo(capo.get(), min_visible_fret.get(), max_visible_fret.get()))?;
+    let colors = layout.theme.get().board_colors();
+
+    Some(view! {
+      <g class="capo" style="pointer-events:none;">
+        <polygon points=polygon_points(&shade) fill=colors.capo_shade opacity="0.45" />
+        <polygon
+          points=polygon_points(&bar)
+          fill=colors.capo_bar
+          stroke=colors.capo_bar
+          stroke-width="4"
+          stroke-linejoin="round"
+          opacity="0.9"
+        />
+      </g>
+    })
   }
 }
 
@@ -234,28 +259,29 @@ pub(crate) fn FretboardOverlays(
 fn FretboardClickableArea(layout: LayoutSnapshot, coord: FretCoord) -> impl IntoView {
   let string_y = Memo::new(move |_| layout.string_y(coord.string_idx));
   let x_and_width = Memo::new(move |_| {
-    if coord.fret_idx == 0 {
-      // Nut rectangle
-      if layout.has_nut.get() {
-        (0.0, layout.nut_width.get())
-      } else {
-        (0.0, 0.0)
+    let fret = coord.fret_idx as usize;
+    layout.board.with(|board| {
+      if fret == 0 {
+        // Nut rectangle
+        return match board.fret_x(coord.string_idx, 0) {
+          Some(x) if layout.has_nut.get() => (x - layout.nut_width.get(), layout.nut_width.get()),
+          _ => (0.0, 0.0),
+        };
       }
-    } else {
-      if coord.fret_idx as usize >= layout.absolute_positions.get().len() {
+      let (Some(prev), Some(curr)) = (
+        board.fret_x(coord.string_idx, fret - 1),
+        board.fret_x(coord.string_idx, fret),
+      ) else {
         leptos::logging::warn!(
           "FretboardClickableArea: Fret index {} out of bounds (len {})",
           coord.fret_idx,
-          layout.absolute_positions.get().len()
+          board.geometry.num_fret_wires()
         );
         return (0.0, 0.0);
-      }
-      let prev = layout.absolute_positions.get()[(coord.fret_idx.saturating_sub(1)) as usize];
-      let curr = layout.absolute_positions.get()[coord.fret_idx as usize];
-      let start = layout.abs_to_viewbox_x((prev + curr) / 2.0 - (curr - prev) / 4.0);
-      let end = layout.abs_to_viewbox_x((prev + curr) / 2.0 + (curr - prev) / 4.0);
-      (start, end - start)
-    }
+      };
+      let start = (prev + curr) / 2.0 - (curr - prev) / 4.0;
+      (start, (curr - prev) / 2.0)
+    })
   });
   let x = move || x_and_width.get().0;
   let width = move || x_and_width.get().1;
@@ -275,7 +301,7 @@ fn FretboardClickableArea(layout: LayoutSnapshot, coord: FretCoord) -> impl Into
   }
 }
 
-/// Single note (circle + optional label) at a fret/string coordinate.
+/// Single note (shape + optional label) at a fret/string coordinate.
 #[component]
 fn FretboardNote(
   layout: LayoutSnapshot,
@@ -283,42 +309,74 @@ fn FretboardNote(
   #[prop(into)] fret_state: Signal<FretState>,
 ) -> impl IntoView {
   move || {
-    let (x, y) = match layout.note_position(coord) {
-      Some(p) => p,
-      None => return None,
+    let (x, y) = layout.note_position(coord)?;
+    let NoteDrawing { shape, label } =
+      NoteDrawing::new(&fret_state.get(), layout.theme.get(), x, y)?;
+
+    let shape = match shape {
+      NoteShape::Circle {
+        cx,
+        cy,
+        r,
+        fill,
+        opacity,
+        stroke,
+        stroke_width,
+        stroke_dasharray,
+      } => view! {
+        <circle
+          cx=cx
+          cy=cy
+          r=r
+          fill=fill
+          opacity=opacity
+          stroke=stroke
+          stroke-width=stroke_width
+          stroke-dasharray=stroke_dasharray
+        />
+      }
+      .into_any(),
+      NoteShape::Square {
+        x,
+        y,
+        size,
+        fill,
+        opacity,
+      } => view! { <rect x=x y=y width=size height=size rx="2" fill=fill opacity=opacity /> }
+        .into_any(),
+      NoteShape::Diamond {
+        points,
+        fill,
+        opacity,
+      } => view! { <polygon points=points fill=fill opacity=opacity /> }.into_any(),
+      NoteShape::Cross { path, halo, color } => view! {
+        <path d=path.clone() stroke=halo stroke-width="6" stroke-linecap="round" />
+        <path d=path stroke=color stroke-width="3" stroke-linecap="round" />
+      }
+      .into_any(),
     };
 
-    let memo = Memo::new(move |_| match fret_state.get() {
-      FretState::Hidden => ("transparent".to_string(), 0.0, None),
-      FretState::Normal(color, label) => (color.as_str().to_string(), 12.0, Some(label)),
+    let label = label.map(move |(text, text_color)| {
+      view! {
+        <text
+          x=x
+          y=y
+          transform=move || layout.orientation.get().upright_at(x, y)
+          text-anchor="middle"
+          dominant-baseline="central"
+          fill=text_color
+          font-size="8"
+          font-weight="bold"
+        >
+          {text}
+        </text>
+      }
     });
-    let fill_color = move || memo.get().0;
-    let radius = move || memo.get().1;
-    let label = move || memo.get().2;
 
     Some(view! {
       <g class="note" data-string=coord.string_idx data-fret=coord.fret_idx>
-        {if radius() > 0.0 {
-          Some(view! { <circle cx=x cy=y r=radius fill=fill_color opacity="0.85" /> })
-        } else {
-          None
-        }}
-        {label()
-          .map(move |text| {
-            view! {
-              <text
-                x=x
-                y=y
-                text-anchor="middle"
-                dominant-baseline="central"
-                fill="white"
-                font-size="8"
-                font-weight="bold"
-              >
-                {text}
-              </text>
-            }
-          })}
+        {shape}
+        {label}
       </g>
     })
   }
@@ -329,8 +387,9 @@ pub(crate) fn FretboardGrid(
   #[prop(into)] layout: LayoutSnapshot,
   #[prop(into)] min_visible_fret: Signal<usize>,
   #[prop(into)] max_visible_fret: Signal<usize>,
-  fret_states: Signal<FretStateSignals>,
+  fret_states: Signal<FretStateGrid>,
   tuning: Signal<Vec<Note>>,
+  #[prop(into)] drone: Signal<Option<DroneString>>,
   /// Optional callback for fret click events
   #[prop(into)]
   click_cb: Signal<Option<Callback<FretClickEvent>>>,
@@ -351,45 +410,43 @@ pub(crate) fn FretboardGrid(
             string_idx,
             fret_idx: fret_idx as u8,
           };
-          let fret_state = fret_states
-            .with_untracked(|borrowed| {
-              *borrowed
-                .get(&coord)
-                .unwrap_or_else(|| {
-                  panic!("Preallocated signals for all coordinates. Tried accessing {coord:?}");
-                })
-            });
+          // Frets beyond the grid, e.g. the extra fret after fret 24, and frets below the
+          // first fret of a drone string stay empty
+          let fret_state = fret_states.with_untracked(|grid| grid.get(coord));
+          let steps = Memo::new(move |_| DroneString::steps_at(drone.get(), coord));
           let handle_click = move |_| {
             if let Some(click_cb) = click_cb.get().as_ref() {
               let note = tuning
                 .get()
                 .get(string_idx as usize)
                 .expect("Bounds checking on model construction")
-                .add_steps(fret_idx);
+                .add_steps(steps.get_untracked().unwrap_or(fret_idx));
               click_cb.run(FretClickEvent { coord, note });
             }
           };
-          view! {
-            <g
-              class="cell-group"
-              data-fret=fret_idx
-              data-string=string_idx
-              style=format!(
-                "cursor: {};",
-                if click_cb.get().is_some() { "pointer" } else { "default" },
-              )
-              on:click=handle_click
-            >
-              {move || {
-                if click_cb.get().is_some() {
-                  Some(view! { <FretboardClickableArea layout=layout coord=coord /> })
-                } else {
-                  None
-                }
-              }}
-              <FretboardNote layout=layout coord=coord fret_state />
-            </g>
-          }
+          fret_state.map(|fret_state| view! {
+            <Show when=move || steps.get().is_some()>
+              <g
+                class="cell-group"
+                data-fret=fret_idx
+                data-string=string_idx
+                style=format!(
+                  "cursor: {};",
+                  if click_cb.get().is_some() { "pointer" } else { "default" },
+                )
+                on:click=handle_click
+              >
+                {move || {
+                  if click_cb.get().is_some() {
+                    Some(view! { <FretboardClickableArea layout=layout coord=coord /> })
+                  } else {
+                    None
+                  }
+                }}
+                <FretboardNote layout=layout coord=coord fret_state />
+              </g>
+            </Show>
+          })
         }
       </For>
 
@@ -0,0 +1,204 @@
+use super::definitions::FretState;
+use super::helper::{diamond_points, muted_cross_path, NOTE_RADIUS};
+use crate::components::fretboard::theme::{BoardColors, FretboardTheme};
+
+/// Stroke of a fret wire, the wires around the playable frets are drawn bolder
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FretWireStyle {
+  pub color: &'static str,
+  pub width: &'static str,
+  pub opacity: &'static str,
+}
+
+impl FretWireStyle {
+  pub fn new(fret_no: usize, start_fret: usize, end_fret: usize, colors: BoardColors) -> Self {
+    let is_playable = fret_no >= start_fret.saturating_sub(1) && fret_no <= end_fret;
+    if is_playable {
+      Self {
+        color: colors.fret,
+        width: "5",
+        opacity: "1.0",
+      }
+    } else {
+      Self {
+        color: colors.fret_inactive,
+        width: "3",
+        opacity: "0.6",
+      }
+    }
+  }
+}
+
+/// Shape of a note with its final coordinates and colours
+#[derive(Clone, Debug, PartialEq)]
+pub enum NoteShape {
+  /// Filled, hollow (`fill` is "none") or dashed ghost circle
+  Circle {
+    cx: f64,
+    cy: f64,
+    r: f64,
+    fill: &'static str,
+    opacity: Option<f64>,
+    stroke: Option<&'static str>,
+    stroke_width: Option<&'static str>,
+    stroke_dasharray: Option<&'static str>,
+  },
+  Square {
+    x: f64,
+    y: f64,
+    size: f64,
+    fill: &'static str,
+    opacity: f64,
+  },
+  Diamond {
+    points: String,
+    fill: &'static str,
+    opacity: f64,
+  },
+  /// "x" drawn twice, a light halo below the cross keeps it readable on the nut and on the wood
+  Cross {
+    path: String,
+    halo: &'static str,
+    color: &'static str,
+  },
+}
+
+/// Everything drawn for one fret state at `(x, y)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoteDrawing {
+  pub shape: NoteShape,
+  /// Text and its colour
+  pub label: Option<(String, &'static str)>,
+}
+
+impl NoteDrawing {
+  /// `None` for hidden frets
+  pub fn new(state: &FretState, theme: FretboardTheme, x: f64, y: f64) -> Option<Self> {
+    let r = NOTE_RADIUS;
+    let shape = match state {
+      FretState::Hidden => return None,
+      FretState::Muted => {
+        let board = theme.board_colors();
+        NoteShape::Cross {
+          path: muted_cross_path(x, y, r * 0.6),
+          halo: board.nut_fill,
+          color: board.nut_stroke,
+        }
+      }
+      FretState::Normal(role, _) => NoteShape::Circle {
+        cx: x,
+        cy: y,
+        r,
+        fill: theme.note_colors(*role).fill,
+        opacity: Some(theme.note_opacity(*role)),
+        stroke: None,
+        stroke_width: None,
+        stroke_dasharray: None,
+      },
+      FretState::Hollow(role, _) => NoteShape::Circle {
+        cx: x,
+        cy: y,
+        r: r - 1.5,
+        fill: "none",
+        opacity: None,
+        stroke: Some(theme.note_colors(*role).fill),
+        stroke_width: Some("3"),
+        stroke_dasharray: None,
+      },
+      FretState::Square(role, _) => NoteShape::Square {
+        x: x - r,
+        y: y - r,
+        size: 2.0 * r,
+        fill: theme.note_colors(*role).fill,
+        opacity: theme.note_opacity(*role),
+      },
+      FretState::Diamond(role, _) => NoteShape::Diamond {
+        points: diamond_points(x, y, r * 1.2),
+        fill: theme.note_colors(*role).fill,
+        opacity: theme.note_opacity(*role),
+      },
+      FretState::Ghost(role, _) => {
+        let fill = theme.note_colors(*role).fill;
+        NoteShape::Circle {
+          cx: x,
+          cy: y,
+          r,
+          fill,
+          opacity: Some(theme.note_opacity(*role) * 0.4),
+          stroke: Some(fill),
+          stroke_width: None,
+          stroke_dasharray: Some("3 2"),
+        }
+      }
+    };
+
+    // Hollow and ghost notes show the wood through the shape, so their labels use the note colour
+    let text_color = match state {
+      FretState::Hollow(role, _) | FretState::Ghost(role, _) => theme.note_colors(*role).fill,
+      _ => state
+        .role()
+        .map_or("white", |role| theme.note_colors(role).text),
+    };
+    let label = state.label().map(|label| (label.to_string(), text_color));
+    Some(Self { shape, label })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::components::fretboard::FretRole;
+
+  #[test]
+  fn test_note_drawing() {
+    let theme = FretboardTheme::default();
+    assert_eq!(NoteDrawing::new(&FretState::Hidden, theme, 0.0, 0.0), None);
+
+    let muted = NoteDrawing::new(&FretState::Muted, theme, 10.0, 20.0).unwrap();
+    assert!(matches!(muted.shape, NoteShape::Cross { .. }));
+    assert_eq!(muted.label, None);
+
+    let colors = theme.note_colors(FretRole::Root);
+    let square = NoteDrawing::new(
+      &FretState::Square(FretRole::Root, "A".into()),
+      theme,
+      50.0,
+      20.0,
+    )
+    .unwrap();
+    assert_eq!(
+      square.shape,
+      NoteShape::Square {
+        x: 50.0 - NOTE_RADIUS,
+        y: 20.0 - NOTE_RADIUS,
+        size: 2.0 * NOTE_RADIUS,
+        fill: colors.fill,
+        opacity: theme.note_opacity(FretRole::Root),
+      }
+    );
+    assert_eq!(square.label, Some(("A".to_string(), colors.text)));
+
+    // Hollow labels are drawn in the note colour on the wood
+    let hollow = NoteDrawing::new(
+      &FretState::Hollow(FretRole::Root, "A".into()),
+      theme,
+      0.0,
+      0.0,
+    )
+    .unwrap();
+    assert_eq!(hollow.label, Some(("A".to_string(), colors.fill)));
+  }
+
+  #[test]
+  fn test_fret_wire_style() {
+    let colors = FretboardTheme::default().board_colors();
+    // The wire before the first playable fret belongs to the playable range
+    assert_eq!(FretWireStyle::new(4, 5, 8, colors).color, colors.fret);
+    assert_eq!(FretWireStyle::new(8, 5, 8, colors).width, "5");
+    assert_eq!(
+      FretWireStyle::new(3, 5, 8, colors).color,
+      colors.fret_inactive
+    );
+    assert_eq!(FretWireStyle::new(9, 5, 8, colors).opacity, "0.6");
+  }
+}
@@ -2,9 +2,21 @@ pub mod base;
 pub(crate) mod visual_config;
 
 mod model_adapter;
+mod orientation_controls;
+mod theme;
+mod theme_controls;
 
-pub use base::{FretClickEvent, FretCoord, FretState, FretStateColor, FretStateSignals, Fretboard};
+pub use base::{
+  DroneString, FretClickEvent, FretCoord, FretRole, FretState, FretStateGrid, Fretboard,
+  FretboardDiagram,
+};
 
 pub use base::definitions;
 pub use model_adapter::FretboardModelAdapter;
-pub use visual_config::{FretboardVisualConfig, FretboardVisualConfigBuilder};
+pub use orientation_controls::FretboardOrientationControls;
+pub use theme::{BoardColors, FretPalette, FretboardTheme, FretboardThemePreference, NoteColors};
+pub use theme_controls::FretboardThemeControls;
+pub use visual_config::{
+  FretboardDirection, FretboardOrientation, FretboardOrientationPreference, FretboardVisualConfig,
+  FretboardVisualConfigBuilder, Handedness, Multiscale, StringOrder,
+};
@@ -1,7 +1,9 @@
 use leptos::prelude::*;
 
 use super::{FretClickEvent, Fretboard};
+use crate::audio::{pluck_note, use_fret_sound};
 use crate::models::fretboard::model::FretboardModel;
+use crate::models::fretboard::use_instrument;
 
 #[component]
 pub fn FretboardModelAdapter(
@@ -13,17 +15,44 @@ pub fn FretboardModelAdapter(
   let start_fret = Signal::derive(move || model.with(|m| m.get_start_fret()).get());
   let end_fret = Signal::derive(move || model.with(|m| m.get_end_fret()).get());
   let tuning = Signal::derive(move || model.with(|m| m.get_tuning()).get());
+  let capo = Signal::derive(move || model.with(|m| m.get_capo()).get());
+  let drone = Signal::derive(move || model.with(|m| m.get_drone()).get());
   let config = Signal::derive(move || model.with(|m| m.get_config()).get());
   let fret_states = Signal::derive(move || model.with(|m| m.get_fret_states()).get());
 
-  // Convert optional callback to signal
-  let on_note_clicked_signal = Signal::derive(move || on_note_clicked);
+  // Clicked frets sound if the app preference asks for it, which makes every fretboard
+  // clickable
+  let fret_sound = use_fret_sound();
+  let instrument = use_instrument();
+  let on_note_clicked_signal = Signal::derive(move || {
+    if !fret_sound.get() {
+      return on_note_clicked;
+    }
+    Some(Callback::new(move |evt: FretClickEvent| {
+      model.with_untracked(|model| {
+        let open_midis = model.get_tuning().with_untracked(|tuning| {
+          instrument
+            .get_untracked()
+            .profile()
+            .open_string_midis(tuning)
+        });
+        if let Some(midi) = model.midi_from_fret(evt.coord, &open_midis) {
+          pluck_note(midi);
+        }
+      });
+      if let Some(callback) = on_note_clicked {
+        callback.run(evt);
+      }
+    }))
+  });
 
   view! {
     <Fretboard
       start_fret
       end_fret
       tuning
+      capo
+      drone
       config
       on_note_clicked=on_note_clicked_signal
       fret_states
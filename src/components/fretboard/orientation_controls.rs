@@ -0,0 +1,93 @@
+use leptos::prelude::*;
+
+use super::visual_config::{FretboardDirection, FretboardOrientation, Handedness, StringOrder};
+
+/// Dropdowns for handedness, direction and string order of a fretboard
+#[component]
+pub fn FretboardOrientationControls(
+  #[prop(into)] value: Signal<FretboardOrientation>,
+  on_change: Callback<FretboardOrientation>,
+) -> impl IntoView {
+  let update = move |f: &dyn Fn(&mut FretboardOrientation)| {
+    let mut orientation = value.get_untracked();
+    f(&mut orientation);
+    on_change.run(orientation);
+  };
+
+  view! {
+    <div class="grid grid-cols-3 gap-3">
+      <label class="flex flex-col text-xs font-medium">
+        "Hand"
+        <select
+          class="p-1 rounded border"
+          on:change=move |e| {
+            let handedness = match event_target_value(&e).as_str() {
+              "left" => Handedness::LeftHanded,
+              _ => Handedness::RightHanded,
+            };
+            update(&|orientation| orientation.handedness = handedness);
+          }
+        >
+          <option
+            value="right"
+            selected=move || value.get().handedness == Handedness::RightHanded
+          >
+            "Right-handed"
+          </option>
+          <option value="left" selected=move || value.get().handedness == Handedness::LeftHanded>
+            "Left-handed"
+          </option>
+        </select>
+      </label>
+      <label class="flex flex-col text-xs font-medium">
+        "Direction"
+        <select
+          class="p-1 rounded border"
+          on:change=move |e| {
+            let direction = match event_target_value(&e).as_str() {
+              "vertical" => FretboardDirection::Vertical,
+              _ => FretboardDirection::Horizontal,
+            };
+            update(&|orientation| orientation.direction = direction);
+          }
+        >
+          <option
+            value="horizontal"
+            selected=move || value.get().direction == FretboardDirection::Horizontal
+          >
+            "Horizontal"
+          </option>
+          <option
+            value="vertical"
+            selected=move || value.get().direction == FretboardDirection::Vertical
+          >
+            "Vertical (chord chart)"
+          </option>
+        </select>
+      </label>
+      <label class="flex flex-col text-xs font-medium">
+        "String Order"
+        <select
+          class="p-1 rounded border"
+          on:change=move |e| {
+            let string_order = match event_target_value(&e).as_str() {
+              "low" => StringOrder::LowOnTop,
+              _ => StringOrder::HighOnTop,
+            };
+            update(&|orientation| orientation.string_order = string_order);
+          }
+        >
+          <option
+            value="high"
+            selected=move || value.get().string_order == StringOrder::HighOnTop
+          >
+            "High string on top"
+          </option>
+          <option value="low" selected=move || value.get().string_order == StringOrder::LowOnTop>
+            "Player's view"
+          </option>
+        </select>
+      </label>
+    </div>
+  }
+}
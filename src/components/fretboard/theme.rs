@@ -0,0 +1,280 @@
+use std::fmt;
+
+use leptos::prelude::*;
+use strum_macros::EnumIter;
+
+use super::base::{FretRole, HEAT_LEVELS};
+
+/// Colours used for the fret roles
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum FretPalette {
+  #[default]
+  Classic,
+  /// Saturated colours and opaque notes
+  HighContrast,
+  /// Okabe-Ito colours, distinguishable with red-green colour blindness
+  ColorBlindSafe,
+}
+
+impl fmt::Display for FretPalette {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FretPalette::Classic => write!(f, "Classic"),
+      FretPalette::HighContrast => write!(f, "High Contrast"),
+      FretPalette::ColorBlindSafe => write!(f, "Colour-Blind Safe"),
+    }
+  }
+}
+
+/// Fill and label colour of a note
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoteColors {
+  pub fill: &'static str,
+  pub text: &'static str,
+}
+
+/// Colours of the fretboard itself
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoardColors {
+  pub background_start: &'static str,
+  pub background_end: &'static str,
+  pub nut_fill: &'static str,
+  pub nut_stroke: &'static str,
+  pub fret: &'static str,
+  pub fret_inactive: &'static str,
+  pub string: &'static str,
+  pub marker: &'static str,
+  /// Covers the frets outside the playable range
+  pub overlay: &'static str,
+  pub capo_shade: &'static str,
+  pub capo_bar: &'static str,
+}
+
+const LIGHT_BOARD: BoardColors = BoardColors {
+  background_start: "#deb887",
+  background_end: "#f5deb3",
+  nut_fill: "#f8f8f8",
+  nut_stroke: "#222",
+  fret: "#444",
+  fret_inactive: "#bbb",
+  string: "#888",
+  marker: "#444",
+  overlay: "#fff",
+  capo_shade: "#777",
+  capo_bar: "#333",
+};
+
+const DARK_BOARD: BoardColors = BoardColors {
+  background_start: "#2b211b",
+  background_end: "#3d3028",
+  nut_fill: "#d8d8d8",
+  nut_stroke: "#111",
+  fret: "#c8c8c8",
+  fret_inactive: "#666",
+  string: "#b5b5b5",
+  marker: "#eee",
+  overlay: "#000",
+  capo_shade: "#000",
+  capo_bar: "#aaa",
+};
+
+/// Palette plus light or dark board
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FretboardTheme {
+  pub palette: FretPalette,
+  pub dark_mode: bool,
+}
+
+/// Heatmap steps from weak to strong, red to green
+const CLASSIC_HEAT: [NoteColors; HEAT_LEVELS as usize] = [
+  NoteColors {
+    fill: "#dc2626",
+    text: "white",
+  },
+  NoteColors {
+    fill: "#f97316",
+    text: "white",
+  },
+  NoteColors {
+    fill: "#eab308",
+    text: "black",
+  },
+  NoteColors {
+    fill: "#84cc16",
+    text: "black",
+  },
+  NoteColors {
+    fill: "#22c55e",
+    text: "white",
+  },
+];
+const HIGH_CONTRAST_HEAT: [NoteColors; HEAT_LEVELS as usize] = [
+  NoteColors {
+    fill: "#991b1b",
+    text: "#fff",
+  },
+  NoteColors {
+    fill: "#c2410c",
+    text: "#fff",
+  },
+  NoteColors {
+    fill: "#a16207",
+    text: "#fff",
+  },
+  NoteColors {
+    fill: "#4d7c0f",
+    text: "#fff",
+  },
+  NoteColors {
+    fill: "#166534",
+    text: "#fff",
+  },
+];
+/// Viridis steps, which don't rely on telling red from green
+const COLOR_BLIND_SAFE_HEAT: [NoteColors; HEAT_LEVELS as usize] = [
+  NoteColors {
+    fill: "#440154",
+    text: "#fff",
+  },
+  NoteColors {
+    fill: "#3b528b",
+    text: "#fff",
+  },
+  NoteColors {
+    fill: "#21918c",
+    text: "#fff",
+  },
+  NoteColors {
+    fill: "#5ec962",
+    text: "#000",
+  },
+  NoteColors {
+    fill: "#fde725",
+    text: "#000",
+  },
+];
+
+fn heat_colors(steps: &[NoteColors; HEAT_LEVELS as usize], level: u8) -> NoteColors {
+  steps[level.min(HEAT_LEVELS - 1) as usize]
+}
+
+/// App wide theme preference, used by fretboards that don't configure a theme
+#[derive(Clone, Copy, Debug)]
+pub struct FretboardThemePreference(pub RwSignal<FretboardTheme>);
+
+impl FretboardTheme {
+  pub fn note_colors(&self, role: FretRole) -> NoteColors {
+    let colors = |fill, text| NoteColors { fill, text };
+    match self.palette {
+      FretPalette::Classic => match role {
+        FretRole::Root => colors("green", "white"),
+        FretRole::ChordTone => colors("#15803d", "white"),
+        FretRole::ScaleTone => colors("blue", "white"),
+        FretRole::Target => colors("#16a34a", "white"),
+        FretRole::Wrong => colors("red", "white"),
+        FretRole::Ghost => colors("gray", "white"),
+        FretRole::Heat(level) => heat_colors(&CLASSIC_HEAT, level),
+      },
+      FretPalette::HighContrast => match (role, self.dark_mode) {
+        (FretRole::Root, false) => colors("#000", "#fff"),
+        (FretRole::Root, true) => colors("#fff", "#000"),
+        (FretRole::ChordTone, _) => colors("#7a0099", "#fff"),
+        (FretRole::ScaleTone, _) => colors("#0033cc", "#fff"),
+        (FretRole::Target, _) => colors("#006400", "#fff"),
+        (FretRole::Wrong, _) => colors("#cc0000", "#fff"),
+        (FretRole::Ghost, false) => colors("#fff", "#000"),
+        (FretRole::Ghost, true) => colors("#555", "#fff"),
+        (FretRole::Heat(level), _) => heat_colors(&HIGH_CONTRAST_HEAT, level),
+      },
+      // Target and wrong are blue and orange instead of green and red
+      FretPalette::ColorBlindSafe => match role {
+        FretRole::Root => colors("#cc79a7", "#000"),
+        FretRole::ChordTone => colors("#009e73", "#fff"),
+        FretRole::ScaleTone => colors("#56b4e9", "#000"),
+        FretRole::Target => colors("#0072b2", "#fff"),
+        FretRole::Wrong => colors("#e69f00", "#000"),
+        FretRole::Ghost => colors("#999999", "#000"),
+        FretRole::Heat(level) => heat_colors(&COLOR_BLIND_SAFE_HEAT, level),
+      },
+    }
+  }
+
+  pub fn note_opacity(&self, role: FretRole) -> f64 {
+    match (self.palette, role) {
+      (FretPalette::HighContrast, _) => 1.0,
+      (_, FretRole::Ghost) => 0.6,
+      _ => 0.85,
+    }
+  }
+
+  pub fn board_colors(&self) -> BoardColors {
+    match (self.dark_mode, self.palette) {
+      (true, _) => DARK_BOARD,
+      (false, FretPalette::HighContrast) => BoardColors {
+        background_start: "#fff",
+        background_end: "#fff",
+        fret: "#000",
+        string: "#333",
+        ..LIGHT_BOARD
+      },
+      (false, _) => LIGHT_BOARD,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use strum::IntoEnumIterator;
+
+  #[test]
+  fn test_palettes_distinguish_all_roles() {
+    for palette in FretPalette::iter() {
+      for dark_mode in [false, true] {
+        let theme = FretboardTheme { palette, dark_mode };
+        let fills: Vec<_> = FretRole::iter()
+          .map(|role| theme.note_colors(role).fill)
+          .collect();
+        for (i, fill) in fills.iter().enumerate() {
+          assert!(
+            !fills[i + 1..].contains(fill),
+            "{palette} uses {fill} for two roles"
+          );
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_heat_levels_are_distinct() {
+    for palette in FretPalette::iter() {
+      let theme = FretboardTheme {
+        palette,
+        dark_mode: false,
+      };
+      let fills: Vec<_> = (0..HEAT_LEVELS)
+        .map(|level| theme.note_colors(FretRole::Heat(level)).fill)
+        .collect();
+      for (i, fill) in fills.iter().enumerate() {
+        assert!(!fills[i + 1..].contains(fill), "{palette} repeats {fill}");
+      }
+      // Levels above the scale use the strongest colour
+      assert_eq!(
+        theme.note_colors(FretRole::Heat(HEAT_LEVELS)).fill,
+        fills[HEAT_LEVELS as usize - 1]
+      );
+    }
+  }
+
+  #[test]
+  fn test_high_contrast_root_stands_out_on_dark_board() {
+    let theme = FretboardTheme {
+      palette: FretPalette::HighContrast,
+      dark_mode: true,
+    };
+    assert_eq!(theme.note_colors(FretRole::Root).fill, "#fff");
+    assert_eq!(theme.board_colors(), DARK_BOARD);
+  }
+}
@@ -0,0 +1,54 @@
+use leptos::prelude::*;
+use strum::IntoEnumIterator;
+
+use super::theme::{FretPalette, FretboardTheme};
+
+/// Palette dropdown and dark mode switch of a fretboard
+#[component]
+pub fn FretboardThemeControls(
+  #[prop(into)] value: Signal<FretboardTheme>,
+  on_change: Callback<FretboardTheme>,
+) -> impl IntoView {
+  view! {
+    <div class="flex flex-wrap gap-3 items-end">
+      <label class="flex flex-col text-xs font-medium">
+        "Palette"
+        <select
+          class="p-1 rounded border"
+          on:change=move |e| {
+            let value_str = event_target_value(&e);
+            if let Some(palette) = FretPalette::iter()
+              .find(|palette| palette.to_string() == value_str)
+            {
+              on_change.run(FretboardTheme { palette, ..value.get_untracked() });
+            }
+          }
+        >
+          {FretPalette::iter()
+            .map(|palette| {
+              view! {
+                <option
+                  value=palette.to_string()
+                  selected=move || value.get().palette == palette
+                >
+                  {palette.to_string()}
+                </option>
+              }
+            })
+            .collect_view()}
+        </select>
+      </label>
+      <label class="flex gap-2 items-center text-xs font-medium">
+        <input
+          type="checkbox"
+          prop:checked=move || value.get().dark_mode
+          on:change=move |_| {
+            let theme = value.get_untracked();
+            on_change.run(FretboardTheme { dark_mode: !theme.dark_mode, ..theme });
+          }
+        />
+        "Dark fretboard"
+      </label>
+    </div>
+  }
+}
@@ -1,5 +1,72 @@
 use leptos::prelude::*;
 
+use super::theme::{FretboardTheme, FretboardThemePreference};
+use crate::models::fretboard::use_instrument;
+
+/// Which hand frets the notes. Left-handed boards are mirrored horizontally.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Handedness {
+  #[default]
+  RightHanded,
+  LeftHanded,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FretboardDirection {
+  /// Nut on the side, frets run from left to right
+  #[default]
+  Horizontal,
+  /// Headstock at the top like a chord chart
+  Vertical,
+}
+
+/// Which string is drawn on top of a horizontal fretboard.
+/// On a vertical fretboard `HighOnTop` draws the high string on the right, as in chord charts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum StringOrder {
+  /// Like tablature
+  #[default]
+  HighOnTop,
+  /// Player's view, looking down at the guitar while playing
+  LowOnTop,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FretboardOrientation {
+  pub handedness: Handedness,
+  pub direction: FretboardDirection,
+  pub string_order: StringOrder,
+}
+
+/// Fanned frets: every string has its own scale length, interpolated from the lowest to the
+/// highest string. Lengths can be given in any unit, only their ratio matters.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Multiscale {
+  /// Scale length of the lowest string, e.g. 27.0 (inches)
+  pub bass_scale_length: f64,
+  /// Scale length of the highest string, e.g. 25.5 (inches)
+  pub treble_scale_length: f64,
+  /// Fret that runs perpendicular to the strings
+  pub perpendicular_fret: usize,
+}
+
+impl Multiscale {
+  /// Scale length of `string_idx` relative to the lowest string. The first index is the
+  /// highest string, like in the tuning.
+  pub fn relative_scale_length(&self, string_idx: usize, num_strings: usize) -> f64 {
+    let treble = self.treble_scale_length / self.bass_scale_length;
+    if num_strings < 2 {
+      return 1.0;
+    }
+    let from_treble = string_idx as f64 / (num_strings - 1) as f64;
+    treble + (1.0 - treble) * from_treble
+  }
+}
+
+/// App wide orientation preference, used by fretboards that don't configure an orientation
+#[derive(Clone, Copy, Debug)]
+pub struct FretboardOrientationPreference(pub RwSignal<FretboardOrientation>);
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct FretboardVisualConfig {
   /// Width-to-height aspect ratio (default: 3.0)
@@ -10,8 +77,14 @@ pub struct FretboardVisualConfig {
   pub nut_width: Signal<f64>,
   /// Number of extra frets to show for context (default: 1)
   pub extra_frets: Signal<usize>,
-  /// Fret positions where markers should be displayed
+  /// Fret positions where markers should be displayed (default: those of the app instrument)
   pub marker_positions: Signal<Vec<usize>>,
+  /// Handedness, direction and string order (default: app preference or right-handed horizontal)
+  pub orientation: Signal<FretboardOrientation>,
+  /// Note palette and light or dark board (default: app preference or classic light)
+  pub theme: Signal<FretboardTheme>,
+  /// Fanned frets (default: none, all strings share one scale length)
+  pub multiscale: Signal<Option<Multiscale>>,
 }
 
 impl Default for FretboardVisualConfig {
@@ -26,6 +99,9 @@ pub struct FretboardVisualConfigBuilder {
   nut_width: Option<Signal<f64>>,
   extra_frets: Option<Signal<usize>>,
   marker_positions: Option<Signal<Vec<usize>>>,
+  orientation: Option<Signal<FretboardOrientation>>,
+  theme: Option<Signal<FretboardTheme>>,
+  multiscale: Option<Signal<Option<Multiscale>>>,
 }
 
 impl FretboardVisualConfigBuilder {
@@ -36,6 +112,9 @@ impl FretboardVisualConfigBuilder {
       nut_width: None,
       extra_frets: None,
       marker_positions: None,
+      orientation: None,
+      theme: None,
+      multiscale: None,
     }
   }
 
@@ -64,6 +143,21 @@ impl FretboardVisualConfigBuilder {
     self
   }
 
+  pub fn orientation(mut self, orientation: Signal<FretboardOrientation>) -> Self {
+    self.orientation = Some(orientation);
+    self
+  }
+
+  pub fn theme(mut self, theme: Signal<FretboardTheme>) -> Self {
+    self.theme = Some(theme);
+    self
+  }
+
+  pub fn multiscale(mut self, multiscale: Signal<Option<Multiscale>>) -> Self {
+    self.multiscale = Some(multiscale);
+    self
+  }
+
   pub fn build(self) -> FretboardVisualConfig {
     let svg_aspect_ratio = self
       .svg_aspect_ratio
@@ -81,9 +175,29 @@ impl FretboardVisualConfigBuilder {
       .extra_frets
       .unwrap_or_else(|| Signal::derive(move || 1));
 
-    let marker_positions = self
-      .marker_positions
-      .unwrap_or_else(|| Signal::derive(move || vec![3, 5, 7, 9, 12, 15, 17, 19, 21, 24]));
+    let marker_positions = self.marker_positions.unwrap_or_else(|| {
+      let instrument = use_instrument();
+      Signal::derive(move || instrument.get().profile().marker_positions)
+    });
+
+    let orientation =
+      self
+        .orientation
+        .unwrap_or_else(|| match use_context::<FretboardOrientationPreference>() {
+          Some(FretboardOrientationPreference(preference)) => preference.into(),
+          None => Signal::derive(FretboardOrientation::default),
+        });
+
+    let theme = self
+      .theme
+      .unwrap_or_else(|| match use_context::<FretboardThemePreference>() {
+        Some(FretboardThemePreference(preference)) => preference.into(),
+        None => Signal::derive(FretboardTheme::default),
+      });
+
+    let multiscale = self
+      .multiscale
+      .unwrap_or_else(|| Signal::derive(move || None));
 
     FretboardVisualConfig {
       svg_aspect_ratio,
@@ -91,6 +205,9 @@ impl FretboardVisualConfigBuilder {
       nut_width,
       extra_frets,
       marker_positions,
+      orientation,
+      theme,
+      multiscale,
     }
   }
 }
@@ -17,9 +17,20 @@ pub fn Metronome(
   /// Optional callback when BPM changes
   #[prop(optional)]
   on_bpm_change: Option<Callback<u32>>,
+  /// Optional callback on every click with the number of beats since start (0-based)
+  #[prop(optional)]
+  on_beat: Option<Callback<u64>>,
 ) -> impl IntoView {
   let (metronome_state, set_metronome_state) = signal(MetronomeState::Stopped);
   let (current_beat, set_current_beat) = signal(1u8);
+  let (beats_since_start, set_beats_since_start) = signal(0u64);
+
+  let notify_beat = move |beat: u64| {
+    set_beats_since_start.set(beat);
+    if let Some(callback) = on_beat {
+      callback.run(beat);
+    }
+  };
 
   // Calculate interval from BPM as a reactive signal
   let interval_signal = Memo::new(move |_| {
@@ -51,6 +62,7 @@ pub fn Metronome(
 
       // Update beat counter
       set_current_beat.set(next_beat);
+      notify_beat(beats_since_start.get_untracked() + 1);
     }
   };
 
@@ -73,6 +85,7 @@ pub fn Metronome(
         if let Some(ctx) = AudioManager::get_context() {
           play_click_with_context(&ctx, true); // Beat 1 is always the accent
         }
+        notify_beat(0);
         resume();
       }
       MetronomeState::Running => {
@@ -5,3 +5,9 @@ pub mod fretboard;
 pub mod metronome;
 pub mod music_selectors;
 pub mod navbar;
+pub mod pitch_listener;
+pub mod trainer_leaderboard;
+pub mod trainer_modes;
+pub mod trainer_settings;
+pub mod trainer_stats;
+pub mod tuning_selector;
@@ -1,7 +1,10 @@
+use crate::models::fretboard::FretLabelMode;
 use crate::music::heptatonic_scales::HeptaScaleType;
+use crate::music::intervals::Interval;
 use crate::music::notes::Note;
 use crate::music::scales::ScaleType;
 use leptos::prelude::*;
+use strum::IntoEnumIterator;
 
 /// Extracts the value from an HTML input or select event.
 /// This is a helper function used by all selector components.
@@ -75,6 +78,74 @@ pub fn NoteSelector(
   }
 }
 
+/// A grid with a button for each of the 12 chromatic notes, e.g. to answer a quiz.
+#[component]
+pub fn NoteButtonGrid(
+  /// Called with the note of the clicked button
+  on_note_clicked: Callback<Note>,
+  /// Disables all buttons, e.g. while no question is asked
+  #[prop(into, optional)]
+  disabled: Signal<bool>,
+) -> impl IntoView {
+  view! {
+    <div class="grid grid-cols-4 gap-2 sm:grid-cols-6">
+      {Note::mapping()
+        .iter()
+        .map(|(note, display_str)| {
+          let note = *note;
+          view! {
+            <button
+              class="py-2 px-3 text-white bg-blue-500 rounded hover:bg-blue-600 disabled:opacity-50"
+              disabled=disabled
+              on:click=move |_| on_note_clicked.run(note)
+            >
+              {*display_str}
+            </button>
+          }
+        })
+        .collect_view()}
+    </div>
+  }
+}
+
+/// A button for each of the given intervals, from the smallest to the largest, e.g. to
+/// answer an ear training question.
+#[component]
+pub fn IntervalButtonGrid(
+  /// Intervals to show a button for
+  #[prop(into)]
+  intervals: Signal<Vec<Interval>>,
+  /// Called with the interval of the clicked button
+  on_interval_clicked: Callback<Interval>,
+  /// Disables all buttons, e.g. while no question is asked
+  #[prop(into, optional)]
+  disabled: Signal<bool>,
+) -> impl IntoView {
+  view! {
+    <div class="grid grid-cols-3 gap-2 sm:grid-cols-4">
+      {move || {
+        intervals
+          .with(|intervals| {
+            Interval::iter()
+              .filter(|interval| intervals.contains(interval))
+              .map(|interval| {
+                view! {
+                  <button
+                    class="py-2 px-3 text-white bg-blue-500 rounded hover:bg-blue-600 disabled:opacity-50"
+                    disabled=disabled
+                    on:click=move |_| on_interval_clicked.run(interval)
+                  >
+                    {interval.to_string()}
+                  </button>
+                }
+              })
+              .collect_view()
+          })
+      }}
+    </div>
+  }
+}
+
 /// A reusable scale type selector dropdown component.
 ///
 /// Currently supports Major, Minor, and Chromatic scales.
@@ -160,6 +231,45 @@ pub fn ScaleTypeSelector(
   }
 }
 
+/// Selector for what is written on highlighted frets: note names, intervals, degrees or fingers
+#[component]
+pub fn FretLabelModeSelector(
+  #[prop(into)] value: Signal<FretLabelMode>,
+  #[prop(into)] on_label_mode_changed: Callback<FretLabelMode>,
+  #[prop(optional)] label: Option<&'static str>,
+  /// Optional CSS classes for styling
+  #[prop(optional, into)]
+  class: Option<String>,
+) -> impl IntoView {
+  let label_text = label.unwrap_or("Labels");
+  let css_class = class.unwrap_or_else(|| "w-full p-2 border rounded-md".to_string());
+
+  view! {
+    <div class="space-y-2">
+      <label class="block text-sm font-medium">{label_text}</label>
+      <select
+        class=css_class
+        on:change=move |ev| {
+          let mode_str = event_target_value(&ev);
+          if let Some(mode) = FretLabelMode::iter().find(|mode| mode.to_string() == mode_str) {
+            on_label_mode_changed.run(mode);
+          }
+        }
+      >
+        {FretLabelMode::iter()
+          .map(|mode| {
+            view! {
+              <option value=mode.to_string() selected=move || value.get() == mode>
+                {mode.to_string()}
+              </option>
+            }
+          })
+          .collect_view()}
+      </select>
+    </div>
+  }
+}
+
 /// A reusable numeric range selector component.
 ///
 /// Provides a labeled range input with dynamic min/max values.
@@ -3,6 +3,14 @@ use leptos::prelude::*;
 use leptos_router::hooks::use_location;
 use leptos_use::storage::use_local_storage;
 
+use strum::IntoEnumIterator;
+
+use crate::audio::FretSoundPreference;
+use crate::components::fretboard::{
+  FretPalette, FretboardOrientationPreference, FretboardThemePreference, Handedness,
+};
+use crate::models::fretboard::{Instrument, InstrumentPreference};
+
 #[component]
 pub fn ThemeToggle() -> impl IntoView {
   // Create signals that work on both server and client
@@ -37,6 +45,123 @@ pub fn ThemeToggle() -> impl IntoView {
   }
 }
 
+/// Switches all fretboards between right- and left-handed
+#[component]
+pub fn HandednessToggle() -> impl IntoView {
+  let preference = use_context::<FretboardOrientationPreference>();
+
+  preference.map(|FretboardOrientationPreference(orientation)| {
+    let is_left_handed = move || orientation.get().handedness == Handedness::LeftHanded;
+    view! {
+      <label class="nav-link" style="float: right; margin-right: 1rem; cursor: pointer;">
+        <input
+          type="checkbox"
+          checked=is_left_handed
+          on:change=move |_| {
+            orientation
+              .update(|orientation| {
+                orientation.handedness = if orientation.handedness == Handedness::LeftHanded {
+                  Handedness::RightHanded
+                } else {
+                  Handedness::LeftHanded
+                };
+              })
+          }
+        />
+        <span>" Left-handed"</span>
+      </label>
+    }
+  })
+}
+
+/// Switches the sound of clicked frets on all fretboards
+#[component]
+pub fn FretSoundToggle() -> impl IntoView {
+  let preference = use_context::<FretSoundPreference>();
+
+  preference.map(|FretSoundPreference(enabled)| {
+    view! {
+      <label class="nav-link" style="float: right; margin-right: 1rem; cursor: pointer;">
+        <input
+          type="checkbox"
+          checked=move || enabled.get()
+          on:change=move |_| enabled.update(|enabled| *enabled = !*enabled)
+        />
+        <span>" Sound"</span>
+      </label>
+    }
+  })
+}
+
+/// Switches the note palette of all fretboards
+#[component]
+pub fn PaletteSelect() -> impl IntoView {
+  let preference = use_context::<FretboardThemePreference>();
+
+  preference.map(|FretboardThemePreference(theme)| {
+    view! {
+      <select
+        class="nav-link"
+        style="float: right; margin-right: 1rem;"
+        title="Fretboard colours"
+        on:change=move |e| {
+          let value = event_target_value(&e);
+          if let Some(palette) = FretPalette::iter().find(|palette| palette.to_string() == value) {
+            theme.update(|theme| theme.palette = palette);
+          }
+        }
+      >
+        {FretPalette::iter()
+          .map(|palette| {
+            view! {
+              <option value=palette.to_string() selected=move || theme.get().palette == palette>
+                {palette.to_string()}
+              </option>
+            }
+          })
+          .collect_view()}
+      </select>
+    }
+  })
+}
+
+/// Switches the instrument of all fretboards
+#[component]
+pub fn InstrumentSelect() -> impl IntoView {
+  let preference = use_context::<InstrumentPreference>();
+
+  preference.map(|InstrumentPreference(instrument)| {
+    view! {
+      <select
+        class="nav-link"
+        style="float: right; margin-right: 1rem;"
+        title="Instrument"
+        on:change=move |e| {
+          let value = event_target_value(&e);
+          if let Some(selected) = Instrument::iter().find(|i| i.to_string() == value) {
+            instrument.set(selected);
+          }
+        }
+      >
+        {Instrument::iter()
+          .map(|option| {
+            let scale_length = option.profile().scale_length;
+            view! {
+              <option
+                value=option.to_string()
+                title=format!("{scale_length}\" scale")
+                selected=move || instrument.get() == option
+              >
+                {option.to_string()}
+              </option>
+            }
+          })
+          .collect_view()}
+      </select>
+    }
+  })
+}
+
 #[component]
 pub fn NavbarLinks() -> impl IntoView {
   let location = use_location();
@@ -67,6 +192,14 @@ pub fn NavbarLinks() -> impl IntoView {
           <span>Fretboard Trainer</span>
         </a>
       </li>
+      <li>
+        <a
+          href="/tuner"
+          class=move || { if pathname() == "/tuner" { "nav-link active" } else { "nav-link" } }
+        >
+          <span>Tuner</span>
+        </a>
+      </li>
       <li>
         <a
           href="/fretboard_dev"
@@ -115,6 +248,10 @@ pub fn Navbar() -> impl IntoView {
     <div class="z-50 navbar">
       <NavbarLinks />
       <ThemeToggle />
+      <HandednessToggle />
+      <FretSoundToggle />
+      <PaletteSelect />
+      <InstrumentSelect />
     </div>
   }
 }
@@ -0,0 +1,131 @@
+use leptos::prelude::*;
+use leptos_use::{
+  use_interval_fn, use_user_media_with_options, UseUserMediaOptions, UseUserMediaReturn,
+};
+
+use crate::audio::{detect_pitch, AudioManager, MicrophoneAnalyser, NoteStabilizer, PlayedNote};
+
+/// Milliseconds between two analysed frames
+const LISTEN_INTERVAL_MS: u64 = 100;
+
+/// Listens to the microphone and reports every note played on the instrument, one at a
+/// time. Nothing is recorded or sent anywhere, the frames are only analysed. Notes aren't
+/// reported while the app plays sound itself.
+#[component]
+pub fn PitchListener(
+  /// Called once for each played note, again only after a pause or another note
+  #[prop(optional)]
+  on_note_played: Option<Callback<PlayedNote>>,
+  /// Called for every analysed frame with the detected frequency in Hz, e.g. for a tuner.
+  /// The caller shows the heard pitch then.
+  #[prop(optional)]
+  on_frequency: Option<Callback<Option<f64>>>,
+  /// Text of the start button
+  #[prop(optional)]
+  label: Option<&'static str>,
+) -> impl IntoView {
+  let UseUserMediaReturn {
+    stream,
+    enabled,
+    set_enabled,
+    ..
+  } = use_user_media_with_options(UseUserMediaOptions::default().audio(true).video(false));
+  let analyser = StoredValue::new_local(None::<MicrophoneAnalyser>);
+  let stabilizer = StoredValue::new(NoteStabilizer::default());
+  let (heard, set_heard) = signal(None::<PlayedNote>);
+  let (error, set_error) = signal(None::<String>);
+
+  // Connect the analyser once the microphone is granted, drop it when it stops
+  Effect::new(move |_| {
+    let connected = stream.with(|stream| match stream {
+      Some(Ok(stream)) => AudioManager::get_context()
+        .ok_or("No audio available in this browser".to_string())
+        .and_then(|ctx| {
+          MicrophoneAnalyser::new(ctx, stream)
+            .map_err(|err| format!("Failed to listen to the microphone: {err:?}"))
+        })
+        .map(Some),
+      Some(Err(_)) => Err("The microphone is not available or was not allowed".to_string()),
+      None => Ok(None),
+    });
+    match connected {
+      Ok(connected) => {
+        analyser.set_value(connected);
+        set_error.set(None);
+      }
+      Err(err) => {
+        analyser.set_value(None);
+        set_error.set(Some(err));
+      }
+    }
+    stabilizer.set_value(NoteStabilizer::default());
+    set_heard.set(None);
+  });
+
+  use_interval_fn(
+    move || {
+      let Some(frequency) = analyser.with_value(|analyser| {
+        analyser
+          .as_ref()
+          .map(|analyser| detect_pitch(&analyser.read_frame(), analyser.sample_rate()))
+      }) else {
+        return;
+      };
+      if let Some(on_frequency) = on_frequency {
+        on_frequency.run(frequency);
+      }
+      // The microphone hears the tones and plucks of the app too, they aren't played notes
+      if AudioManager::is_sounding() {
+        stabilizer.set_value(NoteStabilizer::default());
+        set_heard.set(None);
+        return;
+      }
+      let played = frequency.and_then(PlayedNote::from_frequency);
+      set_heard.set(played);
+      let stable = stabilizer
+        .try_update_value(|stabilizer| stabilizer.push(played.map(|played| played.midi)))
+        .flatten();
+      if let (Some(_), Some(played), Some(on_note_played)) = (stable, played, on_note_played) {
+        on_note_played.run(played);
+      }
+    },
+    LISTEN_INTERVAL_MS,
+  );
+
+  let toggle = move |_| {
+    if !enabled.get_untracked() {
+      // Listening starts with a click, so the audio context may resume
+      let _ = AudioManager::resume();
+    }
+    set_enabled.set(!enabled.get_untracked());
+  };
+
+  view! {
+    <div class="flex flex-wrap gap-3 justify-center items-center">
+      <button
+        class=move || {
+          if enabled.get() {
+            "py-1 px-3 text-sm text-white bg-red-500 rounded hover:bg-red-600"
+          } else {
+            "py-1 px-3 text-sm text-white bg-blue-500 rounded hover:bg-blue-600"
+          }
+        }
+        on:click=toggle
+      >
+        {move || {
+          if enabled.get() { "Stop Listening" } else { label.unwrap_or("🎤 Answer by Playing") }
+        }}
+      </button>
+      <Show when=move || enabled.get() && on_frequency.is_none()>
+        <span class="font-mono text-sm text-gray-700">
+          {move || {
+            heard
+              .get()
+              .map_or("Play a note…".to_string(), |played| format!("{played} {:+.0}¢", played.cents))
+          }}
+        </span>
+      </Show>
+      {move || error.get().map(|error| view! { <span class="text-sm text-red-600">{error}</span> })}
+    </div>
+  }
+}
@@ -0,0 +1,84 @@
+use leptos::prelude::*;
+
+use crate::models::trainer::{Challenge, Leaderboard, TrainerMode};
+
+/// Local date of a time in milliseconds since the epoch, empty outside the browser
+fn format_date(ms: f64) -> String {
+  if cfg!(target_arch = "wasm32") {
+    let date = js_sys::Date::new(&ms.into());
+    String::from(date.to_locale_date_string("default", &Default::default()))
+  } else {
+    String::new()
+  }
+}
+
+/// Best results of a challenge with the current mode and settings
+#[component]
+pub fn TrainerLeaderboard(
+  #[prop(into)] leaderboard: Signal<Leaderboard>,
+  #[prop(into)] challenge: Signal<Challenge>,
+  #[prop(into)] mode: Signal<TrainerMode>,
+  /// Summary of the tuning and trainer settings the scores were achieved with
+  #[prop(into)]
+  settings: Signal<String>,
+) -> impl IntoView {
+  let rows = move || {
+    let challenge = challenge.get();
+    leaderboard.with(|leaderboard| {
+      settings.with(|settings| {
+        leaderboard
+          .top(challenge, mode.get(), settings)
+          .into_iter()
+          .enumerate()
+          .map(|(idx, high_score)| {
+            let answers = high_score.correct + high_score.incorrect;
+            let accuracy = f64::from(high_score.correct) / f64::from(answers.max(1)) * 100.0;
+            let average = high_score
+              .average_response_ms
+              .map_or("-".to_string(), |ms| format!("{:.1}s", ms as f64 / 1000.0));
+            view! {
+              <tr class="border-t">
+                <td class="py-1 px-2">{idx + 1}</td>
+                <td class="py-1 px-2 font-semibold">
+                  {challenge.format_score(high_score.score)}
+                </td>
+                <td class="py-1 px-2 text-right">{format!("{accuracy:.0}%")}</td>
+                <td class="py-1 px-2 text-right">{average}</td>
+                <td class="py-1 px-2 text-right">{format_date(high_score.achieved_ms)}</td>
+              </tr>
+            }
+          })
+          .collect::<Vec<_>>()
+      })
+    })
+  };
+
+  view! {
+    <div class="space-y-2">
+      <h2 class="font-semibold text-center">{move || format!("{} Leaderboard", challenge.get())}</h2>
+      <p class="text-xs text-center text-gray-500">{settings}</p>
+      {move || {
+        let rows = rows();
+        if rows.is_empty() {
+          view! { <p class="text-sm text-center text-gray-500">"No scores yet"</p> }.into_any()
+        } else {
+          view! {
+            <table class="mx-auto text-sm">
+              <thead>
+                <tr>
+                  <th class="py-1 px-2 text-left">"#"</th>
+                  <th class="py-1 px-2 text-left">"Score"</th>
+                  <th class="py-1 px-2 text-right">"Accuracy"</th>
+                  <th class="py-1 px-2 text-right">"Avg. Time"</th>
+                  <th class="py-1 px-2 text-right">"Date"</th>
+                </tr>
+              </thead>
+              <tbody>{rows}</tbody>
+            </table>
+          }
+            .into_any()
+        }
+      }}
+    </div>
+  }
+}
@@ -0,0 +1,170 @@
+use leptos::prelude::*;
+use rand::Rng;
+
+use super::{RoundButtons, TrainerSession};
+use crate::components::fretboard::{FretClickEvent, FretCoord, FretRole, FretState};
+use crate::models::fretboard::FretboardModelExt;
+use crate::models::trainer::ReviewItem;
+use crate::music::notes::Note;
+
+/// Number of frets the notes are searched in when finding all positions of a note
+const FIND_WINDOW_FRETS: usize = 5;
+
+/// A random window of `FIND_WINDOW_FRETS` frets within `start..=end` that contains `fret`
+fn random_fret_window(fret: usize, start: usize, end: usize) -> (usize, usize) {
+  let offset = rand::rng().random_range(0..FIND_WINDOW_FRETS);
+  let latest_start = (end + 1).saturating_sub(FIND_WINDOW_FRETS).max(start);
+  let window_start = fret.saturating_sub(offset).clamp(start, latest_start);
+  (
+    window_start,
+    (window_start + FIND_WINDOW_FRETS - 1).min(end),
+  )
+}
+
+/// Asks for all positions of a note within a few frets. Every position sounds the same, so
+/// the answers are only given on the fretboard.
+#[component]
+pub fn FindNotesTrainer(session: TrainerSession) -> impl IntoView {
+  let current_note = RwSignal::new(Note::C);
+  // Frets searched for the note and the positions of the note in them
+  let find_window = RwSignal::new((0, 0));
+  let find_positions = RwSignal::new(Vec::<FretCoord>::new());
+  let found_positions = RwSignal::new(Vec::<FretCoord>::new());
+  let round_over = RwSignal::new(false);
+
+  let show_new_question = move || {
+    session.start_question();
+    let settings = session.settings.get_untracked();
+    session.model.with_untracked(|model| {
+      let question = session
+        .learning
+        .with_untracked(|learning| settings.next_question_fret(model, learning));
+      let Some(coord) = question else {
+        session.nothing_to_ask();
+        find_positions.set(vec![]);
+        found_positions.set(vec![]);
+        round_over.set(true);
+        return;
+      };
+      let Some(note) = model.note_from_fret(coord) else {
+        return;
+      };
+      current_note.set(note);
+      round_over.set(false);
+      model.hide_all_frets();
+      // The window holds the question fret on an enabled string, so there is at least one
+      // position to find
+      let (start, end) = random_fret_window(
+        coord.fret_idx as usize,
+        model.get_start_fret().get_untracked(),
+        model.get_end_fret().get_untracked(),
+      );
+      find_window.set((start, end));
+      find_positions.set(
+        model
+          .find_note_positions(note, start, end)
+          .into_iter()
+          .filter(|coord| settings.is_string_enabled(coord.string_idx))
+          .collect(),
+      );
+      found_positions.set(vec![]);
+    });
+  };
+
+  // Ends the round and shows the positions that weren't found
+  let finish_round = move || {
+    let missed: Vec<FretCoord> = find_positions
+      .get_untracked()
+      .into_iter()
+      .filter(|coord| !found_positions.get_untracked().contains(coord))
+      .collect();
+    // The found positions are recorded when they are clicked
+    session.record_question(
+      missed
+        .iter()
+        .map(|coord| (ReviewItem::Cell(*coord), false, None))
+        .collect(),
+    );
+    session.num_incorrect.update(|n| *n += missed.len());
+    if !missed.is_empty() {
+      session.challenge_answer(false);
+      session
+        .error_text
+        .set(format!("Missed {} positions", missed.len()));
+    }
+    session.model.with_untracked(|model| {
+      for coord in missed {
+        model.set_fret_state(
+          coord,
+          FretState::Hollow(FretRole::Wrong, current_note.get_untracked().to_string()),
+        );
+      }
+    });
+    round_over.set(true);
+  };
+
+  let on_fret_clicked = Callback::new(move |evt: FretClickEvent| {
+    let Some(clicked_note) = session.clicked_note(evt.coord) else {
+      return;
+    };
+    if round_over.get_untracked() || found_positions.get_untracked().contains(&evt.coord) {
+      return;
+    }
+    if find_positions.get_untracked().contains(&evt.coord) {
+      session.count_correct();
+      found_positions.update(|found| found.push(evt.coord));
+      let response_ms = session.take_response_time();
+      session
+        .learning
+        .update(|learning| learning.record(ReviewItem::Cell(evt.coord), true, response_ms));
+      session.model.with_untracked(|model| {
+        model.set_fret_state(
+          evt.coord,
+          FretState::Normal(FretRole::Target, clicked_note.to_string()),
+        );
+      });
+      if found_positions.get_untracked().len() == find_positions.get_untracked().len() {
+        finish_round();
+        show_new_question();
+      }
+    } else {
+      let (start, end) = find_window.get_untracked();
+      let text = if clicked_note == current_note.get_untracked() {
+        format!("Outside of frets {start}–{end}!")
+      } else {
+        "Incorrect!".to_string()
+      };
+      session.add_error(Some(evt.coord), &text);
+      session.show_wrong_fret(evt.coord, clicked_note);
+    }
+  });
+
+  session.answer_with(Some(on_fret_clicked), None);
+  session.on_next_question(show_new_question);
+
+  let found_text = move || {
+    format!(
+      " ({} of {} found)",
+      found_positions.get().len(),
+      find_positions.get().len()
+    )
+  };
+
+  view! {
+    <div class="text-center">
+      <p class="text-lg">
+        "Find all the " <b>{move || current_note.get().to_string()}</b> "'s in frets "
+        <b>{move || format!("{}–{}", find_window.get().0, find_window.get().1)}</b>
+        {found_text}
+      </p>
+    </div>
+
+    <RoundButtons
+      session
+      round_over
+      finish_label="Done"
+      on_finish=Callback::new(move |_| finish_round())
+      on_next=Callback::new(move |_| show_new_question())
+    />
+  }
+}
@@ -0,0 +1,218 @@
+use leptos::prelude::*;
+use strum::IntoEnumIterator;
+
+use super::TrainerSession;
+use crate::audio::{play_tones, AudioManager, PlayedNote};
+use crate::components::fretboard::{FretClickEvent, FretCoord, FretRole, FretState};
+use crate::components::music_selectors::IntervalButtonGrid;
+use crate::models::fretboard::FretboardModelExt;
+use crate::models::trainer::{EarPlayback, ReviewItem, TrainerSettings};
+use crate::music::intervals::Interval;
+use crate::music::notes::Note;
+
+/// Plays an interval above `reference` in the browser, nothing during server side rendering
+fn play_interval(reference: Note, interval: Interval, playback: EarPlayback) {
+  if cfg!(target_arch = "wasm32") {
+    let _ = AudioManager::resume();
+    if let Some(ctx) = AudioManager::get_context() {
+      play_tones(&ctx, &playback.tones(reference, interval));
+    }
+  }
+}
+
+/// Asks for an interval above a shown note. In ear training the interval is played as
+/// tones and can also be named with the interval buttons.
+#[component]
+pub fn IntervalTrainer(session: TrainerSession, ear_training: bool) -> impl IntoView {
+  let current_note = RwSignal::new(Note::C);
+  let current_interval = RwSignal::new(Interval::PerfectFifth);
+  // Shown fret of the reference note
+  let question_fret = RwSignal::new(FretCoord {
+    string_idx: 0,
+    fret_idx: 0,
+  });
+  // Whether there is a question, nothing fits the settings otherwise
+  let asking = RwSignal::new(false);
+
+  let show_new_question = move || {
+    session.start_question();
+    let settings = session.settings.get_untracked();
+    session.model.with_untracked(|model| {
+      let question = session
+        .learning
+        .with_untracked(|learning| settings.next_interval_question(model, learning));
+      let Some((coord, interval)) = question else {
+        session.nothing_to_ask();
+        asking.set(false);
+        return;
+      };
+      let Some(note) = model.note_from_fret(coord) else {
+        return;
+      };
+      current_note.set(note);
+      current_interval.set(interval);
+      question_fret.set(coord);
+      asking.set(true);
+      model.hide_all_frets();
+      leptos::logging::log!("New reference note: {} at {:?}", note, coord);
+      model.set_fret_state(coord, FretState::Normal(FretRole::Target, note.to_string()));
+      if ear_training {
+        play_interval(note, interval, settings.playback);
+      }
+    });
+  };
+
+  // Records the found interval and asks the next question
+  let interval_found = move || {
+    session.count_correct();
+    let correct = session.answered_first_time();
+    let response_ms = session.take_response_time();
+    session.record_question(vec![
+      (
+        ReviewItem::Cell(question_fret.get_untracked()),
+        correct,
+        response_ms,
+      ),
+      (
+        ReviewItem::Interval(current_interval.get_untracked()),
+        correct,
+        response_ms,
+      ),
+    ]);
+    show_new_question();
+  };
+
+  let on_fret_clicked = Callback::new(move |evt: FretClickEvent| {
+    let Some(clicked_note) = session.clicked_note(evt.coord) else {
+      return;
+    };
+    if !asking.get_untracked() {
+      return;
+    }
+    let target_note = current_interval
+      .get_untracked()
+      .of(current_note.get_untracked());
+
+    leptos::logging::log!(
+      "coord {:?} - target note: {:?} - clicked note: {} - expected interval: {}, got interval: {:?}",
+      evt.coord,
+      target_note,
+      clicked_note,
+      current_interval.get_untracked(),
+      Interval::from_notes(current_note.get_untracked(), clicked_note)
+    );
+
+    if clicked_note == target_note {
+      interval_found();
+    } else {
+      session.add_error(Some(evt.coord), "Incorrect!");
+      session.show_wrong_fret(evt.coord, clicked_note);
+    }
+  });
+
+  let on_interval_named = Callback::new(move |interval: Interval| {
+    if !session.accepts_answers() || !asking.get_untracked() {
+      return;
+    }
+    if interval == current_interval.get_untracked() {
+      interval_found();
+    } else {
+      session.add_error(
+        Some(question_fret.get_untracked()),
+        &format!("Incorrect, it's not a {interval}!"),
+      );
+    }
+  });
+
+  // The octave of a played note doesn't matter
+  let on_note_played = Callback::new(move |played: PlayedNote| {
+    if !session.accepts_answers() || !asking.get_untracked() {
+      return;
+    }
+    if played.note()
+      == current_interval
+        .get_untracked()
+        .of(current_note.get_untracked())
+    {
+      interval_found();
+    } else {
+      session.add_error(
+        Some(question_fret.get_untracked()),
+        &format!("Incorrect, you played {played}!"),
+      );
+    }
+  });
+
+  session.answer_with(Some(on_fret_clicked), Some(on_note_played));
+  session.on_next_question(show_new_question);
+
+  // Plays the current question again
+  let replay = move || {
+    play_interval(
+      current_note.get_untracked(),
+      current_interval.get_untracked(),
+      session.settings.with_untracked(|s| s.playback),
+    );
+  };
+
+  let note_str = move || current_note.get().to_string();
+  let question = move || {
+    if ear_training {
+      view! { "Which interval do you hear above " <b>{note_str}</b> "?" }.into_any()
+    } else {
+      view! {
+        "Looking for " <b>{move || format!("{} ", current_interval.get())}</b> "of "
+        <b>{move || format!("{} ", note_str())}</b>
+      }
+      .into_any()
+    }
+  };
+
+  view! {
+    <div class="text-center">
+      <p class="text-lg">{question}</p>
+    </div>
+
+    <Show when=move || ear_training>
+      <div class="flex gap-4 justify-center items-end">
+        <div class="space-y-2">
+          <label class="block text-sm font-medium">"Playback"</label>
+          <select
+            class="p-2 rounded-md border"
+            on:change=move |ev| {
+              let value = event_target_value(&ev);
+              if let Some(selected) = EarPlayback::iter().find(|p| p.to_string() == value) {
+                session.settings.update(|s| s.playback = selected);
+              }
+            }
+          >
+            {EarPlayback::iter()
+              .map(|option| {
+                view! {
+                  <option
+                    value=option.to_string()
+                    selected=move || session.settings.with(|s| s.playback) == option
+                  >
+                    {option.to_string()}
+                  </option>
+                }
+              })
+              .collect_view()}
+          </select>
+        </div>
+        <button
+          class="py-2 px-4 text-white bg-green-500 rounded hover:bg-green-600"
+          on:click=move |_| replay()
+        >
+          "Play Again"
+        </button>
+      </div>
+      <div class="mx-auto w-full max-w-md">
+        <IntervalButtonGrid
+          intervals=Signal::derive(move || session.settings.with(TrainerSettings::interval_pool))
+          on_interval_clicked=on_interval_named
+        />
+      </div>
+    </Show>
+  }
+}
@@ -0,0 +1,13 @@
+mod find_notes;
+mod intervals;
+mod note_names;
+mod round_buttons;
+mod session;
+mod theory;
+
+pub use find_notes::FindNotesTrainer;
+pub use intervals::IntervalTrainer;
+pub use note_names::NoteNameTrainer;
+use round_buttons::RoundButtons;
+pub use session::{now_ms, TrainerSession};
+pub use theory::TheoryTrainer;
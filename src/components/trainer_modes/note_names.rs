@@ -0,0 +1,82 @@
+use leptos::prelude::*;
+
+use super::TrainerSession;
+use crate::audio::PlayedNote;
+use crate::components::fretboard::{FretCoord, FretRole, FretState};
+use crate::components::music_selectors::NoteButtonGrid;
+use crate::models::fretboard::FretboardModelExt;
+use crate::models::trainer::ReviewItem;
+use crate::music::notes::Note;
+
+/// Asks for the name of a marked fret, answered with the note buttons or on the instrument
+#[component]
+pub fn NoteNameTrainer(session: TrainerSession) -> impl IntoView {
+  let current_note = RwSignal::new(Note::C);
+  // Fret whose name is asked for
+  let question_fret = RwSignal::new(FretCoord {
+    string_idx: 0,
+    fret_idx: 0,
+  });
+  // Whether there is a question, nothing fits the settings otherwise
+  let asking = RwSignal::new(false);
+
+  let show_new_question = move || {
+    session.start_question();
+    let settings = session.settings.get_untracked();
+    session.model.with_untracked(|model| {
+      let question = session
+        .learning
+        .with_untracked(|learning| settings.next_question_fret(model, learning));
+      let Some(coord) = question else {
+        session.nothing_to_ask();
+        asking.set(false);
+        return;
+      };
+      let Some(note) = model.note_from_fret(coord) else {
+        return;
+      };
+      current_note.set(note);
+      question_fret.set(coord);
+      asking.set(true);
+      model.hide_all_frets();
+      model.set_fret_state(coord, FretState::Normal(FretRole::Target, "?".to_string()));
+    });
+  };
+
+  let on_note_named = Callback::new(move |note: Note| {
+    if !session.accepts_answers() || !asking.get_untracked() {
+      return;
+    }
+    let coord = question_fret.get_untracked();
+    if note == current_note.get_untracked() {
+      session.count_correct();
+      session.record_question(vec![(
+        ReviewItem::Cell(coord),
+        session.answered_first_time(),
+        session.take_response_time(),
+      )]);
+      show_new_question();
+    } else {
+      session.add_error(Some(coord), &format!("Incorrect, it's not {note}!"));
+    }
+  });
+
+  // Answered with the note buttons, the fretboard only shows the question
+  session.answer_with(
+    None,
+    Some(Callback::new(move |played: PlayedNote| {
+      on_note_named.run(played.note())
+    })),
+  );
+  session.on_next_question(show_new_question);
+
+  view! {
+    <div class="text-center">
+      <p class="text-lg">"Which note is marked on the fretboard?"</p>
+    </div>
+
+    <div class="mx-auto w-full max-w-md">
+      <NoteButtonGrid on_note_clicked=on_note_named />
+    </div>
+  }
+}
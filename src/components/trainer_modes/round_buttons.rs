@@ -0,0 +1,43 @@
+use leptos::prelude::*;
+
+use super::TrainerSession;
+
+/// Ends a round whose answers are shown before the next question, then asks the next one
+#[component]
+pub fn RoundButtons(
+  session: TrainerSession,
+  #[prop(into)] round_over: Signal<bool>,
+  /// Text of the button that ends the round
+  finish_label: &'static str,
+  on_finish: Callback<()>,
+  on_next: Callback<()>,
+) -> impl IntoView {
+  view! {
+    <div class="flex justify-center">
+      <Show
+        when=move || round_over.get()
+        fallback=move || {
+          view! {
+            <button
+              class="py-2 px-4 text-white bg-blue-500 rounded hover:bg-blue-600"
+              on:click=move |_| {
+                if session.accepts_answers() {
+                  on_finish.run(());
+                }
+              }
+            >
+              {finish_label}
+            </button>
+          }
+        }
+      >
+        <button
+          class="py-2 px-4 text-white bg-green-500 rounded hover:bg-green-600"
+          on:click=move |_| on_next.run(())
+        >
+          "Next"
+        </button>
+      </Show>
+    </div>
+  }
+}
@@ -0,0 +1,269 @@
+use leptos::prelude::*;
+
+use crate::audio::PlayedNote;
+use crate::components::fretboard::{FretClickEvent, FretCoord, FretRole, FretState};
+use crate::models::fretboard::{FretboardModel, FretboardModelExt};
+use crate::models::trainer::{
+  Challenge, ChallengeRun, Leaderboard, LearningState, ReviewItem, TrainerMode, TrainerSettings,
+};
+use crate::music::notes::Note;
+
+/// Milliseconds since the epoch in the browser, 0 during server side rendering
+pub fn now_ms() -> f64 {
+  if cfg!(target_arch = "wasm32") {
+    js_sys::Date::now()
+  } else {
+    0.0
+  }
+}
+
+/// State the trainer modes share: the fretboard, the scores, the answer history and the
+/// timed challenge. Each mode asks its own questions and checks the answers to them.
+#[derive(Clone, Copy)]
+pub struct TrainerSession {
+  pub mode: RwSignal<TrainerMode>,
+  pub settings: RwSignal<TrainerSettings>,
+  pub learning: RwSignal<LearningState>,
+  pub leaderboard: RwSignal<Leaderboard>,
+  pub model: RwSignal<FretboardModel>,
+  pub tuning_notes: Signal<Vec<Note>>,
+  /// Tuning and settings of the challenge, high scores are compared under the same ones
+  pub settings_summary: Signal<String>,
+  pub challenge: RwSignal<Challenge>,
+  /// The current or the last challenge run
+  pub run: RwSignal<Option<ChallengeRun>>,
+  pub result_text: RwSignal<String>,
+  pub num_correct: RwSignal<usize>,
+  pub num_incorrect: RwSignal<usize>,
+  pub error_text: RwSignal<String>,
+  error_coords: RwSignal<Vec<FretCoord>>,
+  /// Whether the current question had a wrong answer, so it is reviewed again soon
+  had_mistake: RwSignal<bool>,
+  /// Start of the question or time of the last answer, to time the answers
+  answer_started: StoredValue<f64>,
+  /// Asks the shown mode for a new question, e.g. after a tuning change
+  next_question: Trigger,
+  /// Answer handlers of the shown mode
+  on_fret_clicked: StoredValue<Option<Callback<FretClickEvent>>>,
+  on_note_played: StoredValue<Option<Callback<PlayedNote>>>,
+}
+
+impl TrainerSession {
+  pub fn new(
+    mode: RwSignal<TrainerMode>,
+    settings: RwSignal<TrainerSettings>,
+    learning: RwSignal<LearningState>,
+    leaderboard: RwSignal<Leaderboard>,
+    model: RwSignal<FretboardModel>,
+    tuning_notes: Signal<Vec<Note>>,
+    settings_summary: Signal<String>,
+  ) -> Self {
+    Self {
+      mode,
+      settings,
+      learning,
+      leaderboard,
+      model,
+      tuning_notes,
+      settings_summary,
+      challenge: RwSignal::new(Challenge::default()),
+      run: RwSignal::new(None),
+      result_text: RwSignal::new(String::new()),
+      num_correct: RwSignal::new(0),
+      num_incorrect: RwSignal::new(0),
+      error_text: RwSignal::new(String::new()),
+      error_coords: RwSignal::new(vec![]),
+      had_mistake: RwSignal::new(false),
+      answer_started: StoredValue::new(0.0),
+      next_question: Trigger::new(),
+      on_fret_clicked: StoredValue::new(None),
+      on_note_played: StoredValue::new(None),
+    }
+  }
+
+  pub fn is_running(self) -> bool {
+    self
+      .run
+      .with_untracked(|run| run.as_ref().is_some_and(|run| !run.is_finished()))
+  }
+
+  /// Practice takes answers all the time, challenges only while they run
+  pub fn accepts_answers(self) -> bool {
+    !self.challenge.get_untracked().is_timed() || self.is_running()
+  }
+
+  fn finish_challenge(self) {
+    let high_score = self.run.with_untracked(|run| {
+      run.as_ref().and_then(|run| {
+        run.high_score(
+          self.mode.get_untracked(),
+          &self.settings_summary.get_untracked(),
+        )
+      })
+    });
+    let text = match high_score {
+      Some(high_score) => {
+        let score = high_score.challenge.format_score(high_score.score);
+        let mut rank = None;
+        self
+          .leaderboard
+          .update(|leaderboard| rank = leaderboard.add(high_score));
+        match rank {
+          Some(rank) => format!("Finished with {score}, place {rank} on the leaderboard!"),
+          None => format!("Finished with {score}"),
+        }
+      }
+      None => "Finished without a score".to_string(),
+    };
+    self.result_text.set(text);
+  }
+
+  /// Counts an answer for the running challenge and ends it when it is over
+  pub fn challenge_answer(self, correct: bool) {
+    if !self.is_running() {
+      return;
+    }
+    let mut over = false;
+    self.run.update(|run| {
+      if let Some(run) = run {
+        over = run.answer(correct, now_ms());
+      }
+    });
+    if over {
+      self.finish_challenge();
+    }
+  }
+
+  /// Advances the clock of the running challenge and ends it when the time is up
+  pub fn tick(self, now: f64) {
+    let mut over = false;
+    self.run.update(|run| {
+      if let Some(run) = run {
+        over = run.tick(now);
+      }
+    });
+    if over {
+      self.finish_challenge();
+    }
+  }
+
+  /// Asks the shown mode for a new question
+  pub fn ask_next_question(self) {
+    self.next_question.notify();
+  }
+
+  /// Asks the first question with `ask` and every next one the session asks for
+  pub fn on_next_question(self, ask: impl Fn() + 'static) {
+    ask();
+    Effect::new(move |prev: Option<()>| {
+      self.next_question.track();
+      if prev.is_some() {
+        ask();
+      }
+    });
+  }
+
+  /// Sends the answers given on the fretboard and on the instrument to the shown mode
+  pub fn answer_with(
+    self,
+    on_fret_clicked: Option<Callback<FretClickEvent>>,
+    on_note_played: Option<Callback<PlayedNote>>,
+  ) {
+    self.on_fret_clicked.set_value(on_fret_clicked);
+    self.on_note_played.set_value(on_note_played);
+  }
+
+  pub fn fret_clicked(self, evt: FretClickEvent) {
+    if let Some(on_fret_clicked) = self.on_fret_clicked.get_value() {
+      on_fret_clicked.run(evt);
+    }
+  }
+
+  pub fn note_played(self, played: PlayedNote) {
+    if let Some(on_note_played) = self.on_note_played.get_value() {
+      on_note_played.run(played);
+    }
+  }
+
+  /// Resets the mistakes and the answer time for a new question
+  pub fn start_question(self) {
+    self.error_coords.set(vec![]);
+    self.error_text.set("".to_string());
+    self.had_mistake.set(false);
+    self.answer_started.set_value(now_ms());
+  }
+
+  /// Clears the fretboard when nothing fits the settings, e.g. all strings are disabled.
+  /// Questions on a disabled string can't be answered, so there is nothing to ask.
+  pub fn nothing_to_ask(self) {
+    self.model.with_untracked(|model| model.hide_all_frets());
+    self
+      .error_text
+      .set("Enable a string to practice on".to_string());
+  }
+
+  /// Whether the current question was answered without a mistake
+  pub fn answered_first_time(self) -> bool {
+    !self.had_mistake.get_untracked()
+  }
+
+  /// Milliseconds since the question started or the last answer
+  pub fn take_response_time(self) -> Option<u64> {
+    let now = now_ms();
+    let started = self.answer_started.get_value();
+    self.answer_started.set_value(now);
+    Some((now - started).max(0.0) as u64)
+  }
+
+  /// The note of a fret clicked as an answer, if answers are taken and its string is enabled
+  pub fn clicked_note(self, coord: FretCoord) -> Option<Note> {
+    if !self.accepts_answers() {
+      return None;
+    }
+    // Disabled strings have no answers
+    if !self
+      .settings
+      .with_untracked(|s| s.is_string_enabled(coord.string_idx))
+    {
+      return None;
+    }
+    self
+      .model
+      .with_untracked(|model| model.note_from_fret(coord))
+  }
+
+  pub fn count_correct(self) {
+    self.num_correct.update(|n| *n += 1);
+    self.challenge_answer(true);
+  }
+
+  /// Counts a mistake, marking the fret if the answer was given on the fretboard
+  pub fn add_error(self, coord: Option<FretCoord>, text: &str) {
+    self.challenge_answer(false);
+    if self.error_text.get_untracked().is_empty() {
+      self.error_text.set(text.to_string());
+    }
+    self.num_incorrect.update(|n| *n += 1);
+    self.had_mistake.set(true);
+    if let Some(coord) = coord.filter(|coord| !self.error_coords.get_untracked().contains(coord)) {
+      self.error_coords.update(|coords| coords.push(coord));
+    }
+  }
+
+  /// Shows the note of a wrongly clicked fret
+  pub fn show_wrong_fret(self, coord: FretCoord, note: Note) {
+    self.model.with_untracked(|model| {
+      model.set_fret_state(coord, FretState::Normal(FretRole::Wrong, note.to_string()));
+    });
+  }
+
+  /// Records the answers of the finished question for the review schedule
+  pub fn record_question(self, answers: Vec<(ReviewItem, bool, Option<u64>)>) {
+    self.learning.update(|learning| {
+      for (item, correct, response_ms) in answers {
+        learning.record(item, correct, response_ms);
+      }
+      learning.finish_question();
+    });
+  }
+}
@@ -0,0 +1,160 @@
+use leptos::prelude::*;
+
+use super::{RoundButtons, TrainerSession};
+use crate::audio::PlayedNote;
+use crate::components::fretboard::{FretClickEvent, FretCoord, FretRole, FretState};
+use crate::models::fretboard::FretboardModelExt;
+use crate::models::trainer::{ReviewItem, TheoryQuestion};
+
+/// Asks for a degree of a scale or, with `chord_tones`, for a tone of a chord whose root
+/// is shown. The answers are shown once the question is answered or given up.
+#[component]
+pub fn TheoryTrainer(session: TrainerSession, chord_tones: bool) -> impl IntoView {
+  let theory_question = RwSignal::new(None::<TheoryQuestion>);
+  let round_over = RwSignal::new(false);
+
+  let show_new_question = move || {
+    session.start_question();
+    let settings = session.settings.get_untracked();
+    session.model.with_untracked(|model| {
+      let question = session.learning.with_untracked(|learning| {
+        if chord_tones {
+          settings.next_chord_tone_question(model, learning)
+        } else {
+          settings.next_scale_degree_question(model, learning)
+        }
+      });
+      model.hide_all_frets();
+      if let Some((coord, note)) = question.and_then(|question| question.shown_fret()) {
+        model.set_fret_state(coord, FretState::Normal(FretRole::Root, note.to_string()));
+      }
+      theory_question.set(question);
+    });
+    round_over.set(false);
+  };
+
+  // Ends the question and shows every correct position, the answered one filled
+  let reveal_answers = move |answered: Option<FretCoord>| {
+    let Some(question) = theory_question.get_untracked() else {
+      return;
+    };
+    let label = question.degree_name();
+    session.model.with_untracked(|model| {
+      let answers = session.settings.with_untracked(|settings| {
+        question.answers(model, |string_idx| settings.is_string_enabled(string_idx))
+      });
+      for coord in answers {
+        let state = if Some(coord) == answered {
+          FretState::Normal(FretRole::Target, label.clone())
+        } else {
+          FretState::Hollow(FretRole::Target, label.clone())
+        };
+        model.set_fret_state(coord, state);
+      }
+    });
+    round_over.set(true);
+  };
+
+  let give_up = move || {
+    if round_over.get_untracked() {
+      return;
+    }
+    session.challenge_answer(false);
+    session.num_incorrect.update(|n| *n += 1);
+    if let Some(question) = theory_question.get_untracked() {
+      session.record_question(vec![(
+        ReviewItem::Interval(question.interval()),
+        false,
+        None,
+      )]);
+    }
+    reveal_answers(None);
+  };
+
+  let on_fret_clicked = Callback::new(move |evt: FretClickEvent| {
+    let Some(clicked_note) = session.clicked_note(evt.coord) else {
+      return;
+    };
+    let Some(question) = theory_question.get_untracked() else {
+      return;
+    };
+    if round_over.get_untracked()
+      || question.shown_fret().map(|(coord, _)| coord) == Some(evt.coord)
+    {
+      return;
+    }
+    if session
+      .model
+      .with_untracked(|model| question.is_answer(model, evt.coord))
+    {
+      session.count_correct();
+      let correct = session.answered_first_time();
+      let response_ms = session.take_response_time();
+      session.record_question(vec![
+        (ReviewItem::Cell(evt.coord), correct, response_ms),
+        (
+          ReviewItem::Interval(question.interval()),
+          correct,
+          response_ms,
+        ),
+      ]);
+      reveal_answers(Some(evt.coord));
+    } else {
+      let text = if clicked_note == question.target_note() {
+        "Wrong string!"
+      } else {
+        "Incorrect!"
+      };
+      session.add_error(Some(evt.coord), text);
+      session.show_wrong_fret(evt.coord, clicked_note);
+    }
+  });
+
+  // The octave doesn't matter and there is no fret to mark, so a played note only counts
+  // for the interval
+  let on_note_played = Callback::new(move |played: PlayedNote| {
+    if !session.accepts_answers() || round_over.get_untracked() {
+      return;
+    }
+    let Some(question) = theory_question.get_untracked() else {
+      return;
+    };
+    if played.note() == question.target_note() {
+      session.count_correct();
+      session.record_question(vec![(
+        ReviewItem::Interval(question.interval()),
+        session.answered_first_time(),
+        session.take_response_time(),
+      )]);
+      reveal_answers(None);
+    } else {
+      session.add_error(None, &format!("Incorrect, you played {played}!"));
+    }
+  });
+
+  session.answer_with(Some(on_fret_clicked), Some(on_note_played));
+  session.on_next_question(show_new_question);
+
+  let prompt = move || {
+    theory_question.with(|question| {
+      question.map_or(
+        "Nothing to ask with these settings".to_string(),
+        |question| session.tuning_notes.with(|tuning| question.prompt(tuning)),
+      )
+    })
+  };
+
+  view! {
+    <div class="text-center">
+      <p class="text-lg">{prompt}</p>
+    </div>
+
+    <RoundButtons
+      session
+      round_over
+      finish_label="Show Answers"
+      on_finish=Callback::new(move |_| give_up())
+      on_next=Callback::new(move |_| show_new_question())
+    />
+  }
+}
@@ -0,0 +1,128 @@
+use leptos::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::components::fret_range_selector::FretRangeSelector;
+use crate::components::fretboard::definitions::MAX_FRETS;
+use crate::models::trainer::TrainerSettings;
+use crate::music::intervals::Interval;
+use crate::music::notes::Note;
+
+/// Collapsible panel to restrict the questions of the fretboard trainer
+#[component]
+pub fn TrainerSettingsPanel(
+  settings: RwSignal<TrainerSettings>,
+  /// Open string notes, to label the string toggles
+  #[prop(into)]
+  tuning: Signal<Vec<Note>>,
+) -> impl IntoView {
+  let start_fret = Signal::derive(move || settings.with(|s| s.start_fret));
+  let end_fret = Signal::derive(move || settings.with(|s| s.end_fret));
+
+  view! {
+    <details class="p-4 rounded-lg border">
+      <summary class="font-semibold cursor-pointer">"Trainer Settings"</summary>
+      <div class="grid grid-cols-1 gap-6 mt-4 md:grid-cols-2">
+        <FretRangeSelector
+          start_fret
+          end_fret
+          label="Fret Range"
+          max=MAX_FRETS - 1
+          on_start_fret_change=Callback::new(move |start| {
+            settings.update(|s| s.start_fret = start)
+          })
+          on_end_fret_change=Callback::new(move |end| settings.update(|s| s.end_fret = end))
+        />
+
+        <div class="space-y-2">
+          <label class="block text-sm font-medium text-gray-700">"Strings"</label>
+          <div class="flex flex-wrap gap-3">
+            {move || {
+              tuning
+                .get()
+                .into_iter()
+                .enumerate()
+                .map(|(string_idx, note)| {
+                  let string_idx = string_idx as u8;
+                  view! {
+                    <label class="flex gap-1 items-center text-sm">
+                      <input
+                        type="checkbox"
+                        prop:checked=move || settings.with(|s| s.is_string_enabled(string_idx))
+                        on:change=move |_| settings.update(|s| s.toggle_string(string_idx))
+                      />
+                      {format!("{} ({note})", string_idx + 1)}
+                    </label>
+                  }
+                })
+                .collect_view()
+            }}
+          </div>
+
+          <label class="block text-sm font-medium text-gray-700">"Key"</label>
+          <select
+            class="p-2 w-full rounded-md border"
+            on:change=move |ev| {
+              let key = event_target_value(&ev).parse::<Note>().ok();
+              settings.update(|s| s.key = key);
+            }
+          >
+            <option value="" selected=move || settings.with(|s| s.key.is_none())>
+              "Any key"
+            </option>
+            {Note::mapping()
+              .iter()
+              .map(|(note, display_str)| {
+                let note = *note;
+                view! {
+                  <option
+                    value=*display_str
+                    selected=move || settings.with(|s| s.key == Some(note))
+                  >
+                    {format!("{display_str} major")}
+                  </option>
+                }
+              })
+              .collect_view()}
+          </select>
+        </div>
+
+        <div class="space-y-2 md:col-span-2">
+          <div class="flex gap-2 items-center">
+            <label class="text-sm font-medium text-gray-700">"Intervals"</label>
+            <button
+              class="py-1 px-2 text-xs text-white bg-blue-500 rounded hover:bg-blue-600"
+              on:click=move |_| {
+                settings.update(|s| s.intervals = TrainerSettings::beginner_intervals())
+              }
+            >
+              "Beginner"
+            </button>
+            <button
+              class="py-1 px-2 text-xs text-white bg-blue-500 rounded hover:bg-blue-600"
+              on:click=move |_| settings.update(|s| s.intervals = TrainerSettings::all_intervals())
+            >
+              "All"
+            </button>
+          </div>
+          <div class="grid grid-cols-2 gap-1 sm:grid-cols-4">
+            {Interval::iter()
+              .filter(|interval| interval != &Interval::Unison)
+              .map(|interval| {
+                view! {
+                  <label class="flex gap-1 items-center text-sm">
+                    <input
+                      type="checkbox"
+                      prop:checked=move || settings.with(|s| s.intervals.contains(&interval))
+                      on:change=move |_| settings.update(|s| s.toggle_interval(interval))
+                    />
+                    {interval.to_string()}
+                  </label>
+                }
+              })
+              .collect_view()}
+          </div>
+        </div>
+      </div>
+    </details>
+  }
+}
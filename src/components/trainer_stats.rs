@@ -0,0 +1,134 @@
+use leptos::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::components::fretboard::{DroneString, FretRole, FretState, FretboardModelAdapter};
+use crate::models::fretboard::FretboardModelBuilder;
+use crate::models::trainer::{HeatMetric, LearningState, ReviewItem};
+use crate::music::intervals::Interval;
+use crate::music::notes::Note;
+
+/// Heatmap of the trainer results on the fretboard and the accuracy of each interval
+#[component]
+pub fn TrainerStats(
+  #[prop(into)] learning: Signal<LearningState>,
+  #[prop(into)] tuning: Signal<Vec<Note>>,
+  #[prop(into)] drone: Signal<Option<DroneString>>,
+  #[prop(into)] start_fret: Signal<usize>,
+  #[prop(into)] end_fret: Signal<usize>,
+) -> impl IntoView {
+  let metric = RwSignal::new(HeatMetric::default());
+  let heatmap_model = RwSignal::new(
+    FretboardModelBuilder::new()
+      .start_fret(start_fret)
+      .end_fret(end_fret)
+      .tuning(tuning)
+      .drone(drone)
+      .build()
+      .expect("The trainer board is valid"),
+  );
+
+  // Colour every position that was asked for
+  Effect::new(move |_| {
+    let metric = metric.get();
+    tuning.track();
+    drone.track();
+    learning.with(|learning| {
+      heatmap_model.with_untracked(|model| {
+        model.get_fret_states().with_untracked(|fret_states| {
+          fret_states.update_all(|coord| {
+            if !model.is_playable(coord) {
+              return FretState::Hidden;
+            }
+            learning
+              .get(ReviewItem::Cell(coord))
+              .and_then(|state| metric.heat(state))
+              .map_or(FretState::Hidden, |(level, label)| {
+                FretState::Normal(FretRole::Heat(level), label)
+              })
+          });
+        });
+      });
+    });
+  });
+
+  let interval_rows = move || {
+    learning.with(|learning| {
+      Interval::iter()
+        .filter_map(|interval| {
+          let state = learning.get(ReviewItem::Interval(interval))?;
+          let accuracy = state.accuracy()?;
+          let average = state
+            .average_response_ms()
+            .map_or("-".to_string(), |ms| format!("{:.1}s", ms / 1000.0));
+          Some(view! {
+            <tr class="border-t">
+              <td class="py-1 px-2">{interval.to_string()}</td>
+              <td class="py-1 px-2 text-right">{state.correct}</td>
+              <td class="py-1 px-2 text-right">{state.incorrect}</td>
+              <td class="py-1 px-2 text-right">{format!("{:.0}%", accuracy * 100.0)}</td>
+              <td class="py-1 px-2 text-right">{average}</td>
+            </tr>
+          })
+        })
+        .collect_view()
+    })
+  };
+  let has_interval_results = move || {
+    learning.with(|learning| {
+      Interval::iter().any(|interval| learning.get(ReviewItem::Interval(interval)).is_some())
+    })
+  };
+
+  view! {
+    <details class="p-4 rounded-lg border">
+      <summary class="font-semibold cursor-pointer">"Statistics"</summary>
+      <div class="mt-4 space-y-4">
+        <div class="flex gap-4 items-center">
+          <label class="text-sm font-medium text-gray-700">"Heatmap"</label>
+          <select
+            class="p-2 rounded-md border"
+            on:change=move |ev| {
+              let value = event_target_value(&ev);
+              if let Some(selected) = HeatMetric::iter().find(|m| m.to_string() == value) {
+                metric.set(selected);
+              }
+            }
+          >
+            {HeatMetric::iter()
+              .map(|option| {
+                view! {
+                  <option value=option.to_string() selected=move || metric.get() == option>
+                    {option.to_string()}
+                  </option>
+                }
+              })
+              .collect_view()}
+          </select>
+          <span class="text-sm text-gray-600">
+            {move || format!("{} questions answered", learning.with(|l| l.step))}
+          </span>
+        </div>
+
+        <FretboardModelAdapter model=heatmap_model />
+
+        <Show
+          when=has_interval_results
+          fallback=|| view! { <p class="text-sm text-gray-500">"No intervals answered yet"</p> }
+        >
+          <table class="mx-auto text-sm">
+            <thead>
+              <tr>
+                <th class="py-1 px-2 text-left">"Interval"</th>
+                <th class="py-1 px-2 text-right">"Correct"</th>
+                <th class="py-1 px-2 text-right">"Incorrect"</th>
+                <th class="py-1 px-2 text-right">"Accuracy"</th>
+                <th class="py-1 px-2 text-right">"Avg. Time"</th>
+              </tr>
+            </thead>
+            <tbody>{interval_rows}</tbody>
+          </table>
+        </Show>
+      </div>
+    </details>
+  }
+}
@@ -0,0 +1,186 @@
+use leptos::prelude::*;
+
+use crate::components::fretboard::base::MAX_STRINGS;
+use crate::models::fretboard::use_instrument;
+use crate::models::storage::{delete_custom_tuning, load_custom_tunings, save_custom_tuning};
+use crate::music::Tuning;
+
+/// Tuning dropdown with the built-in tunings of the app instrument and user-defined tunings.
+///
+/// Custom tunings can be created and deleted in place and are persisted in local storage.
+/// A selected tuning of another instrument, e.g. from a saved exercise, stays listed.
+#[component]
+pub fn TuningSelector(
+  /// Currently selected tuning
+  #[prop(into)]
+  value: Signal<Tuning>,
+  on_tuning_change: Callback<Tuning>,
+  #[prop(optional)] label: Option<&'static str>,
+) -> impl IntoView {
+  let custom_tunings = RwSignal::new(load_custom_tunings());
+  let show_editor = RwSignal::new(false);
+  let custom_name = RwSignal::new(String::new());
+  let custom_notes = RwSignal::new(String::new());
+  let editor_error = RwSignal::new(None::<String>);
+  let instrument = use_instrument();
+  let library = Memo::new(move |_| instrument.get().profile().tunings);
+
+  let is_custom = move || {
+    value.with(|tuning| custom_tunings.with(|custom| custom.iter().any(|t| t.name == tuning.name)))
+  };
+
+  let on_select = move |name: String| {
+    let tuning = Tuning::from_library(&name)
+      .or_else(|| custom_tunings.with(|custom| custom.iter().find(|t| t.name == name).cloned()));
+    if let Some(tuning) = tuning {
+      on_tuning_change.run(tuning);
+    }
+  };
+
+  let open_editor = move |_| {
+    value.with_untracked(|tuning| {
+      custom_name.set(format!("{} (custom)", tuning.name));
+      custom_notes.set(tuning.notes_string());
+    });
+    editor_error.set(None);
+    show_editor.set(true);
+  };
+
+  let save_tuning = move |_| {
+    let name = custom_name.get_untracked().trim().to_string();
+    if name.is_empty() {
+      editor_error.set(Some("Name is required".to_string()));
+      return;
+    }
+    let tuning = match Tuning::parse_notes(&custom_notes.get_untracked()) {
+      Ok(notes) if notes.len() > MAX_STRINGS => {
+        editor_error.set(Some(format!("At most {MAX_STRINGS} strings are supported")));
+        return;
+      }
+      Ok(notes) => Tuning::new(name, notes),
+      Err(err) => {
+        editor_error.set(Some(err));
+        return;
+      }
+    };
+    if let Err(err) = save_custom_tuning(&tuning) {
+      editor_error.set(Some(err));
+      return;
+    }
+    custom_tunings.set(load_custom_tunings());
+    show_editor.set(false);
+    on_tuning_change.run(tuning);
+  };
+
+  let delete_tuning = move |_| {
+    let name = value.with_untracked(|tuning| tuning.name.clone());
+    if delete_custom_tuning(&name).is_ok() {
+      custom_tunings.set(load_custom_tunings());
+      on_tuning_change.run(Tuning::standard());
+    }
+  };
+
+  let tuning_option = move |tuning: Tuning| {
+    let name = tuning.name.clone();
+    view! {
+      <option
+        value=tuning.name.clone()
+        selected=move || value.with(|selected| selected.name == name)
+      >
+        {tuning.to_string()}
+      </option>
+    }
+  };
+
+  view! {
+    <div class="space-y-2">
+      <label class="block text-sm font-medium">{label.unwrap_or("Tuning")}</label>
+      <div class="flex gap-2">
+        <select
+          class="flex-1 p-2 rounded-md border"
+          on:change=move |e| on_select(event_target_value(&e))
+        >
+          {move || {
+            let selected = value.get();
+            let is_listed = library.with(|library| library.contains(&selected))
+              || custom_tunings.with(|custom| custom.contains(&selected));
+            (!is_listed).then(|| tuning_option(selected))
+          }}
+          <optgroup label=move || instrument.get().to_string()>
+            {move || library.get().into_iter().map(tuning_option).collect_view()}
+          </optgroup>
+          {move || {
+            let custom = custom_tunings.get();
+            (!custom.is_empty())
+              .then(|| {
+                view! {
+                  <optgroup label="Custom">
+                    {custom.into_iter().map(tuning_option).collect_view()}
+                  </optgroup>
+                }
+              })
+          }}
+        </select>
+        <button
+          type="button"
+          class="py-1 px-2 text-xs bg-gray-200 rounded hover:bg-gray-300"
+          on:click=open_editor
+        >
+          "Custom…"
+        </button>
+        <Show when=is_custom>
+          <button
+            type="button"
+            class="py-1 px-2 text-xs text-red-500 hover:text-red-700"
+            on:click=delete_tuning
+          >
+            "Delete"
+          </button>
+        </Show>
+      </div>
+
+      <Show when=move || show_editor.get()>
+        <div class="p-2 space-y-2 bg-white rounded border border-gray-200">
+          <div class="flex flex-wrap gap-2 items-end">
+            <label class="flex flex-col flex-1 text-xs text-gray-600">
+              "Name"
+              <input
+                type="text"
+                class="py-1 px-2 text-sm rounded border border-gray-300"
+                prop:value=move || custom_name.get()
+                on:input=move |e| custom_name.set(event_target_value(&e))
+              />
+            </label>
+            <label class="flex flex-col flex-1 text-xs text-gray-600">
+              "Notes (lowest string first)"
+              <input
+                type="text"
+                class="py-1 px-2 font-mono text-sm rounded border border-gray-300"
+                prop:value=move || custom_notes.get()
+                on:input=move |e| custom_notes.set(event_target_value(&e))
+                placeholder="D A D G B E"
+              />
+            </label>
+            <button
+              type="button"
+              class="py-1 px-2 text-xs text-white bg-blue-500 rounded hover:bg-blue-600"
+              on:click=save_tuning
+            >
+              "Save"
+            </button>
+            <button
+              type="button"
+              class="py-1 px-2 text-xs text-gray-600 hover:text-gray-800"
+              on:click=move |_| show_editor.set(false)
+            >
+              "Cancel"
+            </button>
+          </div>
+          {move || {
+            editor_error.get().map(|err| view! { <p class="text-xs text-red-600">{err}</p> })
+          }}
+        </div>
+      </Show>
+    </div>
+  }
+}
@@ -1,9 +1,13 @@
+#![recursion_limit = "256"]
+
 pub mod app;
 pub mod audio;
 pub mod components;
 pub mod models;
 pub mod music;
 pub(crate) mod pages;
+#[cfg(feature = "ssr")]
+pub mod server;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
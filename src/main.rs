@@ -1,12 +1,15 @@
+#![recursion_limit = "256"]
+
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
-  use axum::Router;
+  use axum::{routing::get, Router};
   use leptos::logging::log;
   use leptos::prelude::*;
   use leptos_axum::{generate_route_list, LeptosRoutes};
   use rust_guitar_app::app::shell;
   use rust_guitar_app::app::App;
+  use rust_guitar_app::server::diagram::{chord_diagram, diagram_png, diagram_svg, scale_diagram};
 
   let conf = get_configuration(None).unwrap();
   let addr = conf.leptos_options.site_addr;
@@ -15,6 +18,10 @@ async fn main() {
   let routes = generate_route_list(App);
 
   let app = Router::new()
+    .route("/diagram.svg", get(diagram_svg))
+    .route("/diagram.png", get(diagram_png))
+    .route("/diagram/scale/{root}/{scale}", get(scale_diagram))
+    .route("/diagram/chord/{symbol}", get(chord_diagram))
     .leptos_routes(&leptos_options, routes, {
       let leptos_options = leptos_options.clone();
       move || shell(leptos_options.clone())
@@ -1,8 +1,15 @@
+use crate::models::song::Song;
+use crate::models::technique::Technique;
 use crate::music::{
   notes::Note,
+  progressions::ChordProgression,
   scales::{Scale, ScaleType},
+  tunings::Tuning,
 };
 
+/// Highest fret a capo can be placed on
+pub const MAX_CAPO_FRET: u8 = 12;
+
 /// Exercise types with their specific configuration
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ExerciseType {
@@ -19,10 +26,12 @@ pub enum ExerciseType {
     scale_type: ScaleType,
     fret_range: (u8, u8),
   },
-  /// Technique practice (no key or fret range needed)
-  Technique,
-  /// Song practice (no key or fret range needed)
-  Song,
+  /// Chord change practice, the metronome drives which chord is current
+  ChordProgression { progression: ChordProgression },
+  /// Technique practice with a tempo ladder (no key or fret range needed)
+  Technique(Technique),
+  /// Song practice split into sections with their own tempo targets
+  Song(Song),
 }
 
 impl ExerciseType {
@@ -39,7 +48,9 @@ impl ExerciseType {
         scale_type,
         ..
       } => Some(Scale::new(*root_note, *scale_type)),
-      ExerciseType::Technique | ExerciseType::Song => None,
+      ExerciseType::ChordProgression { .. }
+      | ExerciseType::Technique(_)
+      | ExerciseType::Song(_) => None,
     }
   }
 
@@ -61,8 +72,17 @@ impl ExerciseType {
         "triad_{}_{}_{}-{}",
         root_note, scale_type, fret_range.0, fret_range.1
       ),
-      ExerciseType::Technique => "technique".to_string(),
-      ExerciseType::Song => "song".to_string(),
+      ExerciseType::ChordProgression { progression } => format!(
+        "chord_progression_{}",
+        progression
+          .steps
+          .iter()
+          .map(|step| step.chord.to_string())
+          .collect::<Vec<_>>()
+          .join("-")
+      ),
+      ExerciseType::Technique(_) => "technique".to_string(),
+      ExerciseType::Song(_) => "song".to_string(),
     }
   }
 
@@ -71,7 +91,33 @@ impl ExerciseType {
     match self {
       ExerciseType::Scale { fret_range, .. } => Some(*fret_range),
       ExerciseType::Triad { fret_range, .. } => Some(*fret_range),
-      ExerciseType::Technique | ExerciseType::Song => None,
+      ExerciseType::ChordProgression { .. }
+      | ExerciseType::Technique(_)
+      | ExerciseType::Song(_) => None,
+    }
+  }
+
+  /// Get the chord progression if this exercise type uses one
+  pub fn get_chord_progression(&self) -> Option<&ChordProgression> {
+    match self {
+      ExerciseType::ChordProgression { progression } => Some(progression),
+      _ => None,
+    }
+  }
+
+  /// Get the technique settings if this is a technique exercise
+  pub fn get_technique(&self) -> Option<&Technique> {
+    match self {
+      ExerciseType::Technique(technique) => Some(technique),
+      _ => None,
+    }
+  }
+
+  /// Get the song details if this is a song exercise
+  pub fn get_song(&self) -> Option<&Song> {
+    match self {
+      ExerciseType::Song(song) => Some(song),
+      _ => None,
     }
   }
 
@@ -80,8 +126,9 @@ impl ExerciseType {
     match self {
       ExerciseType::Scale { .. } => "Scale",
       ExerciseType::Triad { .. } => "Triad",
-      ExerciseType::Technique => "Technique",
-      ExerciseType::Song => "Song",
+      ExerciseType::ChordProgression { .. } => "Chord Progression",
+      ExerciseType::Technique(_) => "Technique",
+      ExerciseType::Song(_) => "Song",
     }
   }
 
@@ -89,7 +136,9 @@ impl ExerciseType {
     match self {
       ExerciseType::Scale { root_note, .. } => *root_note = new_root,
       ExerciseType::Triad { root_note, .. } => *root_note = new_root,
-      ExerciseType::Technique | ExerciseType::Song => {}
+      ExerciseType::ChordProgression { .. }
+      | ExerciseType::Technique(_)
+      | ExerciseType::Song(_) => {}
     }
   }
 
@@ -98,7 +147,9 @@ impl ExerciseType {
     match self {
       ExerciseType::Scale { scale_type, .. } => *scale_type = new_scale_type,
       ExerciseType::Triad { scale_type, .. } => *scale_type = new_scale_type,
-      ExerciseType::Technique | ExerciseType::Song => {}
+      ExerciseType::ChordProgression { .. }
+      | ExerciseType::Technique(_)
+      | ExerciseType::Song(_) => {}
     }
   }
 
@@ -106,7 +157,9 @@ impl ExerciseType {
     match self {
       ExerciseType::Scale { fret_range, .. } => *fret_range = new_range,
       ExerciseType::Triad { fret_range, .. } => *fret_range = new_range,
-      ExerciseType::Technique | ExerciseType::Song => {}
+      ExerciseType::ChordProgression { .. }
+      | ExerciseType::Technique(_)
+      | ExerciseType::Song(_) => {}
     }
   }
 }
@@ -136,8 +189,16 @@ impl std::fmt::Display for ExerciseType {
           root_note, scale_type, fret_range.0, fret_range.1
         )
       }
-      ExerciseType::Technique => write!(f, "Technique"),
-      ExerciseType::Song => write!(f, "Song"),
+      ExerciseType::ChordProgression { progression } => {
+        write!(f, "Chord Progression ({progression})")
+      }
+      ExerciseType::Technique(technique) => write!(
+        f,
+        "{} ({}-{} BPM)",
+        technique.category, technique.start_bpm, technique.target_bpm
+      ),
+      ExerciseType::Song(song) if song.artist.is_empty() => write!(f, "Song"),
+      ExerciseType::Song(song) => write!(f, "Song by {}", song.artist),
     }
   }
 }
@@ -149,15 +210,57 @@ pub struct Exercise {
   pub name: String,
   pub exercise_type: ExerciseType,
   pub description: Option<String>,
+  /// Tuning the exercise is played in, standard tuning for exercises saved before tunings existed
+  #[serde(default)]
+  pub tuning: Tuning,
+  /// Fret the capo is placed on, 0 means no capo. Chords and fret ranges are relative to the capo.
+  #[serde(default)]
+  pub capo: u8,
 }
 
 impl Exercise {
   pub fn new(name: String, exercise_type: ExerciseType) -> Self {
-    Self {
-      id: exercise_type.create_id(),
+    let mut exercise = Self {
+      id: String::new(),
       name,
       exercise_type,
       description: None,
+      tuning: Tuning::default(),
+      capo: 0,
+    };
+    exercise.id = exercise.derive_id();
+    exercise
+  }
+
+  /// Id of a new exercise. Scales and triads are identified by their configuration, including
+  /// the tuning and capo they are played with. Songs, techniques and progressions are not (the
+  /// same chords can be practiced with other beats), but exercise names are unique.
+  fn derive_id(&self) -> String {
+    let id = self.exercise_type.create_id();
+    match &self.exercise_type {
+      ExerciseType::Song(_)
+      | ExerciseType::Technique(_)
+      | ExerciseType::ChordProgression { .. } => {
+        format!("{id}_{}", self.name.trim().to_lowercase().replace(' ', "_"))
+      }
+      ExerciseType::Scale { .. } | ExerciseType::Triad { .. } => {
+        // Standard tuning without a capo keeps the ids from before tunings existed
+        let mut id = id;
+        if self.tuning.notes != Tuning::standard().notes {
+          id.push_str(&format!(
+            "_{}",
+            self
+              .tuning
+              .notes_string()
+              .replace(' ', "")
+              .replace('♯', "s")
+          ));
+        }
+        if self.capo > 0 {
+          id.push_str(&format!("_capo{}", self.capo));
+        }
+        id
+      }
     }
   }
 
@@ -165,12 +268,96 @@ impl Exercise {
     self.description = Some(description);
     self
   }
+
+  pub fn with_tuning(mut self, tuning: Tuning) -> Self {
+    self.tuning = tuning;
+    self.id = self.derive_id();
+    self
+  }
+
+  pub fn with_capo(mut self, capo: u8) -> Self {
+    self.capo = capo;
+    self.id = self.derive_id();
+    self
+  }
+
+  /// Deserializes a saved exercise. Technique exercises saved before they had settings
+  /// store a bare `"Technique"` type and get the default settings.
+  pub fn from_saved(mut value: serde_json::Value) -> Result<Self, serde_json::Error> {
+    if let Some(exercise_type) = value.get_mut("exercise_type") {
+      if exercise_type.as_str() == Some("Technique") {
+        *exercise_type = serde_json::json!({ "Technique": {} });
+      }
+    }
+    serde_json::from_value(value)
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  #[cfg(target_arch = "wasm32")]
   use super::*;
+  use std::str::FromStr;
+
+  #[test]
+  fn test_from_saved_migrates_legacy_technique() {
+    let saved = serde_json::json!({
+      "id": "technique",
+      "name": "Alternate Picking",
+      "exercise_type": "Technique",
+      "description": null
+    });
+    let exercise = Exercise::from_saved(saved).unwrap();
+    assert_eq!(
+      exercise.exercise_type,
+      ExerciseType::Technique(Technique::default())
+    );
+
+    let current = Exercise::new(
+      "Legato".to_string(),
+      ExerciseType::Technique(Technique {
+        target_bpm: 160,
+        ..Technique::default()
+      }),
+    );
+    let saved = serde_json::to_value(&current).unwrap();
+    assert_eq!(Exercise::from_saved(saved).unwrap(), current);
+  }
+
+  #[test]
+  fn test_progressions_with_the_same_chords_have_their_own_ids() {
+    let progression = |name: &str| {
+      Exercise::new(
+        name.to_string(),
+        ExerciseType::ChordProgression {
+          progression: "C G Am F".parse().unwrap(),
+        },
+      )
+    };
+    let pop = progression("Pop Song");
+    assert_eq!(pop.id, "chord_progression_C-G-Am-F_pop_song");
+    assert_ne!(pop.id, progression("Slow Ballad").id);
+  }
+
+  #[test]
+  fn test_scale_ids_include_tuning_and_capo() {
+    let scale = || {
+      Exercise::new(
+        "A Minor".to_string(),
+        ExerciseType::Scale {
+          root_note: Note::A,
+          scale_type: ScaleType::from_str("Minor").unwrap(),
+          fret_range: (5, 8),
+        },
+      )
+    };
+    let standard = scale();
+    let drop_d = scale().with_tuning(Tuning::from_library("Drop D").unwrap());
+    let capo = scale().with_capo(2);
+    assert_eq!(standard.id, scale().with_tuning(Tuning::standard()).id);
+    assert_ne!(standard.id, drop_d.id);
+    assert_ne!(standard.id, capo.id);
+    assert_ne!(drop_d.id, drop_d.clone().with_capo(2).id);
+  }
 
   #[test]
   #[cfg(target_arch = "wasm32")]
@@ -193,7 +380,10 @@ mod tests {
   #[test]
   #[cfg(target_arch = "wasm32")]
   fn test_technique_exercise_creation() {
-    let exercise = Exercise::new("Alternate Picking".to_string(), ExerciseType::Technique);
+    let exercise = Exercise::new(
+      "Alternate Picking".to_string(),
+      ExerciseType::Technique(Technique::default()),
+    );
 
     assert_eq!(exercise.exercise_type.type_name(), "Technique");
     assert_eq!(exercise.exercise_type.get_fret_range(), None);
@@ -0,0 +1,521 @@
+use std::fmt;
+use std::str::FromStr;
+
+use leptos::prelude::*;
+
+use crate::components::fretboard::{
+  FretPalette, FretboardDirection, FretboardOrientation, FretboardTheme,
+  FretboardVisualConfigBuilder, Handedness, StringOrder,
+};
+use crate::models::exercise::MAX_CAPO_FRET;
+use crate::models::fretboard::{
+  FretLabelMode, FretboardModel, FretboardModelBuilder, FretboardModelExt, Instrument,
+};
+use crate::music::chords::Chord;
+use crate::music::notes::{parse_note_prefix, Note, NoteExt};
+use crate::music::scales::ScaleType;
+use crate::music::voicings::{ChordVoicing, MAX_SEARCH_POSITION};
+use crate::music::{Scale, Tuning};
+
+/// Frets shown by a chord chart, counted from its first fret
+const CHORD_CHART_FRETS: usize = 4;
+
+/// Inclusive fret range written as "2-7" in diagram URLs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FretSpan {
+  pub start: usize,
+  pub end: usize,
+}
+
+impl fmt::Display for FretSpan {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}-{}", self.start, self.end)
+  }
+}
+
+impl FromStr for FretSpan {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("Invalid fret range \"{s}\", expected e.g. \"2-7\"");
+    let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+    let start = start.trim().parse().map_err(|_| invalid())?;
+    let end = end.trim().parse().map_err(|_| invalid())?;
+    Ok(Self { start, end })
+  }
+}
+
+impl TryFrom<String> for FretSpan {
+  type Error = String;
+
+  fn try_from(s: String) -> Result<Self, Self::Error> {
+    s.parse()
+  }
+}
+
+impl From<FretSpan> for String {
+  fn from(span: FretSpan) -> Self {
+    span.to_string()
+  }
+}
+
+/// Parses a root note written like in chord symbols, e.g. "F#", "Bb" or "E♭"
+pub fn parse_root(root: &str) -> Result<Note, String> {
+  match parse_note_prefix(root) {
+    Some((note, "")) => Ok(note),
+    _ => Err(format!("Unknown root note: {root}")),
+  }
+}
+
+/// Root notes are written as "F#" in diagram URLs
+mod root_name {
+  use serde::{Deserialize, Deserializer, Serializer};
+
+  use super::{parse_root, Note, NoteExt};
+
+  pub fn serialize<S: Serializer>(root: &Note, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&root.to_short_string().replace('♯', "#"))
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Note, D::Error> {
+    let root = String::deserialize(deserializer)?;
+    parse_root(&root).map_err(serde::de::Error::custom)
+  }
+}
+
+/// Looks up a library tuning by name, or parses notes from the lowest string like "D A D G B E"
+fn parse_tuning(tuning: &str) -> Result<Tuning, String> {
+  match Tuning::from_library(tuning) {
+    Some(tuning) => Ok(tuning),
+    None => Tuning::parse_notes(tuning).map(|notes| Tuning::new("Custom", notes)),
+  }
+}
+
+fn validate_capo(capo: usize) -> Result<(), String> {
+  if capo > MAX_CAPO_FRET as usize {
+    return Err(format!(
+      "The capo has to be at fret {MAX_CAPO_FRET} or below"
+    ));
+  }
+  Ok(())
+}
+
+fn validate_position(position: Option<usize>) -> Result<(), String> {
+  if position.is_some_and(|position| position > MAX_SEARCH_POSITION) {
+    return Err(format!(
+      "The position has to be at fret {MAX_SEARCH_POSITION} or below"
+    ));
+  }
+  Ok(())
+}
+
+/// Builds the model of a diagram. Has to run inside a reactive owner.
+fn diagram_model(
+  frets: FretSpan,
+  tuning: Tuning,
+  capo: usize,
+  instrument: Instrument,
+  theme: FretboardTheme,
+  orientation: FretboardOrientation,
+) -> Result<FretboardModel, String> {
+  let profile = instrument.profile();
+  let drone = profile.drone_for(&tuning.notes);
+  FretboardModelBuilder::new()
+    .start_fret_val(frets.start)
+    .end_fret_val(frets.end)
+    .tuning(Signal::derive(move || tuning.notes.clone()))
+    .capo_val(capo)
+    .drone_val(drone)
+    .config(Signal::derive(move || {
+      let marker_positions = profile.marker_positions.clone();
+      FretboardVisualConfigBuilder::new()
+        .marker_positions(Signal::derive(move || marker_positions.clone()))
+        .orientation(Signal::derive(move || orientation))
+        .theme(Signal::derive(move || theme))
+        .build()
+    }))
+    .build()
+}
+
+/// Renders a diagram model to a standalone SVG
+fn render_svg(to_model: impl FnOnce() -> Result<FretboardModel, String>) -> Result<String, String> {
+  // Scoped owner, so the signals of the model are disposed after rendering
+  let owner = Owner::new();
+  owner.with(|| to_model().map(|model| model.to_svg()))
+}
+
+/// Scale diagram as described by the query string of the diagram export routes,
+/// e.g. `/diagram.svg?root=A&scale=Minor&frets=5-8`.
+///
+/// `/diagram/scale/{root}/{type}` takes the same query without `root` and `scale`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DiagramRequest {
+  /// Written like "F#" or "Bb"
+  #[serde(with = "root_name")]
+  pub root: Note,
+  /// Scale type name, e.g. "Major"
+  pub scale: String,
+  pub frets: FretSpan,
+  /// Name of a library tuning, or notes from the lowest string like "D A D G B E"
+  pub tuning: String,
+  pub capo: usize,
+  /// Picks the fret markers and the drone string, e.g. `banjo`
+  pub instrument: Instrument,
+  pub labels: FretLabelMode,
+  pub palette: FretPalette,
+  pub dark: bool,
+  pub handedness: Handedness,
+  pub direction: FretboardDirection,
+  pub string_order: StringOrder,
+}
+
+impl Default for DiagramRequest {
+  fn default() -> Self {
+    Self {
+      root: Note::C,
+      scale: "Major".to_string(),
+      frets: FretSpan { start: 0, end: 12 },
+      tuning: Tuning::standard().name,
+      capo: 0,
+      instrument: Instrument::default(),
+      labels: FretLabelMode::default(),
+      palette: FretPalette::default(),
+      dark: false,
+      handedness: Handedness::default(),
+      direction: FretboardDirection::default(),
+      string_order: StringOrder::default(),
+    }
+  }
+}
+
+impl DiagramRequest {
+  pub fn new(
+    scale: Scale,
+    start: usize,
+    end: usize,
+    tuning: &Tuning,
+    labels: FretLabelMode,
+    theme: FretboardTheme,
+    orientation: FretboardOrientation,
+  ) -> Self {
+    // Custom tunings are unknown to the server, so they are sent as notes
+    let tuning = if Tuning::from_library(&tuning.name).as_ref() == Some(tuning) {
+      tuning.name.clone()
+    } else {
+      tuning.notes_string()
+    };
+    Self {
+      root: scale.root_note().unwrap_or(Note::C),
+      scale: scale.scale_type().to_string(),
+      frets: FretSpan { start, end },
+      tuning,
+      capo: 0,
+      instrument: Instrument::default(),
+      labels,
+      palette: theme.palette,
+      dark: theme.dark_mode,
+      handedness: orientation.handedness,
+      direction: orientation.direction,
+      string_order: orientation.string_order,
+    }
+  }
+
+  pub fn to_query_string(&self) -> String {
+    serde_urlencoded::to_string(self).unwrap_or_default()
+  }
+
+  pub fn scale(&self) -> Result<Scale, String> {
+    Ok(Scale::new(self.root, ScaleType::from_str(&self.scale)?))
+  }
+
+  pub fn tuning(&self) -> Result<Tuning, String> {
+    parse_tuning(&self.tuning)
+  }
+
+  pub fn theme(&self) -> FretboardTheme {
+    FretboardTheme {
+      palette: self.palette,
+      dark_mode: self.dark,
+    }
+  }
+
+  pub fn orientation(&self) -> FretboardOrientation {
+    FretboardOrientation {
+      handedness: self.handedness,
+      direction: self.direction,
+      string_order: self.string_order,
+    }
+  }
+
+  /// Builds a fretboard model showing the scale. Has to run inside a reactive owner.
+  pub fn to_model(&self) -> Result<FretboardModel, String> {
+    let scale = self.scale()?;
+    validate_capo(self.capo)?;
+    let model = diagram_model(
+      self.frets,
+      self.tuning()?,
+      self.capo,
+      self.instrument,
+      self.theme(),
+      self.orientation(),
+    )?;
+    model.update_from_scale_with_labels(scale, self.labels);
+    Ok(model)
+  }
+
+  /// Standalone SVG of the requested diagram
+  pub fn to_svg(&self) -> Result<String, String> {
+    render_svg(|| self.to_model())
+  }
+}
+
+/// Chord chart served at `/diagram/chord/{symbol}`, e.g. `/diagram/chord/Am7?capo=2`.
+///
+/// Shows the lowest voicing of the chord, or the one at `position`. With a capo the
+/// chord is played relative to the capo, like in a songbook.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ChordDiagramRequest {
+  /// Chord symbol, taken from the path
+  #[serde(skip)]
+  pub symbol: String,
+  /// Name of a library tuning, or notes from the lowest string like "D A D G B E"
+  pub tuning: String,
+  pub capo: usize,
+  /// Picks the fret markers and the drone string, e.g. `banjo`
+  pub instrument: Instrument,
+  /// Lowest fret of the voicing search, the lowest playable voicing if not set
+  pub position: Option<usize>,
+  pub palette: FretPalette,
+  pub dark: bool,
+  pub handedness: Handedness,
+  pub direction: FretboardDirection,
+  pub string_order: StringOrder,
+}
+
+impl Default for ChordDiagramRequest {
+  fn default() -> Self {
+    Self {
+      symbol: "C".to_string(),
+      tuning: Tuning::standard().name,
+      capo: 0,
+      instrument: Instrument::default(),
+      position: None,
+      palette: FretPalette::default(),
+      dark: false,
+      handedness: Handedness::default(),
+      // Chord charts are usually drawn upright
+      direction: FretboardDirection::Vertical,
+      string_order: StringOrder::default(),
+    }
+  }
+}
+
+impl ChordDiagramRequest {
+  pub fn chord(&self) -> Result<Chord, String> {
+    Chord::from_str(&self.symbol)
+  }
+
+  pub fn tuning(&self) -> Result<Tuning, String> {
+    parse_tuning(&self.tuning)
+  }
+
+  pub fn theme(&self) -> FretboardTheme {
+    FretboardTheme {
+      palette: self.palette,
+      dark_mode: self.dark,
+    }
+  }
+
+  pub fn orientation(&self) -> FretboardOrientation {
+    FretboardOrientation {
+      handedness: self.handedness,
+      direction: self.direction,
+      string_order: self.string_order,
+    }
+  }
+
+  /// The shown voicing, already shifted by the capo
+  pub fn voicing(&self) -> Result<ChordVoicing, String> {
+    validate_position(self.position)?;
+    let chord = self.chord()?;
+    let tuning = self.tuning()?;
+    let voicing = match self.position {
+      Some(position) => ChordVoicing::find_at_position(&chord, &tuning.notes, position),
+      None => ChordVoicing::find(&chord, &tuning.notes),
+    };
+    voicing
+      .map(|voicing| voicing.with_capo(self.capo))
+      .ok_or_else(|| format!("No playable voicing of {chord} found"))
+  }
+
+  /// Fret range of the chart. Shapes close to the nut or capo are drawn from there,
+  /// so muted strings can be marked.
+  pub fn frets(&self, voicing: &ChordVoicing) -> FretSpan {
+    let start = if voicing.max_fret() < self.capo + CHORD_CHART_FRETS {
+      self.capo
+    } else {
+      voicing.min_fret()
+    };
+    FretSpan {
+      start,
+      end: voicing.max_fret().max(start + CHORD_CHART_FRETS - 1),
+    }
+  }
+
+  /// Builds a fretboard model showing the chord. Has to run inside a reactive owner.
+  pub fn to_model(&self) -> Result<FretboardModel, String> {
+    validate_capo(self.capo)?;
+    let voicing = self.voicing()?;
+    let model = diagram_model(
+      self.frets(&voicing),
+      self.tuning()?,
+      self.capo,
+      self.instrument,
+      self.theme(),
+      self.orientation(),
+    )?;
+    model.update_from_voicings(&voicing, None);
+    Ok(model)
+  }
+
+  /// Standalone SVG of the requested chord chart
+  pub fn to_svg(&self) -> Result<String, String> {
+    render_svg(|| self.to_model())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::components::fretboard::FretState;
+
+  #[test]
+  fn test_query_string_round_trip() {
+    let request = DiagramRequest {
+      root: Note::FSharpOrGFlat,
+      scale: "Minor".to_string(),
+      frets: FretSpan { start: 2, end: 5 },
+      labels: FretLabelMode::Interval,
+      tuning: "D A D G B E".to_string(),
+      palette: FretPalette::ColorBlindSafe,
+      direction: FretboardDirection::Vertical,
+      ..DiagramRequest::default()
+    };
+    let query = request.to_query_string();
+    assert!(query.contains("root=F%23"));
+    assert!(query.contains("tuning=D+A+D+G+B+E"));
+    assert!(query.contains("frets=2-5"));
+    assert!(query.contains("labels=intervals"));
+    let parsed: DiagramRequest = serde_urlencoded::from_str(&query).unwrap();
+    assert_eq!(parsed, request);
+    assert_eq!(
+      parsed.tuning().unwrap().notes,
+      Tuning::from_library("Drop D").unwrap().notes
+    );
+
+    // Missing parameters fall back to the defaults
+    let parsed: DiagramRequest = serde_urlencoded::from_str("root=A&scale=Minor").unwrap();
+    assert_eq!(parsed.frets, FretSpan { start: 0, end: 12 });
+    assert!(serde_urlencoded::from_str::<DiagramRequest>("frets=2").is_err());
+    assert_eq!(parsed.tuning().unwrap(), Tuning::standard());
+
+    let parsed: DiagramRequest = serde_urlencoded::from_str("root=Bb").unwrap();
+    assert_eq!(parsed.root, Note::ASharpOrBFlat);
+    assert!(serde_urlencoded::from_str::<DiagramRequest>("root=H").is_err());
+    assert!(serde_urlencoded::from_str::<DiagramRequest>("root=FSharpOrGFlat").is_err());
+  }
+
+  #[test]
+  fn test_svg_shows_scale_notes() {
+    let request = DiagramRequest {
+      root: Note::A,
+      scale: "Minor".to_string(),
+      frets: FretSpan { start: 5, end: 8 },
+      ..DiagramRequest::default()
+    };
+    let svg = request.to_svg().unwrap();
+    // Root on the low E string at fret 5, drawn as a square
+    assert!(svg.contains(">A</text>"));
+    assert!(svg.contains(r#"rx="2""#));
+    assert!(!svg.contains(">F#"));
+
+    let invalid = DiagramRequest {
+      frets: FretSpan { start: 0, end: 30 },
+      ..DiagramRequest::default()
+    };
+    assert!(invalid.to_svg().is_err());
+    let invalid = DiagramRequest {
+      scale: "Bebop".to_string(),
+      ..DiagramRequest::default()
+    };
+    assert!(invalid.to_svg().is_err());
+  }
+
+  #[test]
+  fn test_banjo_diagram_has_short_fifth_string() {
+    let request = DiagramRequest {
+      root: Note::G,
+      frets: FretSpan { start: 0, end: 7 },
+      tuning: "Banjo Open G".to_string(),
+      instrument: Instrument::Banjo,
+      ..DiagramRequest::default()
+    };
+    let diagram = Owner::new().with(|| request.to_model().unwrap().to_diagram());
+    // The fifth string is only fretted from its peg at the 5th fret, where it sounds G
+    let fifth_string: Vec<_> = diagram
+      .fret_states
+      .iter()
+      .filter(|(coord, _)| coord.string_idx == 4)
+      .map(|(coord, state)| (coord.fret_idx, state.clone()))
+      .collect();
+    assert!(fifth_string.iter().all(|(fret, _)| *fret >= 5));
+    assert!(matches!(fifth_string[0], (5, FretState::Square(..))));
+    assert_eq!(
+      diagram.marker_positions,
+      Instrument::Banjo.profile().marker_positions
+    );
+
+    // Peg of the fifth string
+    let svg = request.to_svg().unwrap();
+    assert_eq!(svg.matches(r#" r="4" fill="#).count(), 1);
+  }
+
+  #[test]
+  fn test_chord_chart() {
+    let request = ChordDiagramRequest {
+      symbol: "C".to_string(),
+      ..ChordDiagramRequest::default()
+    };
+    // x32010, drawn from the nut with the low E string muted
+    let voicing = request.voicing().unwrap();
+    assert_eq!(request.frets(&voicing), FretSpan { start: 0, end: 3 });
+    let svg = request.to_svg().unwrap();
+    assert_eq!(svg.matches(">C</text>").count(), 2);
+    assert_eq!(svg.matches(">E</text>").count(), 2);
+    assert!(svg.contains(">G</text>"));
+
+    // A barre chord higher up the neck starts at its lowest fret
+    let request = ChordDiagramRequest {
+      symbol: "Bb".to_string(),
+      position: Some(6),
+      ..ChordDiagramRequest::default()
+    };
+    let voicing = request.voicing().unwrap();
+    assert_eq!(request.frets(&voicing).start, voicing.min_fret());
+    assert!(request.to_svg().is_ok());
+
+    let invalid = ChordDiagramRequest {
+      symbol: "H7".to_string(),
+      ..ChordDiagramRequest::default()
+    };
+    assert!(invalid.to_svg().is_err());
+    let invalid = ChordDiagramRequest {
+      position: Some(usize::MAX),
+      ..ChordDiagramRequest::default()
+    };
+    assert!(invalid.to_svg().is_err());
+  }
+}
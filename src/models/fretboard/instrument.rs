@@ -0,0 +1,259 @@
+use leptos::prelude::*;
+use strum_macros::EnumIter;
+
+use crate::components::fretboard::{DroneString, Multiscale};
+use crate::music::{Note, Tuning};
+
+/// Fretted instruments the fretboards can show
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Eq, Hash, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Instrument {
+  #[default]
+  Guitar,
+  Bass,
+  Ukulele,
+  Mandolin,
+  Banjo,
+}
+
+impl std::fmt::Display for Instrument {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Instrument::Guitar => write!(f, "Guitar"),
+      Instrument::Bass => write!(f, "Bass"),
+      Instrument::Ukulele => write!(f, "Ukulele"),
+      Instrument::Mandolin => write!(f, "Mandolin"),
+      Instrument::Banjo => write!(f, "Banjo"),
+    }
+  }
+}
+
+/// Everything that differs between instruments on the fretboard
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstrumentProfile {
+  pub instrument: Instrument,
+  /// Built-in tunings, the first one is the default and has the usual number of strings
+  pub tunings: Vec<Tuning>,
+  /// Typical scale length in inches, that of the highest string with fanned frets
+  pub scale_length: f64,
+  pub marker_positions: Vec<usize>,
+  /// Short string that starts up the neck, e.g. the fifth string of a banjo
+  pub drone: Option<DroneString>,
+  /// MIDI numbers of the open strings of the default tuning, highest string first
+  pub open_midis: Vec<u8>,
+}
+
+impl Instrument {
+  pub fn profile(self) -> InstrumentProfile {
+    let (tunings, scale_length, marker_positions, drone, open_midis) = match self {
+      // E4 B3 G3 D3 A2 E2
+      Instrument::Guitar => (
+        Tuning::guitar_library(),
+        25.5,
+        vec![3, 5, 7, 9, 12, 15, 17, 19, 21, 24],
+        None,
+        vec![64, 59, 55, 50, 45, 40],
+      ),
+      // G2 D2 A1 E1
+      Instrument::Bass => (
+        Tuning::bass_library(),
+        34.0,
+        vec![3, 5, 7, 9, 12, 15, 17, 19, 21, 24],
+        None,
+        vec![43, 38, 33, 28],
+      ),
+      // A4 E4 C4 G4, the G string is re-entrant
+      Instrument::Ukulele => (
+        Tuning::ukulele_library(),
+        15.0,
+        vec![5, 7, 10, 12, 15],
+        None,
+        vec![69, 64, 60, 67],
+      ),
+      // E5 A4 D4 G3
+      Instrument::Mandolin => (
+        Tuning::mandolin_library(),
+        13.875,
+        vec![3, 5, 7, 10, 12, 15, 17],
+        None,
+        vec![76, 69, 62, 55],
+      ),
+      // The fifth string is the lowest row on the board and is fretted from the 5th fret.
+      // D4 B3 G3 D3 and the short G4 string.
+      Instrument::Banjo => (
+        Tuning::banjo_library(),
+        26.25,
+        vec![3, 5, 7, 10, 12, 15, 17, 19, 22],
+        Some(DroneString {
+          string_idx: 4,
+          first_fret: 5,
+        }),
+        vec![62, 59, 55, 50, 67],
+      ),
+    };
+    InstrumentProfile {
+      instrument: self,
+      tunings,
+      scale_length,
+      marker_positions,
+      drone,
+      open_midis,
+    }
+  }
+}
+
+impl InstrumentProfile {
+  pub fn default_tuning(&self) -> Tuning {
+    self.tunings[0].clone()
+  }
+
+  pub fn num_strings(&self) -> usize {
+    self.tunings[0].num_strings()
+  }
+
+  /// Fanned frets for this instrument. The lowest string is as much longer than the highest
+  /// one as on a 25.5"-27" guitar, the 7th fret runs straight across.
+  pub fn multiscale(&self) -> Multiscale {
+    Multiscale {
+      bass_scale_length: self.scale_length * 27.0 / 25.5,
+      treble_scale_length: self.scale_length,
+      perpendicular_fret: 7,
+    }
+  }
+
+  /// The drone string for a tuning, only if the tuning has the strings of this instrument.
+  /// A custom 4-string tuning on a banjo profile has no drone.
+  pub fn drone_for(&self, tuning: &[Note]) -> Option<DroneString> {
+    self.drone.filter(|_| tuning.len() == self.num_strings())
+  }
+
+  /// MIDI numbers of the open strings of a tuning, highest string first. Each string
+  /// sounds in the octave closest to the string of the default tuning at the same place,
+  /// counted from the lowest string. Extra high strings continue a fourth higher each.
+  pub fn open_string_midis(&self, tuning: &[Note]) -> Vec<u8> {
+    let lowest = self.open_midis.len() - 1;
+    tuning
+      .iter()
+      .enumerate()
+      .map(|(string_idx, note)| {
+        let from_lowest = tuning.len() - 1 - string_idx;
+        let reference = match lowest.checked_sub(from_lowest) {
+          Some(idx) => i32::from(self.open_midis[idx]),
+          None => i32::from(self.open_midis[0]) + 5 * (from_lowest - lowest) as i32,
+        };
+        let pitch_class = Note::all_notes()
+          .iter()
+          .position(|other| other == note)
+          .unwrap_or_default() as i32;
+        // Up to a tritone up or down, ties go down
+        let below = reference - (reference - pitch_class).rem_euclid(12);
+        let midi = if reference - below <= 6 {
+          below
+        } else {
+          below + 12
+        };
+        midi.clamp(0, 127) as u8
+      })
+      .collect()
+  }
+}
+
+/// App wide instrument, picks the default tuning, fret markers and drone strings
+#[derive(Clone, Copy, Debug)]
+pub struct InstrumentPreference(pub RwSignal<Instrument>);
+
+/// The app wide instrument, guitar if there is no preference (e.g. in server rendered diagrams)
+pub fn use_instrument() -> Signal<Instrument> {
+  match use_context::<InstrumentPreference>() {
+    Some(InstrumentPreference(instrument)) => instrument.into(),
+    None => Signal::derive(Instrument::default),
+  }
+}
+
+/// Runs `on_switch` whenever the app instrument changes after the calling component was
+/// created, e.g. to reset the tuning of a page. The saved instrument is loaded after the first
+/// render, which counts as a switch too.
+pub fn on_instrument_switch(on_switch: impl Fn(InstrumentProfile) + 'static) {
+  let instrument = use_instrument();
+  let shown = StoredValue::new(instrument.get_untracked());
+  Effect::new(move |_| {
+    let current = instrument.get();
+    if current != shown.get_value() {
+      shown.set_value(current);
+      untrack(|| on_switch(current.profile()));
+    }
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use strum::IntoEnumIterator;
+
+  #[test]
+  fn test_profiles() {
+    for instrument in Instrument::iter() {
+      let profile = instrument.profile();
+      assert_eq!(profile.instrument, instrument);
+      assert!(profile
+        .tunings
+        .iter()
+        .all(|tuning| tuning.num_strings() >= 4));
+      assert!(profile.marker_positions.contains(&12));
+    }
+    assert_eq!(Instrument::Bass.profile().num_strings(), 4);
+    assert_eq!(Instrument::Banjo.profile().num_strings(), 5);
+  }
+
+  #[test]
+  fn test_multiscale_follows_scale_length() {
+    let guitar = Instrument::Guitar.profile().multiscale();
+    assert_eq!(guitar.treble_scale_length, 25.5);
+    assert!((guitar.bass_scale_length - 27.0).abs() < 1e-9);
+    // A bass fans out from its own, longer scale
+    let bass = Instrument::Bass.profile().multiscale();
+    assert_eq!(bass.treble_scale_length, 34.0);
+    assert!(bass.bass_scale_length > 35.5 && bass.bass_scale_length < 36.5);
+  }
+
+  #[test]
+  fn test_banjo_drone() {
+    let banjo = Instrument::Banjo.profile();
+    let open_g = banjo.default_tuning();
+    let drone = banjo.drone_for(&open_g.notes).unwrap();
+    // The short string is the G written first
+    assert_eq!(open_g.notes[drone.string_idx as usize], Note::G);
+    assert_eq!(banjo.drone_for(&[Note::D, Note::B, Note::G, Note::D]), None);
+    assert_eq!(Instrument::Guitar.profile().drone, None);
+  }
+
+  #[test]
+  fn test_open_string_midis() {
+    for instrument in Instrument::iter() {
+      let profile = instrument.profile();
+      assert_eq!(
+        profile.open_string_midis(&profile.default_tuning().notes),
+        profile.open_midis
+      );
+    }
+    let guitar = Instrument::Guitar.profile();
+    let tuning = |name| Tuning::from_library(name).unwrap().notes;
+    // The low string of drop D goes down, the B of a 7-string below the low E
+    assert_eq!(
+      guitar.open_string_midis(&tuning("Drop D")),
+      vec![64, 59, 55, 50, 45, 38]
+    );
+    assert_eq!(
+      guitar.open_string_midis(&tuning("7-String B Standard")),
+      vec![64, 59, 55, 50, 45, 40, 35]
+    );
+    // The high C of a 6-string bass above the G
+    let bass = Instrument::Bass.profile();
+    assert_eq!(
+      bass.open_string_midis(&tuning("6-String Bass BEADGC")),
+      vec![48, 43, 38, 33, 28, 23]
+    );
+  }
+}
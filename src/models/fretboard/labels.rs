@@ -0,0 +1,107 @@
+use std::fmt;
+
+use strum_macros::EnumIter;
+
+use crate::music::{intervals::Interval, Note, Scale};
+
+/// What is written on the highlighted frets of a scale
+#[derive(
+  Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum FretLabelMode {
+  #[default]
+  #[serde(rename = "notes")]
+  NoteName,
+  /// Interval from the root, e.g. "R", "b3", "5"
+  #[serde(rename = "intervals")]
+  Interval,
+  /// Scale degree number
+  #[serde(rename = "degrees")]
+  ScaleDegree,
+  /// Suggested fretting finger, one finger per fret
+  #[serde(rename = "fingers")]
+  Finger,
+}
+
+impl fmt::Display for FretLabelMode {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FretLabelMode::NoteName => write!(f, "Note Names"),
+      FretLabelMode::Interval => write!(f, "Intervals"),
+      FretLabelMode::ScaleDegree => write!(f, "Scale Degrees"),
+      FretLabelMode::Finger => write!(f, "Fingers"),
+    }
+  }
+}
+
+/// Suggested finger for `fret` in a position starting at `position_fret`: 0 for open strings,
+/// which sound at the capo fret (`open_fret`) with a capo, otherwise one finger per fret,
+/// with the pinky taking stretches beyond the fourth fret.
+pub fn suggested_finger(fret: usize, position_fret: usize, open_fret: usize) -> usize {
+  if fret <= open_fret {
+    return 0;
+  }
+  let first_finger_fret = position_fret.max(open_fret + 1);
+  fret.saturating_sub(first_finger_fret).min(3) + 1
+}
+
+/// Label for a scale note at `fret`, falling back to the note name when the mode
+/// does not apply (e.g. degrees of a chromatic scale). See [`suggested_finger`] for the frets.
+pub fn scale_fret_label(
+  mode: FretLabelMode,
+  scale: &Scale,
+  note: Note,
+  fret: usize,
+  position_fret: usize,
+  open_fret: usize,
+) -> String {
+  let label = match mode {
+    FretLabelMode::NoteName => None,
+    FretLabelMode::Interval => scale
+      .root_note()
+      .and_then(|root| Interval::from_notes(root, note))
+      .map(|interval| interval.short_name().to_string()),
+    FretLabelMode::ScaleDegree => scale.degree_of(note).map(|degree| degree.to_string()),
+    FretLabelMode::Finger => Some(suggested_finger(fret, position_fret, open_fret).to_string()),
+  };
+  label.unwrap_or_else(|| note.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::music::{heptatonic_scales::HeptaScaleType, ScaleType};
+
+  #[test]
+  fn test_suggested_finger() {
+    assert_eq!(suggested_finger(0, 0, 0), 0);
+    assert_eq!(suggested_finger(1, 0, 0), 1);
+    assert_eq!(suggested_finger(3, 0, 0), 3);
+    assert_eq!(suggested_finger(5, 5, 0), 1);
+    assert_eq!(suggested_finger(8, 5, 0), 4);
+    assert_eq!(suggested_finger(9, 5, 0), 4);
+    // With a capo on the 2nd fret, the capo fret is the open string
+    assert_eq!(suggested_finger(2, 2, 2), 0);
+    assert_eq!(suggested_finger(3, 2, 2), 1);
+    assert_eq!(suggested_finger(5, 2, 2), 3);
+  }
+
+  #[test]
+  fn test_scale_fret_labels() {
+    let a_minor = Scale::new(Note::A, ScaleType::Hepatonic(HeptaScaleType::Minor));
+    let label = |mode, note| scale_fret_label(mode, &a_minor, note, 5, 5, 0);
+
+    assert_eq!(label(FretLabelMode::NoteName, Note::C), "C");
+    assert_eq!(label(FretLabelMode::Interval, Note::A), "R");
+    assert_eq!(label(FretLabelMode::Interval, Note::C), "b3");
+    assert_eq!(label(FretLabelMode::Interval, Note::E), "5");
+    assert_eq!(label(FretLabelMode::ScaleDegree, Note::G), "7");
+    assert_eq!(label(FretLabelMode::Finger, Note::A), "1");
+
+    let chromatic = Scale::new(Note::C, ScaleType::Chromatic);
+    assert_eq!(
+      scale_fret_label(FretLabelMode::ScaleDegree, &chromatic, Note::D, 3, 0, 0),
+      "D"
+    );
+  }
+}
@@ -1,7 +1,15 @@
+pub mod diagram;
+pub mod instrument;
+pub mod labels;
 pub mod model;
 pub mod model_builder;
 pub mod model_ext;
 
+pub use diagram::{parse_root, ChordDiagramRequest, DiagramRequest, FretSpan};
+pub use instrument::{
+  on_instrument_switch, use_instrument, Instrument, InstrumentPreference, InstrumentProfile,
+};
+pub use labels::FretLabelMode;
 pub use model::FretboardModel;
 pub use model_builder::{default_tuning, FretboardModelBuilder};
 pub use model_ext::FretboardModelExt;
@@ -1,105 +1,340 @@
 use crate::music::Note;
 use leptos::prelude::*;
 
-use crate::components::fretboard::{FretCoord, FretState, FretStateSignals, FretboardVisualConfig};
+use crate::components::fretboard::definitions::{MAX_FRETS, MAX_STRINGS};
+use crate::components::fretboard::{
+  DroneString, FretCoord, FretState, FretStateGrid, FretboardDiagram, FretboardVisualConfig,
+};
 use crate::models::fretboard::model_builder::FretboardModelBuilder;
 
+/// A setting of the model. Values given to the builder are owned by the model and can be
+/// changed in place, signals given to the builder stay controlled by the caller.
+#[derive(Debug, PartialEq)]
+pub struct ModelSetting<T: Send + Sync + 'static> {
+  signal: Signal<T>,
+  owned: Option<RwSignal<T>>,
+}
+
+impl<T: Send + Sync + 'static> Clone for ModelSetting<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: Send + Sync + 'static> Copy for ModelSetting<T> {}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> ModelSetting<T> {
+  pub fn owned(value: T) -> Self {
+    let owned = RwSignal::new(value);
+    Self {
+      signal: owned.into(),
+      owned: Some(owned),
+    }
+  }
+
+  pub fn controlled(signal: Signal<T>) -> Self {
+    Self {
+      signal,
+      owned: None,
+    }
+  }
+
+  pub fn signal(&self) -> Signal<T> {
+    self.signal
+  }
+
+  /// Keeps a controlled setting within `bound` whenever its signal changes. Owned settings
+  /// are validated when they are set instead.
+  pub fn bounded(
+    self,
+    name: &'static str,
+    bound: impl Fn(&T) -> T + Send + Sync + 'static,
+  ) -> Self {
+    if self.owned.is_some() {
+      return self;
+    }
+    let signal = self.signal;
+    let bounded = Memo::new(move |_| {
+      signal.with(|value| {
+        let bounded = bound(value);
+        if bounded != *value {
+          leptos::logging::warn!("The {} of the fretboard model is out of range", name);
+        }
+        bounded
+      })
+    });
+    Self::controlled(bounded.into())
+  }
+
+  /// Fails for a setting controlled by a signal, which only the caller can change
+  fn check_owned(&self, name: &str) -> Result<(), String> {
+    match self.owned {
+      Some(_) => Ok(()),
+      None => Err(format!(
+        "The {name} of the fretboard model is controlled by a signal"
+      )),
+    }
+  }
+
+  /// Changes an owned setting, subscribers are only notified if the value differs
+  fn set(&self, name: &str, value: T) -> Result<(), String> {
+    self.check_owned(name)?;
+    if let Some(owned) = self.owned {
+      if owned.with_untracked(|current| *current != value) {
+        owned.set(value);
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Checks the fret range, string count and capo against the fret state grid, which holds
+/// the largest supported instrument
+pub fn validate_settings(
+  start_fret: usize,
+  end_fret: usize,
+  num_strings: usize,
+  capo: usize,
+) -> Result<(), String> {
+  if num_strings == 0 {
+    return Err("The tuning needs at least one string".into());
+  }
+  if num_strings > MAX_STRINGS {
+    return Err(format!("At most {MAX_STRINGS} strings are supported"));
+  }
+  if start_fret > end_fret || end_fret >= MAX_FRETS {
+    return Err(format!(
+      "The fret range has to lie within 0-{}",
+      MAX_FRETS - 1
+    ));
+  }
+  if capo >= MAX_FRETS {
+    return Err(format!(
+      "The capo has to be at fret {} or below",
+      MAX_FRETS - 1
+    ));
+  }
+  Ok(())
+}
+
 #[derive(Clone, Debug, PartialEq, Copy)]
 pub struct FretboardModel {
   /// First fret in the active/playable range
-  start_fret: Signal<usize>,
+  start_fret: ModelSetting<usize>,
   /// Last fret in the active/playable range
-  end_fret: Signal<usize>,
-  /// Tuning of the guitar strings, first index is the lowest string (6th string)
-  tuning: Signal<Vec<Note>>,
+  end_fret: ModelSetting<usize>,
+  /// Tuning of the guitar strings, first index is the highest string (1st string)
+  tuning: ModelSetting<Vec<Note>>,
+  /// Fret the capo is placed on, 0 means no capo
+  capo: ModelSetting<usize>,
+  /// Short string that starts up the neck, e.g. the fifth string of a banjo
+  drone: ModelSetting<Option<DroneString>>,
   /// Visual configuration for fretboard display properties
   config: Signal<FretboardVisualConfig>,
   /// States for each fret
-  fret_states: Signal<FretStateSignals>,
+  fret_states: Signal<FretStateGrid>,
 }
 
 impl Default for FretboardModel {
   fn default() -> Self {
-    FretboardModelBuilder::new().build()
+    FretboardModelBuilder::new()
+      .build()
+      .expect("The default settings are valid")
   }
 }
 
 impl FretboardModel {
   pub fn new(
-    start_fret: Signal<usize>,
-    end_fret: Signal<usize>,
-    tuning: Signal<Vec<Note>>,
+    start_fret: ModelSetting<usize>,
+    end_fret: ModelSetting<usize>,
+    tuning: ModelSetting<Vec<Note>>,
+    capo: ModelSetting<usize>,
+    drone: ModelSetting<Option<DroneString>>,
     config: Signal<FretboardVisualConfig>,
-    fret_states: Signal<FretStateSignals>,
+    fret_states: Signal<FretStateGrid>,
   ) -> Self {
     Self {
       start_fret,
       end_fret,
       tuning,
+      capo,
+      drone,
       config,
       fret_states,
     }
   }
 
   pub fn get_num_frets_untracked(&self) -> usize {
-    self.end_fret.get_untracked() - self.start_fret.get_untracked() + 1
+    self.get_end_fret().get_untracked() - self.get_start_fret().get_untracked() + 1
   }
 
   pub fn get_tuning(&self) -> Signal<Vec<Note>> {
-    self.tuning
+    self.tuning.signal()
+  }
+
+  pub fn get_capo(&self) -> Signal<usize> {
+    self.capo.signal()
+  }
+
+  pub fn get_drone(&self) -> Signal<Option<DroneString>> {
+    self.drone.signal()
+  }
+
+  /// Whether the fret lies between the nut and the capo and can't be played
+  pub fn is_behind_capo(&self, fret_idx: usize) -> bool {
+    fret_idx < self.get_capo().get_untracked()
+  }
+
+  /// Whether the string exists and has a fret at the position that isn't behind the capo.
+  /// The capo doesn't cover a drone string, which is only fretted from its first fret.
+  pub fn is_playable(&self, coord: FretCoord) -> bool {
+    let has_string = self
+      .get_tuning()
+      .with_untracked(|tuning| (coord.string_idx as usize) < tuning.len());
+    match self.get_drone().get_untracked() {
+      Some(drone) if drone.string_idx == coord.string_idx => {
+        has_string && coord.fret_idx as usize >= drone.first_fret
+      }
+      _ => has_string && !self.is_behind_capo(coord.fret_idx as usize),
+    }
   }
 
   pub fn get_start_fret(&self) -> Signal<usize> {
-    self.start_fret
+    self.start_fret.signal()
   }
 
   pub fn get_end_fret(&self) -> Signal<usize> {
-    self.end_fret
+    self.end_fret.signal()
+  }
+
+  /// Changes the playable range in place, without rebuilding the model or remounting the
+  /// fretboard. Reapply the fret states afterwards, e.g. with `update_from_scale`.
+  /// Settings controlled by a signal can't be changed, nothing is changed then.
+  pub fn set_fret_range(&self, start_fret: usize, end_fret: usize) -> Result<(), String> {
+    self.validate_with(start_fret, end_fret, None, None)?;
+    self.start_fret.check_owned("start fret")?;
+    self.end_fret.check_owned("end fret")?;
+    self.start_fret.set("start fret", start_fret)?;
+    self.end_fret.set("end fret", end_fret)
+  }
+
+  /// Changes the tuning in place, see [`Self::set_fret_range`]
+  pub fn set_tuning(&self, tuning: Vec<Note>) -> Result<(), String> {
+    let (start_fret, end_fret) = self.fret_range_untracked();
+    self.validate_with(start_fret, end_fret, Some(tuning.len()), None)?;
+    self.tuning.set("tuning", tuning)
+  }
+
+  /// Changes the drone string in place, e.g. together with the tuning of a banjo. A drone on
+  /// a string the tuning doesn't have is ignored.
+  pub fn set_drone(&self, drone: Option<DroneString>) -> Result<(), String> {
+    self.drone.set("drone string", drone)
+  }
+
+  /// Moves the capo in place, see [`Self::set_fret_range`]
+  pub fn set_capo(&self, capo: usize) -> Result<(), String> {
+    let (start_fret, end_fret) = self.fret_range_untracked();
+    self.validate_with(start_fret, end_fret, None, Some(capo))?;
+    self.capo.set("capo", capo)
+  }
+
+  fn fret_range_untracked(&self) -> (usize, usize) {
+    (
+      self.get_start_fret().get_untracked(),
+      self.get_end_fret().get_untracked(),
+    )
+  }
+
+  /// Validates the current settings with some of them replaced
+  fn validate_with(
+    &self,
+    start_fret: usize,
+    end_fret: usize,
+    num_strings: Option<usize>,
+    capo: Option<usize>,
+  ) -> Result<(), String> {
+    validate_settings(
+      start_fret,
+      end_fret,
+      num_strings.unwrap_or_else(|| self.get_tuning().with_untracked(Vec::len)),
+      capo.unwrap_or_else(|| self.get_capo().get_untracked()),
+    )
   }
 
   pub fn get_config(&self) -> Signal<FretboardVisualConfig> {
     self.config
   }
 
-  pub fn get_fret_states(&self) -> Signal<FretStateSignals> {
+  pub fn get_fret_states(&self) -> Signal<FretStateGrid> {
     self.fret_states
   }
 
-  /// Update the fret states by merging new states into existing preallocated signals
-  pub fn update_fret_states(&self, new_states: FretStateSignals) {
-    // Merge into existing preallocated signals (no new signal creation during reactive updates).
-    self.fret_states.with(|existing| {
-      for (coord, state_signal) in new_states.into_iter() {
-        if let Some(dest) = existing.get(&coord) {
-          if dest.get() != state_signal.get() {
-            dest.set(state_signal.get());
-          }
-        }
-      }
-    });
+  /// Update the fret states by copying new states into the existing preallocated signals
+  pub fn update_fret_states(&self, new_states: &FretStateGrid) {
+    self
+      .fret_states
+      .with(|existing| existing.copy_from(new_states));
   }
 
-  /// Calculate the note at a specific fret position
-  pub fn note_from_fret(&self, coord: FretCoord) -> Note {
-    self.tuning.with_untracked(|tuning| {
-      if let Some(string_note) = tuning.get(coord.string_idx as usize) {
-        string_note.add_steps(coord.fret_idx as usize)
-      } else {
-        Note::C // Fallback for invalid string
-      }
+  /// Calculate the note at a specific fret position, `None` if the string doesn't exist or
+  /// has no fret there (below the first fret of a drone string).
+  /// Frets behind the capo sound like the capo fret.
+  pub fn note_from_fret(&self, coord: FretCoord) -> Option<Note> {
+    let steps = self.steps_from_fret(coord)?;
+    self.get_tuning().with_untracked(|tuning| {
+      tuning
+        .get(coord.string_idx as usize)
+        .map(|string_note| string_note.add_steps(steps))
     })
   }
 
-  pub fn set_fret_state(&self, coord: FretCoord, state: FretState) {
-    self.fret_states.with_untracked(|fret_states| {
-      if let Some(sig) = fret_states.get(&coord) {
-        sig.set(state);
+  /// MIDI number of the note at a fret, given the pitches of the open strings, see
+  /// [`InstrumentProfile::open_string_midis`](crate::models::fretboard::InstrumentProfile::open_string_midis)
+  pub fn midi_from_fret(&self, coord: FretCoord, open_midis: &[u8]) -> Option<u8> {
+    let steps = self.steps_from_fret(coord)?;
+    let open = *open_midis.get(coord.string_idx as usize)?;
+    u8::try_from(usize::from(open) + steps).ok()
+  }
+
+  /// Half-tone steps above the open string at a fret
+  fn steps_from_fret(&self, coord: FretCoord) -> Option<usize> {
+    match self.get_drone().get_untracked() {
+      Some(drone) if drone.string_idx == coord.string_idx => {
+        DroneString::steps_at(Some(drone), coord)
       }
-    });
+      _ => Some((coord.fret_idx as usize).max(self.get_capo().get_untracked())),
+    }
+  }
+
+  pub fn set_fret_state(&self, coord: FretCoord, state: FretState) {
+    self
+      .fret_states
+      .with_untracked(|fret_states| fret_states.set(coord, state));
+  }
+
+  /// Snapshot of the current fret states and display settings
+  pub fn to_diagram(&self) -> FretboardDiagram {
+    let fret_states = self.fret_states.with_untracked(FretStateGrid::shown_states);
+    FretboardDiagram::new(
+      self.get_start_fret().get_untracked(),
+      self.get_end_fret().get_untracked(),
+      self
+        .get_tuning()
+        .with_untracked(|tuning| tuning.len() as u8),
+      self.get_capo().get_untracked(),
+      self.get_drone().get_untracked(),
+      fret_states,
+      &self.config.get_untracked(),
+    )
+  }
+
+  /// Renders the fretboard with its current fret states into a standalone SVG document
+  pub fn to_svg(&self) -> String {
+    self.to_diagram().to_svg()
   }
 
   pub fn get_min_fret(&self) -> Signal<usize> {
-    let start_fret = self.start_fret;
+    let start_fret = self.get_start_fret();
     let config = self.config;
 
     Signal::derive(move || {
@@ -110,13 +345,13 @@ impl FretboardModel {
   }
 
   pub fn get_min_fret_untracked(&self) -> usize {
-    let start_fret = self.start_fret.get_untracked();
+    let start_fret = self.get_start_fret().get_untracked();
     let extra_frets = self.config.get_untracked().extra_frets.get_untracked();
     start_fret.saturating_sub(extra_frets)
   }
 
   pub fn get_max_visible_fret(&self) -> Signal<usize> {
-    let end_fret = self.end_fret;
+    let end_fret = self.get_end_fret();
     let config = self.config;
     let extra_frets = Signal::derive(move || config.get().extra_frets.get());
 
@@ -124,8 +359,201 @@ impl FretboardModel {
   }
 
   pub fn get_max_visible_fret_untracked(&self) -> usize {
-    let end_fret = self.end_fret.get_untracked();
+    let end_fret = self.get_end_fret().get_untracked();
     let extra_frets = self.config.get_untracked().extra_frets.get_untracked();
     end_fret + extra_frets
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::fretboard::{FretboardModelExt, Instrument};
+  use crate::music::{Scale, ScaleType};
+  use std::str::FromStr;
+
+  #[test]
+  fn test_reconfigure_in_place() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let model = FretboardModelBuilder::new().build().unwrap();
+      let fret_states = model.get_fret_states().get_untracked();
+      model.update_from_scale(Scale::new(Note::C, ScaleType::from_str("Major").unwrap()));
+
+      model
+        .set_tuning(vec![Note::E, Note::B, Note::G, Note::D, Note::A, Note::D])
+        .unwrap();
+      model.set_capo(2).unwrap();
+      model.set_fret_range(5, 9).unwrap();
+      model.update_from_scale(Scale::new(Note::D, ScaleType::from_str("Major").unwrap()));
+
+      assert_eq!(model.get_start_fret().get_untracked(), 5);
+      assert_eq!(model.get_end_fret().get_untracked(), 9);
+      assert_eq!(model.get_capo().get_untracked(), 2);
+      // The same cells are reused, the old range is cleared
+      assert_eq!(model.get_fret_states().get_untracked(), fret_states);
+      let shown = fret_states.shown_states();
+      assert!(shown
+        .iter()
+        .all(|(coord, _)| (5..=9).contains(&coord.fret_idx)));
+      // The root on the A string
+      let root = FretCoord {
+        string_idx: 4,
+        fret_idx: 5,
+      };
+      assert_eq!(model.note_from_fret(root), Some(Note::D));
+      assert!(matches!(
+        fret_states.get(root).unwrap().get_untracked(),
+        FretState::Square(..)
+      ));
+    });
+  }
+
+  #[test]
+  fn test_controlled_settings_are_not_changed() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let start_fret = RwSignal::new(3);
+      let model = FretboardModelBuilder::new()
+        .start_fret(start_fret.into())
+        .build()
+        .unwrap();
+      // Neither end of the range moves if one of them is controlled
+      assert!(model.set_fret_range(0, 5).is_err());
+      assert_eq!(model.get_start_fret().get_untracked(), 3);
+      assert_eq!(model.get_end_fret().get_untracked(), 12);
+      start_fret.set(0);
+      assert_eq!(model.get_start_fret().get_untracked(), 0);
+    });
+  }
+
+  #[test]
+  fn test_controlled_settings_stay_on_the_grid() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let start_fret = RwSignal::new(3);
+      let end_fret = RwSignal::new(7);
+      let capo = RwSignal::new(0);
+      let model = FretboardModelBuilder::new()
+        .start_fret(start_fret.into())
+        .end_fret(end_fret.into())
+        .capo(capo.into())
+        .build()
+        .unwrap();
+
+      end_fret.set(MAX_FRETS + 5);
+      capo.set(MAX_FRETS);
+      assert_eq!(model.get_end_fret().get_untracked(), MAX_FRETS - 1);
+      assert_eq!(model.get_capo().get_untracked(), MAX_FRETS - 1);
+
+      // An inverted range shrinks to the start fret
+      start_fret.set(9);
+      end_fret.set(4);
+      assert_eq!(model.get_start_fret().get_untracked(), 9);
+      assert_eq!(model.get_end_fret().get_untracked(), 9);
+      assert_eq!(model.get_num_frets_untracked(), 1);
+    });
+  }
+
+  #[test]
+  fn test_extended_range_instruments() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let twelve_strings = vec![Note::E; MAX_STRINGS];
+      let model = FretboardModelBuilder::new()
+        .start_fret_val(0)
+        .end_fret_val(27)
+        .tuning(Signal::derive(move || twelve_strings.clone()))
+        .build()
+        .unwrap();
+      let highest = FretCoord {
+        string_idx: MAX_STRINGS as u8 - 1,
+        fret_idx: 27,
+      };
+      assert_eq!(model.note_from_fret(highest), Some(Note::G));
+      assert_eq!(
+        model.note_from_fret(FretCoord {
+          string_idx: MAX_STRINGS as u8,
+          fret_idx: 0,
+        }),
+        None
+      );
+    });
+  }
+
+  #[test]
+  fn test_invalid_settings_are_rejected() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let build = |start: usize, end: usize, num_strings: usize, capo: usize| {
+        FretboardModelBuilder::new()
+          .start_fret_val(start)
+          .end_fret_val(end)
+          .tuning(Signal::derive(move || vec![Note::E; num_strings]))
+          .capo_val(capo)
+          .build()
+      };
+      assert!(build(0, 12, 6, 0).is_ok());
+      assert!(build(0, 12, 0, 0).is_err());
+      assert!(build(0, 12, MAX_STRINGS + 1, 0).is_err());
+      assert!(build(7, 5, 6, 0).is_err());
+      assert!(build(0, MAX_FRETS, 6, 0).is_err());
+      assert!(build(0, 12, 6, MAX_FRETS).is_err());
+
+      // Rejected changes keep the previous settings
+      let model = FretboardModelBuilder::new().build().unwrap();
+      assert!(model.set_fret_range(3, MAX_FRETS).is_err());
+      assert!(model.set_tuning(vec![]).is_err());
+      assert_eq!(model.get_end_fret().get_untracked(), 12);
+      assert_eq!(model.get_tuning().with_untracked(Vec::len), 6);
+    });
+  }
+
+  #[test]
+  fn test_playback_midis() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let model = FretboardModelBuilder::new()
+        .start_fret_val(7)
+        .end_fret_val(10)
+        .build()
+        .unwrap();
+      let open_midis = Instrument::Guitar.profile().open_midis;
+      // A2 on the low E string, A4 on the high E string
+      assert_eq!(
+        model.midi_from_fret(
+          FretCoord {
+            string_idx: 5,
+            fret_idx: 5
+          },
+          &open_midis
+        ),
+        Some(45)
+      );
+      assert_eq!(
+        model.midi_from_fret(
+          FretCoord {
+            string_idx: 0,
+            fret_idx: 5
+          },
+          &open_midis
+        ),
+        Some(69)
+      );
+      // The A minor arpeggio in 7th position, from C3 to C5
+      let a_minor = [Note::A, Note::C, Note::E];
+      assert_eq!(
+        model.playback_midis(&open_midis, |note| a_minor.contains(&note)),
+        vec![48, 52, 57, 60, 64, 69, 72]
+      );
+
+      // Frets behind the capo sound like the capo fret, as on the instrument
+      model.set_capo(3).unwrap();
+      let behind_capo = FretCoord {
+        string_idx: 5,
+        fret_idx: 1,
+      };
+      assert_eq!(model.midi_from_fret(behind_capo, &open_midis), Some(43));
+    });
+  }
+}
@@ -1,23 +1,30 @@
-use crate::music::Note;
-use leptos::prelude::{RwSignal, Signal};
+use crate::music::{Note, Tuning};
+use leptos::prelude::*;
 
 use crate::{
-  components::fretboard::{
-    base::get_preallocated_fret_states, FretStateSignals, FretboardVisualConfig,
-  },
-  models::fretboard::model::FretboardModel,
+  components::fretboard::definitions::{MAX_FRETS, MAX_STRINGS},
+  components::fretboard::{DroneString, FretStateGrid, FretboardVisualConfig},
+  models::fretboard::instrument::use_instrument,
+  models::fretboard::model::{validate_settings, FretboardModel, ModelSetting},
 };
 
 pub fn default_tuning() -> Signal<Vec<Note>> {
-  Signal::derive(move || vec![Note::E, Note::B, Note::G, Note::D, Note::A, Note::E])
+  Signal::derive(move || Tuning::standard().notes)
 }
 
+/// Settings given as values (`*_val`) or left out are owned by the model and can be changed
+/// in place later, settings given as signals are controlled by the caller. A left out tuning
+/// and drone string are those of the app instrument when the model is built. Building fails if
+/// the initial settings don't fit on the largest supported instrument. Controlled settings that
+/// leave it later are clamped to it.
 pub struct FretboardModelBuilder {
-  start_fret: Option<Signal<usize>>,
-  end_fret: Option<Signal<usize>>,
-  tuning: Option<Signal<Vec<Note>>>,
+  start_fret: Option<ModelSetting<usize>>,
+  end_fret: Option<ModelSetting<usize>>,
+  tuning: Option<ModelSetting<Vec<Note>>>,
+  capo: Option<ModelSetting<usize>>,
+  drone: Option<ModelSetting<Option<DroneString>>>,
   config: Option<Signal<FretboardVisualConfig>>,
-  fret_states: Option<Signal<FretStateSignals>>,
+  fret_states: Option<Signal<FretStateGrid>>,
 }
 
 impl FretboardModelBuilder {
@@ -26,31 +33,51 @@ impl FretboardModelBuilder {
       start_fret: None,
       end_fret: None,
       tuning: None,
+      capo: None,
+      drone: None,
       config: None,
       fret_states: None,
     }
   }
 
   pub fn start_fret(mut self, start_fret: Signal<usize>) -> Self {
-    self.start_fret = Some(start_fret);
+    self.start_fret = Some(ModelSetting::controlled(start_fret));
     self
   }
   pub fn start_fret_val(mut self, start_fret: usize) -> Self {
-    self.start_fret = Some(Signal::derive(move || start_fret));
+    self.start_fret = Some(ModelSetting::owned(start_fret));
     self
   }
 
   pub fn end_fret(mut self, end_fret: Signal<usize>) -> Self {
-    self.end_fret = Some(end_fret);
+    self.end_fret = Some(ModelSetting::controlled(end_fret));
     self
   }
   pub fn end_fret_val(mut self, end_fret: usize) -> Self {
-    self.end_fret = Some(Signal::derive(move || end_fret));
+    self.end_fret = Some(ModelSetting::owned(end_fret));
     self
   }
 
   pub fn tuning(mut self, tuning: Signal<Vec<Note>>) -> Self {
-    self.tuning = Some(tuning);
+    self.tuning = Some(ModelSetting::controlled(tuning));
+    self
+  }
+
+  pub fn capo(mut self, capo: Signal<usize>) -> Self {
+    self.capo = Some(ModelSetting::controlled(capo));
+    self
+  }
+  pub fn capo_val(mut self, capo: usize) -> Self {
+    self.capo = Some(ModelSetting::owned(capo));
+    self
+  }
+
+  pub fn drone(mut self, drone: Signal<Option<DroneString>>) -> Self {
+    self.drone = Some(ModelSetting::controlled(drone));
+    self
+  }
+  pub fn drone_val(mut self, drone: Option<DroneString>) -> Self {
+    self.drone = Some(ModelSetting::owned(drone));
     self
   }
 
@@ -59,34 +86,68 @@ impl FretboardModelBuilder {
     self
   }
 
-  pub fn fret_states(mut self, fret_states: Signal<FretStateSignals>) -> Self {
+  pub fn fret_states(mut self, fret_states: Signal<FretStateGrid>) -> Self {
     self.fret_states = Some(fret_states);
     self
   }
 
   pub fn from_options(
-    start_fret: Option<Signal<usize>>,
-    end_fret: Option<Signal<usize>>,
-    tuning: Option<Signal<Vec<Note>>>,
+    start_fret: Option<ModelSetting<usize>>,
+    end_fret: Option<ModelSetting<usize>>,
+    tuning: Option<ModelSetting<Vec<Note>>>,
+    capo: Option<ModelSetting<usize>>,
+    drone: Option<ModelSetting<Option<DroneString>>>,
     config: Option<Signal<FretboardVisualConfig>>,
-    fret_states: Option<Signal<FretStateSignals>>,
-  ) -> FretboardModel {
-    let fret_states =
-      fret_states.unwrap_or_else(|| RwSignal::new(get_preallocated_fret_states()).into());
-    FretboardModel::new(
-      start_fret.unwrap_or_else(|| Signal::derive(move || 0)),
-      end_fret.unwrap_or_else(|| Signal::derive(move || 12)),
-      tuning.unwrap_or_else(default_tuning),
+    fret_states: Option<Signal<FretStateGrid>>,
+  ) -> Result<FretboardModel, String> {
+    let profile = use_instrument().get_untracked().profile();
+    let start_fret = start_fret.unwrap_or_else(|| ModelSetting::owned(0));
+    let end_fret = end_fret.unwrap_or_else(|| ModelSetting::owned(12));
+    let tuning = tuning.unwrap_or_else(|| ModelSetting::owned(profile.default_tuning().notes));
+    let capo = capo.unwrap_or_else(|| ModelSetting::owned(0));
+    validate_settings(
+      start_fret.signal().get_untracked(),
+      end_fret.signal().get_untracked(),
+      tuning.signal().with_untracked(Vec::len),
+      capo.signal().get_untracked(),
+    )?;
+    // Controlled settings can change after the model is built, so they are kept on the grid
+    let start_fret = start_fret.bounded("start fret", |start| (*start).min(MAX_FRETS - 1));
+    let start_signal = start_fret.signal();
+    let end_fret = end_fret.bounded("end fret", move |end| {
+      (*end).clamp(start_signal.get(), MAX_FRETS - 1)
+    });
+    let tuning = tuning.bounded("tuning", |notes| {
+      notes.iter().copied().take(MAX_STRINGS).collect()
+    });
+    let capo = capo.bounded("capo", |capo| (*capo).min(MAX_FRETS - 1));
+    let drone = drone.unwrap_or_else(|| {
+      ModelSetting::owned(
+        tuning
+          .signal()
+          .with_untracked(|notes| profile.drone_for(notes)),
+      )
+    });
+
+    let fret_states = fret_states.unwrap_or_else(|| RwSignal::new(FretStateGrid::new()).into());
+    Ok(FretboardModel::new(
+      start_fret,
+      end_fret,
+      tuning,
+      capo,
+      drone,
       config.unwrap_or_else(|| Signal::derive(FretboardVisualConfig::default)),
       fret_states,
-    )
+    ))
   }
 
-  pub fn build(self) -> FretboardModel {
+  pub fn build(self) -> Result<FretboardModel, String> {
     Self::from_options(
       self.start_fret,
       self.end_fret,
       self.tuning,
+      self.capo,
+      self.drone,
       self.config,
       self.fret_states,
     )
@@ -1,14 +1,21 @@
-use crate::{music::intervals::Interval, music::Scale};
+use crate::{
+  music::intervals::Interval, music::notes::Note, music::voicings::ChordVoicing, music::Scale,
+};
 use leptos::prelude::*;
 
 use crate::{
-  components::fretboard::{FretCoord, FretState, FretStateColor},
+  components::fretboard::{DroneString, FretCoord, FretRole, FretState},
+  models::fretboard::labels::{scale_fret_label, FretLabelMode},
   models::fretboard::model::FretboardModel,
 };
 
 pub trait FretboardModelExt {
   fn update_from_scale(&self, scale: Scale);
+  fn update_from_scale_with_labels(&self, scale: Scale, label_mode: FretLabelMode);
   fn get_random_fret(&self) -> FretCoord;
+  fn playable_frets(&self) -> Vec<FretCoord>;
+  fn find_note_positions(&self, note: Note, start_fret: usize, end_fret: usize) -> Vec<FretCoord>;
+  fn playback_midis(&self, open_midis: &[u8], include: impl Fn(Note) -> bool) -> Vec<u8>;
   fn is_interval_of(
     &self,
     coord_left: FretCoord,
@@ -16,57 +23,116 @@ pub trait FretboardModelExt {
     interval: Interval,
   ) -> bool;
   fn hide_all_frets(&self);
+  fn update_from_voicings(&self, current: &ChordVoicing, next: Option<&ChordVoicing>);
 }
 
 impl FretboardModelExt for FretboardModel {
   fn update_from_scale(&self, scale: Scale) {
-    self
-      .get_tuning()
-      .get_untracked()
-      .iter()
-      .enumerate()
-      .for_each(move |(string_idx, string_note)| {
-        for fret_idx in self.get_min_fret_untracked()..=self.get_max_visible_fret_untracked() {
-          let coord = FretCoord {
-            string_idx: string_idx as u8,
-            fret_idx: fret_idx as u8,
-          };
-          let state = if fret_idx >= self.get_start_fret().get_untracked()
-            && fret_idx <= self.get_end_fret().get_untracked()
-          {
-            let note_at_fret = string_note.add_steps(fret_idx);
-            if scale.root_note() == Some(note_at_fret) {
-              FretState::Normal(FretStateColor::Green, note_at_fret.to_string())
-            } else if scale.contains_note(note_at_fret) {
-              FretState::Normal(FretStateColor::Blue, note_at_fret.to_string())
-            } else {
-              FretState::Hidden
-            }
-          } else {
-            FretState::Hidden
-          };
-          self.get_fret_states().with_untracked(move |fret_states| {
-            if let Some(sig) = fret_states.get(&coord) {
-              sig.set(state);
-            }
-          });
+    self.update_from_scale_with_labels(scale, FretLabelMode::NoteName);
+  }
+
+  /// Highlight the scale notes in the playable range, labeled according to `label_mode`.
+  /// Every cell is written in one batch, cells outside the range are hidden.
+  fn update_from_scale_with_labels(&self, scale: Scale, label_mode: FretLabelMode) {
+    let start_fret = self.get_start_fret().get_untracked();
+    let end_fret = self.get_end_fret().get_untracked();
+    let capo = self.get_capo().get_untracked();
+    let position_fret = start_fret.max(capo);
+    let tuning = self.get_tuning().get_untracked();
+    let drone = self.get_drone().get_untracked();
+
+    self.get_fret_states().with_untracked(|fret_states| {
+      fret_states.update_all(|coord| {
+        let fret_idx = coord.fret_idx as usize;
+        let Some(string_note) = tuning.get(coord.string_idx as usize) else {
+          return FretState::Hidden;
+        };
+        let on_drone = drone.is_some_and(|drone| drone.string_idx == coord.string_idx);
+        if fret_idx < start_fret
+          || fret_idx > end_fret
+          || !on_drone && self.is_behind_capo(fret_idx)
+        {
+          return FretState::Hidden;
+        }
+        // Below the first fret of a drone string there is nothing to play
+        let Some(steps) = DroneString::steps_at(drone, coord) else {
+          return FretState::Hidden;
+        };
+        let note_at_fret = string_note.add_steps(steps);
+        // A drone string isn't under the capo, it is open at its first fret
+        let open_fret = match drone {
+          Some(drone) if on_drone => drone.first_fret,
+          _ => capo,
+        };
+        let label = || {
+          scale_fret_label(
+            label_mode,
+            &scale,
+            note_at_fret,
+            fret_idx,
+            position_fret,
+            open_fret,
+          )
+        };
+        if scale.root_note() == Some(note_at_fret) {
+          FretState::Square(FretRole::Root, label())
+        } else if scale.contains_note(note_at_fret) {
+          FretState::Normal(FretRole::ScaleTone, label())
+        } else {
+          FretState::Hidden
         }
       });
+    });
   }
 
-  /// Get a random fret within the active range
+  /// Get a random playable fret within the active range, excluding frets behind the capo
+  /// and below the first fret of a drone string
   fn get_random_fret(&self) -> FretCoord {
-    use rand::Rng;
-    let mut rng = rand::rng();
+    use rand::seq::IndexedRandom;
 
     let start = self.get_start_fret().get_untracked();
     let end = self.get_end_fret().get_untracked();
-    let num_strings = self.get_tuning().get_untracked().len();
+    let playable = self.playable_frets();
 
-    FretCoord {
-      string_idx: rng.random_range(0..num_strings) as u8,
-      fret_idx: rng.random_range(start..=end) as u8,
-    }
+    // Everything is behind the capo, fall back to the capo fret
+    playable
+      .choose(&mut rand::rng())
+      .copied()
+      .unwrap_or(FretCoord {
+        string_idx: 0,
+        fret_idx: self.get_capo().get_untracked().max(start).min(end) as u8,
+      })
+  }
+
+  /// All playable frets within the active range
+  fn playable_frets(&self) -> Vec<FretCoord> {
+    playable_coords(
+      self,
+      self.get_start_fret().get_untracked(),
+      self.get_end_fret().get_untracked(),
+    )
+  }
+
+  /// All playable positions of `note` between `start_fret` and `end_fret`, inclusive
+  fn find_note_positions(&self, note: Note, start_fret: usize, end_fret: usize) -> Vec<FretCoord> {
+    playable_coords(self, start_fret, end_fret)
+      .into_iter()
+      .filter(|coord| self.note_from_fret(*coord) == Some(note))
+      .collect()
+  }
+
+  /// Pitches of the playable frets whose note passes `include`, lowest first and each
+  /// once, e.g. a scale in the shown position
+  fn playback_midis(&self, open_midis: &[u8], include: impl Fn(Note) -> bool) -> Vec<u8> {
+    let mut midis: Vec<u8> = self
+      .playable_frets()
+      .into_iter()
+      .filter(|coord| self.note_from_fret(*coord).is_some_and(&include))
+      .filter_map(|coord| self.midi_from_fret(coord, open_midis))
+      .collect();
+    midis.sort_unstable();
+    midis.dedup();
+    midis
   }
 
   fn is_interval_of(
@@ -75,20 +141,89 @@ impl FretboardModelExt for FretboardModel {
     coord_right: FretCoord,
     interval: Interval,
   ) -> bool {
-    let note_left = self.note_from_fret(coord_left);
-    let note_right = self.note_from_fret(coord_right);
-    let left_note_plus_interval = interval.of(note_left);
-
-    note_right == left_note_plus_interval
+    match (
+      self.note_from_fret(coord_left),
+      self.note_from_fret(coord_right),
+    ) {
+      (Some(note_left), Some(note_right)) => note_right == interval.of(note_left),
+      _ => false,
+    }
   }
 
   fn hide_all_frets(&self) {
+    self
+      .get_fret_states()
+      .with_untracked(|fret_states| fret_states.hide_all());
+  }
+
+  /// Show the current chord voicing with its muted strings and outline the positions of the
+  /// next one
+  fn update_from_voicings(&self, current: &ChordVoicing, next: Option<&ChordVoicing>) {
+    // Muted strings are marked at the nut, or at the capo which acts as the nut
+    let open_fret = self.get_capo().get_untracked();
+    let fret_on =
+      |voicing: &ChordVoicing, string_idx: usize| voicing.frets.get(string_idx).copied();
+
     self.get_fret_states().with_untracked(|fret_states| {
-      fret_states.iter().for_each(|(_, sig)| {
-        if sig.get_untracked() != FretState::Hidden {
-          sig.set(FretState::Hidden);
+      fret_states.update_all(|coord| {
+        let (string_idx, fret_idx) = (coord.string_idx as usize, coord.fret_idx as usize);
+        // Strings the tuning doesn't have stay hidden
+        let Some(note) = self.note_from_fret(coord) else {
+          return FretState::Hidden;
+        };
+        match fret_on(current, string_idx) {
+          Some(Some(fret)) if fret == fret_idx => {
+            return FretState::Normal(FretRole::ChordTone, note.to_string());
+          }
+          Some(None) if fret_idx == open_fret => return FretState::Muted,
+          _ => {}
+        }
+        match next.and_then(|next| fret_on(next, string_idx)) {
+          Some(Some(fret)) if fret == fret_idx => {
+            FretState::Hollow(FretRole::Ghost, note.to_string())
+          }
+          _ => FretState::Hidden,
         }
       });
     });
   }
 }
+
+/// Playable coordinates of every string between `start` and `end`, inclusive
+fn playable_coords(model: &FretboardModel, start: usize, end: usize) -> Vec<FretCoord> {
+  let num_strings = model.get_tuning().with_untracked(Vec::len);
+  (0..num_strings)
+    .flat_map(|string_idx| {
+      (start..=end).map(move |fret_idx| FretCoord {
+        string_idx: string_idx as u8,
+        fret_idx: fret_idx as u8,
+      })
+    })
+    .filter(|coord| model.is_playable(*coord))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::fretboard::FretboardModelBuilder;
+
+  #[test]
+  fn test_find_note_positions() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let model = FretboardModelBuilder::new().build().unwrap();
+      let coord = |string_idx, fret_idx| FretCoord {
+        string_idx,
+        fret_idx,
+      };
+      assert_eq!(
+        model.find_note_positions(Note::A, 5, 9),
+        vec![coord(0, 5), coord(3, 7), coord(5, 5)]
+      );
+      // Frets behind the capo can't be found
+      model.set_capo(6).unwrap();
+      assert_eq!(model.find_note_positions(Note::A, 5, 9), vec![coord(3, 7)]);
+    });
+  }
+}
@@ -1,3 +1,8 @@
 pub mod exercise;
 pub mod fretboard;
 pub mod repository;
+pub mod song;
+pub mod storage;
+pub mod technique;
+pub mod trainer;
+pub mod tuner;
@@ -0,0 +1,135 @@
+use std::fmt;
+
+use strum_macros::EnumIter;
+
+use crate::music::ChordProgression;
+
+/// How well a song section can be played
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+  EnumIter,
+  serde::Serialize,
+  serde::Deserialize,
+)]
+pub enum MasteryLevel {
+  #[default]
+  New,
+  Learning,
+  Comfortable,
+  Mastered,
+}
+
+impl fmt::Display for MasteryLevel {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      MasteryLevel::New => write!(f, "New"),
+      MasteryLevel::Learning => write!(f, "Learning"),
+      MasteryLevel::Comfortable => write!(f, "Comfortable"),
+      MasteryLevel::Mastered => write!(f, "Mastered"),
+    }
+  }
+}
+
+/// A part of a song such as intro, verse or solo
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SongSection {
+  pub name: String,
+  pub target_bpm: u32,
+  pub progression: Option<ChordProgression>,
+  pub mastery: MasteryLevel,
+  /// Total time spent practicing this section
+  #[serde(default)]
+  pub practiced_seconds: u64,
+}
+
+impl SongSection {
+  pub fn new(name: String, target_bpm: u32) -> Self {
+    Self {
+      name,
+      target_bpm,
+      progression: None,
+      mastery: MasteryLevel::default(),
+      practiced_seconds: 0,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Song {
+  pub artist: String,
+  pub sections: Vec<SongSection>,
+  /// Links to tabs, lessons or recordings
+  pub links: Vec<String>,
+  pub notes: Option<String>,
+}
+
+impl Song {
+  /// Add practice time to the section at `section_idx`
+  pub fn log_practice_time(&mut self, section_idx: usize, seconds: u64) {
+    if let Some(section) = self.sections.get_mut(section_idx) {
+      section.practiced_seconds += seconds;
+    }
+  }
+
+  pub fn total_practiced_seconds(&self) -> u64 {
+    self
+      .sections
+      .iter()
+      .map(|section| section.practiced_seconds)
+      .sum()
+  }
+
+  /// Lowest mastery level over all sections, which is the level of the song as a whole
+  pub fn overall_mastery(&self) -> MasteryLevel {
+    self
+      .sections
+      .iter()
+      .map(|section| section.mastery)
+      .min()
+      .unwrap_or_default()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_log_practice_time() {
+    let mut song = Song {
+      sections: vec![
+        SongSection::new("Intro".to_string(), 90),
+        SongSection::new("Solo".to_string(), 120),
+      ],
+      ..Song::default()
+    };
+    song.log_practice_time(1, 30);
+    song.log_practice_time(1, 15);
+    song.log_practice_time(5, 100);
+
+    assert_eq!(song.sections[0].practiced_seconds, 0);
+    assert_eq!(song.sections[1].practiced_seconds, 45);
+    assert_eq!(song.total_practiced_seconds(), 45);
+  }
+
+  #[test]
+  fn test_overall_mastery() {
+    let mut song = Song {
+      sections: vec![
+        SongSection::new("Verse".to_string(), 100),
+        SongSection::new("Chorus".to_string(), 100),
+      ],
+      ..Song::default()
+    };
+    song.sections[0].mastery = MasteryLevel::Mastered;
+    song.sections[1].mastery = MasteryLevel::Learning;
+    assert_eq!(song.overall_mastery(), MasteryLevel::Learning);
+  }
+}
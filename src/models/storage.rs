@@ -0,0 +1,259 @@
+use leptos::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use web_sys::Storage;
+
+use crate::components::fretboard::{FretboardOrientation, FretboardTheme};
+use crate::models::exercise::Exercise;
+use crate::models::fretboard::Instrument;
+use crate::models::trainer::{Leaderboard, LearningState, TrainerSettings};
+use crate::models::tuner::clamp_reference;
+use crate::music::Tuning;
+
+const EXERCISES_KEY: &str = "exercises";
+const CUSTOM_TUNINGS_KEY: &str = "custom_tunings";
+const FRETBOARD_ORIENTATION_KEY: &str = "fretboard_orientation";
+const FRETBOARD_THEME_KEY: &str = "fretboard_theme";
+const INSTRUMENT_KEY: &str = "instrument";
+const FRET_SOUND_KEY: &str = "fret_sound";
+const TRAINER_SETTINGS_KEY: &str = "trainer_settings";
+const TRAINER_LEARNING_KEY: &str = "trainer_learning";
+const TRAINER_LEADERBOARD_KEY: &str = "trainer_leaderboard";
+const TUNER_REFERENCE_KEY: &str = "tuner_reference";
+
+/// Browser local storage, unavailable during server side rendering
+fn local_storage() -> Result<Storage, String> {
+  if cfg!(not(target_arch = "wasm32")) {
+    return Err("Local storage is only available in the browser".to_string());
+  }
+  web_sys::window()
+    .and_then(|window| window.local_storage().ok().flatten())
+    .ok_or_else(|| "Local storage is not available".to_string())
+}
+
+/// Load and deserialize a JSON value from local storage
+pub fn load_json<T: DeserializeOwned>(key: &str) -> Option<T> {
+  let raw = local_storage().ok()?.get_item(key).ok()??;
+  match serde_json::from_str(&raw) {
+    Ok(value) => Some(value),
+    Err(err) => {
+      leptos::logging::warn!(
+        "Failed to deserialize '{}' from local storage: {}",
+        key,
+        err
+      );
+      None
+    }
+  }
+}
+
+/// Serialize a value as JSON and write it to local storage
+pub fn save_json<T: Serialize>(key: &str, value: &T) -> Result<(), String> {
+  let raw = serde_json::to_string(value).map_err(|err| err.to_string())?;
+  local_storage()?
+    .set_item(key, &raw)
+    .map_err(|err| format!("Failed to write '{key}' to local storage: {err:?}"))
+}
+
+/// Keeps `value` in local storage: the saved value is loaded once the page runs in the
+/// browser and every change is saved. `name` describes the value in warnings.
+pub fn persist_signal<T: Send + Sync + 'static>(
+  name: &'static str,
+  value: RwSignal<T>,
+  load: impl Fn() -> Option<T> + 'static,
+  save: impl Fn(&T) -> Result<(), String> + Clone + 'static,
+) {
+  persist_signal_by_key(
+    name,
+    value,
+    || (),
+    move |_| load(),
+    move |_, value| save(value),
+  );
+}
+
+/// Like [`persist_signal`] for a value saved under a key that can change, e.g. one per
+/// tuning. The value is loaded again when the key changes and saved under the new key.
+pub fn persist_signal_by_key<K: 'static, T: Send + Sync + 'static>(
+  name: &'static str,
+  value: RwSignal<T>,
+  key: impl Fn() -> K + 'static,
+  load: impl Fn(&K) -> Option<T> + 'static,
+  save: impl Fn(&K, &T) -> Result<(), String> + Clone + 'static,
+) {
+  Effect::new(move |_| {
+    let key = key();
+    if let Some(saved) = load(&key) {
+      value.set(saved);
+    }
+    let save = save.clone();
+    Effect::new(move |_| {
+      if let Err(err) = value.with(|value| save(&key, value)) {
+        leptos::logging::warn!("Failed to save {}: {}", name, err);
+      }
+    });
+  });
+}
+
+pub fn load_exercises() -> Vec<Exercise> {
+  load_json::<Vec<serde_json::Value>>(EXERCISES_KEY)
+    .unwrap_or_default()
+    .into_iter()
+    .filter_map(|saved| match Exercise::from_saved(saved) {
+      Ok(exercise) => Some(exercise),
+      Err(err) => {
+        leptos::logging::warn!("Skipping a saved exercise that can't be read: {}", err);
+        None
+      }
+    })
+    .collect()
+}
+
+fn save_exercises(exercises: &[Exercise]) -> Result<(), String> {
+  save_json(EXERCISES_KEY, &exercises)
+}
+
+pub fn load_exercise_by_id(id: &str) -> Option<Exercise> {
+  load_exercises().into_iter().find(|e| e.id == id)
+}
+
+pub fn save_exercise(exercise: &Exercise) -> Result<(), String> {
+  let mut exercises = load_exercises();
+  if exercises.iter().any(|e| e.id == exercise.id) {
+    return Err(format!(
+      "An exercise with id '{}' already exists",
+      exercise.id
+    ));
+  }
+  exercises.push(exercise.clone());
+  save_exercises(&exercises)
+}
+
+pub fn update_exercise(exercise: &Exercise) -> Result<(), String> {
+  let mut exercises = load_exercises();
+  let existing = exercises
+    .iter_mut()
+    .find(|e| e.id == exercise.id)
+    .ok_or_else(|| format!("No exercise with id '{}'", exercise.id))?;
+  *existing = exercise.clone();
+  save_exercises(&exercises)
+}
+
+pub fn delete_exercise(id: &str) -> Result<(), String> {
+  let mut exercises = load_exercises();
+  exercises.retain(|e| e.id != id);
+  save_exercises(&exercises)
+}
+
+pub fn exercise_name_exists(name: &str, exclude_id: Option<&str>) -> bool {
+  load_exercises()
+    .iter()
+    .any(|e| e.name == name && Some(e.id.as_str()) != exclude_id)
+}
+
+/// User-defined tunings, in addition to [`Tuning::library`]
+pub fn load_custom_tunings() -> Vec<Tuning> {
+  load_json(CUSTOM_TUNINGS_KEY).unwrap_or_default()
+}
+
+/// Saves a custom tuning, replacing an existing custom tuning with the same name
+pub fn save_custom_tuning(tuning: &Tuning) -> Result<(), String> {
+  if Tuning::from_library(&tuning.name).is_some() {
+    return Err(format!("'{}' is a built-in tuning", tuning.name));
+  }
+  let mut tunings = load_custom_tunings();
+  match tunings.iter_mut().find(|t| t.name == tuning.name) {
+    Some(existing) => *existing = tuning.clone(),
+    None => tunings.push(tuning.clone()),
+  }
+  save_json(CUSTOM_TUNINGS_KEY, &tunings)
+}
+
+pub fn delete_custom_tuning(name: &str) -> Result<(), String> {
+  let mut tunings = load_custom_tunings();
+  tunings.retain(|t| t.name != name);
+  save_json(CUSTOM_TUNINGS_KEY, &tunings)
+}
+
+pub fn load_fretboard_orientation() -> Option<FretboardOrientation> {
+  load_json(FRETBOARD_ORIENTATION_KEY)
+}
+
+pub fn save_fretboard_orientation(orientation: FretboardOrientation) -> Result<(), String> {
+  save_json(FRETBOARD_ORIENTATION_KEY, &orientation)
+}
+
+pub fn load_fretboard_theme() -> Option<FretboardTheme> {
+  load_json(FRETBOARD_THEME_KEY)
+}
+
+pub fn save_fretboard_theme(theme: FretboardTheme) -> Result<(), String> {
+  save_json(FRETBOARD_THEME_KEY, &theme)
+}
+
+pub fn load_instrument() -> Option<Instrument> {
+  load_json(INSTRUMENT_KEY)
+}
+
+pub fn save_instrument(instrument: Instrument) -> Result<(), String> {
+  save_json(INSTRUMENT_KEY, &instrument)
+}
+
+pub fn load_fret_sound() -> Option<bool> {
+  load_json(FRET_SOUND_KEY)
+}
+
+pub fn save_fret_sound(enabled: bool) -> Result<(), String> {
+  save_json(FRET_SOUND_KEY, &enabled)
+}
+
+/// Saved trainer settings, if they still fit on a fretboard
+pub fn load_trainer_settings() -> Option<TrainerSettings> {
+  load_json(TRAINER_SETTINGS_KEY).filter(TrainerSettings::is_valid)
+}
+
+pub fn save_trainer_settings(settings: &TrainerSettings) -> Result<(), String> {
+  save_json(TRAINER_SETTINGS_KEY, settings)
+}
+
+/// The same position holds another note in another tuning, so each tuning of an instrument
+/// has its own history. Standard guitar tuning keeps the key from before the split.
+fn learning_key(instrument: Instrument, tuning: &Tuning) -> String {
+  if instrument == Instrument::Guitar && tuning.notes == Tuning::standard().notes {
+    TRAINER_LEARNING_KEY.to_string()
+  } else {
+    format!(
+      "{TRAINER_LEARNING_KEY}_{instrument}_{}",
+      tuning.notes_string()
+    )
+  }
+}
+
+/// Answer history and review schedule of the trainer in a tuning
+pub fn load_learning_state(instrument: Instrument, tuning: &Tuning) -> LearningState {
+  load_json(&learning_key(instrument, tuning)).unwrap_or_default()
+}
+
+pub fn save_learning_state(
+  instrument: Instrument,
+  tuning: &Tuning,
+  learning: &LearningState,
+) -> Result<(), String> {
+  save_json(&learning_key(instrument, tuning), learning)
+}
+
+pub fn load_leaderboard() -> Leaderboard {
+  load_json(TRAINER_LEADERBOARD_KEY).unwrap_or_default()
+}
+
+pub fn save_leaderboard(leaderboard: &Leaderboard) -> Result<(), String> {
+  save_json(TRAINER_LEADERBOARD_KEY, leaderboard)
+}
+
+/// Saved A4 reference of the tuner in Hz
+pub fn load_tuner_reference() -> Option<f64> {
+  load_json(TUNER_REFERENCE_KEY).map(clamp_reference)
+}
+
+pub fn save_tuner_reference(reference_hz: f64) -> Result<(), String> {
+  save_json(TUNER_REFERENCE_KEY, &reference_hz)
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+
+use strum_macros::EnumIter;
+
+#[derive(
+  Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum TechniqueCategory {
+  #[default]
+  AlternatePicking,
+  Legato,
+  Sweep,
+  Tapping,
+  HybridPicking,
+}
+
+impl fmt::Display for TechniqueCategory {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TechniqueCategory::AlternatePicking => write!(f, "Alternate Picking"),
+      TechniqueCategory::Legato => write!(f, "Legato"),
+      TechniqueCategory::Sweep => write!(f, "Sweep Picking"),
+      TechniqueCategory::Tapping => write!(f, "Tapping"),
+      TechniqueCategory::HybridPicking => write!(f, "Hybrid Picking"),
+    }
+  }
+}
+
+/// How many notes are played per metronome beat
+#[derive(
+  Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum Subdivision {
+  Quarter,
+  Eighth,
+  Triplet,
+  #[default]
+  Sixteenth,
+  Sextuplet,
+}
+
+impl Subdivision {
+  pub fn notes_per_beat(self) -> u32 {
+    match self {
+      Subdivision::Quarter => 1,
+      Subdivision::Eighth => 2,
+      Subdivision::Triplet => 3,
+      Subdivision::Sixteenth => 4,
+      Subdivision::Sextuplet => 6,
+    }
+  }
+}
+
+impl fmt::Display for Subdivision {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Subdivision::Quarter => write!(f, "Quarter Notes"),
+      Subdivision::Eighth => write!(f, "Eighth Notes"),
+      Subdivision::Triplet => write!(f, "Triplets"),
+      Subdivision::Sixteenth => write!(f, "Sixteenth Notes"),
+      Subdivision::Sextuplet => write!(f, "Sextuplets"),
+    }
+  }
+}
+
+/// Settings of a technique exercise, practiced with a tempo ladder from `start_bpm` up to
+/// `target_bpm`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Technique {
+  pub category: TechniqueCategory,
+  pub start_bpm: u32,
+  pub target_bpm: u32,
+  /// BPM increase per ladder step
+  pub bpm_step: u32,
+  pub subdivision: Subdivision,
+  /// Clean repetitions needed before moving up the ladder
+  pub clean_reps_per_step: u32,
+  /// Highest BPM at which a ladder step was completed cleanly
+  pub best_bpm: Option<u32>,
+}
+
+impl Default for Technique {
+  fn default() -> Self {
+    Self {
+      category: TechniqueCategory::default(),
+      start_bpm: 60,
+      target_bpm: 120,
+      bpm_step: 5,
+      subdivision: Subdivision::default(),
+      clean_reps_per_step: 3,
+      best_bpm: None,
+    }
+  }
+}
+
+impl Technique {
+  /// BPM of the ladder step after `bpm`, or `None` if the target is reached
+  pub fn next_ladder_bpm(&self, bpm: u32) -> Option<u32> {
+    (bpm < self.target_bpm).then(|| (bpm + self.bpm_step.max(1)).min(self.target_bpm))
+  }
+
+  /// Stores `bpm` as best BPM if it is higher than the current one.
+  /// Returns whether the best BPM changed.
+  pub fn record_clean_bpm(&mut self, bpm: u32) -> bool {
+    if self.best_bpm.is_some_and(|best| best >= bpm) {
+      return false;
+    }
+    self.best_bpm = Some(bpm);
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_tempo_ladder() {
+    let technique = Technique {
+      start_bpm: 100,
+      target_bpm: 112,
+      bpm_step: 5,
+      ..Technique::default()
+    };
+    assert_eq!(technique.next_ladder_bpm(100), Some(105));
+    assert_eq!(technique.next_ladder_bpm(110), Some(112));
+    assert_eq!(technique.next_ladder_bpm(112), None);
+    assert_eq!(technique.next_ladder_bpm(130), None);
+  }
+
+  #[test]
+  fn test_record_clean_bpm() {
+    let mut technique = Technique::default();
+    assert!(technique.record_clean_bpm(80));
+    assert!(!technique.record_clean_bpm(70));
+    assert!(technique.record_clean_bpm(90));
+    assert_eq!(technique.best_bpm, Some(90));
+  }
+}
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+
+use strum_macros::EnumIter;
+
+use crate::models::trainer::TrainerMode;
+
+/// Length of a sprint in milliseconds
+pub const SPRINT_MS: f64 = 60_000.0;
+/// Correct answers needed to finish a race
+pub const RACE_TARGET: u32 = 20;
+/// Best scores kept for each challenge, mode and settings
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// Timed ways to play the trainer
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum Challenge {
+  /// No clock and no score, the questions go on until the player stops
+  #[default]
+  Practice,
+  /// As many correct answers as possible in 60 seconds
+  Sprint,
+  /// 20 correct answers as fast as possible
+  FirstTo20,
+  /// Correct answers until the first mistake
+  SuddenDeath,
+}
+
+impl std::fmt::Display for Challenge {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Challenge::Practice => write!(f, "Practice"),
+      Challenge::Sprint => write!(f, "60-Second Sprint"),
+      Challenge::FirstTo20 => write!(f, "First to {RACE_TARGET}"),
+      Challenge::SuddenDeath => write!(f, "Sudden Death"),
+    }
+  }
+}
+
+impl Challenge {
+  pub fn is_timed(self) -> bool {
+    self != Challenge::Practice
+  }
+
+  /// Orders two scores of this challenge, the better one first. Races are scored by
+  /// their time, the other challenges by the correct answers.
+  pub fn compare_scores(self, left: u64, right: u64) -> Ordering {
+    match self {
+      Challenge::FirstTo20 => left.cmp(&right),
+      _ => right.cmp(&left),
+    }
+  }
+
+  pub fn format_score(self, score: u64) -> String {
+    match self {
+      Challenge::FirstTo20 => format!("{:.1}s", score as f64 / 1000.0),
+      _ => format!("{score} correct"),
+    }
+  }
+}
+
+/// A running or finished challenge. Times are milliseconds since the epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChallengeRun {
+  pub challenge: Challenge,
+  pub started_ms: f64,
+  pub correct: u32,
+  pub incorrect: u32,
+  /// Time from the previous answer, or the start, to each answer
+  pub response_times_ms: Vec<u64>,
+  last_answer_ms: f64,
+  /// End of the run, `None` while it is running
+  pub finished_ms: Option<f64>,
+}
+
+impl ChallengeRun {
+  pub fn new(challenge: Challenge, now_ms: f64) -> Self {
+    Self {
+      challenge,
+      started_ms: now_ms,
+      correct: 0,
+      incorrect: 0,
+      response_times_ms: vec![],
+      last_answer_ms: now_ms,
+      finished_ms: None,
+    }
+  }
+
+  pub fn is_finished(&self) -> bool {
+    self.finished_ms.is_some()
+  }
+
+  /// Ends a sprint once its time is up, returns whether the run is over
+  pub fn tick(&mut self, now_ms: f64) -> bool {
+    if !self.is_finished() && self.remaining_ms(now_ms) == Some(0.0) {
+      self.finished_ms = Some(self.started_ms + SPRINT_MS);
+    }
+    self.is_finished()
+  }
+
+  /// Records an answer given at `now_ms`, returns whether the run is over. Answers after
+  /// the end, e.g. after the sprint time ran out, don't count.
+  pub fn answer(&mut self, correct: bool, now_ms: f64) -> bool {
+    if self.tick(now_ms) {
+      return true;
+    }
+    self
+      .response_times_ms
+      .push((now_ms - self.last_answer_ms).max(0.0) as u64);
+    self.last_answer_ms = now_ms;
+    if correct {
+      self.correct += 1;
+    } else {
+      self.incorrect += 1;
+    }
+    let over = match self.challenge {
+      Challenge::FirstTo20 => self.correct >= RACE_TARGET,
+      Challenge::SuddenDeath => self.incorrect > 0,
+      Challenge::Sprint | Challenge::Practice => false,
+    };
+    if over {
+      self.finished_ms = Some(now_ms);
+    }
+    over
+  }
+
+  /// Time left of a sprint, `None` for the other challenges
+  pub fn remaining_ms(&self, now_ms: f64) -> Option<f64> {
+    (self.challenge == Challenge::Sprint).then(|| (SPRINT_MS - (now_ms - self.started_ms)).max(0.0))
+  }
+
+  pub fn elapsed_ms(&self, now_ms: f64) -> f64 {
+    (self.finished_ms.unwrap_or(now_ms) - self.started_ms).max(0.0)
+  }
+
+  pub fn average_response_ms(&self) -> Option<u64> {
+    let answers = self.response_times_ms.len() as u64;
+    (answers > 0).then(|| self.response_times_ms.iter().sum::<u64>() / answers)
+  }
+
+  /// Result of a finished run, `None` while it is running or if there is nothing to
+  /// rank, e.g. a sprint without a correct answer
+  pub fn high_score(&self, mode: TrainerMode, settings: &str) -> Option<HighScore> {
+    let finished_ms = self.finished_ms?;
+    let score = match self.challenge {
+      Challenge::Practice => return None,
+      Challenge::FirstTo20 => self.elapsed_ms(finished_ms).round() as u64,
+      Challenge::Sprint | Challenge::SuddenDeath => u64::from(self.correct),
+    };
+    (self.correct > 0).then(|| HighScore {
+      challenge: self.challenge,
+      mode,
+      settings: settings.to_string(),
+      score,
+      correct: self.correct,
+      incorrect: self.incorrect,
+      average_response_ms: self.average_response_ms(),
+      achieved_ms: finished_ms,
+    })
+  }
+}
+
+/// A finished challenge on the leaderboard
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HighScore {
+  pub challenge: Challenge,
+  pub mode: TrainerMode,
+  /// Summary of the tuning and trainer settings, scores only compete with equal settings
+  pub settings: String,
+  pub score: u64,
+  pub correct: u32,
+  pub incorrect: u32,
+  pub average_response_ms: Option<u64>,
+  /// Milliseconds since the epoch
+  pub achieved_ms: f64,
+}
+
+impl HighScore {
+  fn competes_in(&self, challenge: Challenge, mode: TrainerMode, settings: &str) -> bool {
+    self.challenge == challenge && self.mode == mode && self.settings == settings
+  }
+}
+
+/// Best challenge results of this browser
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Leaderboard {
+  pub scores: Vec<HighScore>,
+}
+
+impl Leaderboard {
+  /// Best scores of a challenge with a mode and settings, the best first. Equal scores
+  /// are ordered by age.
+  pub fn top(&self, challenge: Challenge, mode: TrainerMode, settings: &str) -> Vec<&HighScore> {
+    let mut scores: Vec<&HighScore> = self
+      .scores
+      .iter()
+      .filter(|score| score.competes_in(challenge, mode, settings))
+      .collect();
+    scores.sort_by(|left, right| {
+      challenge
+        .compare_scores(left.score, right.score)
+        .then(left.achieved_ms.total_cmp(&right.achieved_ms))
+    });
+    scores
+  }
+
+  /// Adds a score and drops the ones that fall off the board.
+  /// Returns the rank of the new score, starting at 1, if it made the board.
+  pub fn add(&mut self, high_score: HighScore) -> Option<usize> {
+    let (challenge, mode, settings) = (
+      high_score.challenge,
+      high_score.mode,
+      high_score.settings.clone(),
+    );
+    self.scores.push(high_score.clone());
+    let kept: Vec<HighScore> = self
+      .top(challenge, mode, &settings)
+      .into_iter()
+      .take(LEADERBOARD_SIZE)
+      .cloned()
+      .collect();
+    let rank = kept.iter().position(|score| *score == high_score);
+    self
+      .scores
+      .retain(|score| !score.competes_in(challenge, mode, &settings));
+    self.scores.extend(kept);
+    rank.map(|idx| idx + 1)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_challenge_runs() {
+    let mut sprint = ChallengeRun::new(Challenge::Sprint, 1000.0);
+    assert!(!sprint.answer(true, 3000.0));
+    assert!(!sprint.answer(false, 4000.0));
+    assert_eq!(sprint.remaining_ms(31_000.0), Some(30_000.0));
+    assert!(sprint.tick(61_000.0));
+    // Too late
+    assert!(sprint.answer(true, 62_000.0));
+    assert_eq!((sprint.correct, sprint.incorrect), (1, 1));
+    assert_eq!(sprint.response_times_ms, vec![2000, 1000]);
+    assert_eq!(sprint.average_response_ms(), Some(1500));
+
+    let mut race = ChallengeRun::new(Challenge::FirstTo20, 0.0);
+    for answer in 1..RACE_TARGET {
+      assert!(!race.answer(true, f64::from(answer) * 500.0));
+    }
+    assert!(race.answer(true, 12_345.0));
+    assert_eq!(
+      race.high_score(TrainerMode::NoteNames, "").unwrap().score,
+      12_345
+    );
+
+    let mut sudden_death = ChallengeRun::new(Challenge::SuddenDeath, 0.0);
+    assert!(!sudden_death.answer(true, 100.0));
+    assert!(sudden_death.answer(false, 200.0));
+    assert_eq!(
+      sudden_death
+        .high_score(TrainerMode::Intervals, "")
+        .unwrap()
+        .score,
+      1
+    );
+  }
+
+  #[test]
+  fn test_leaderboard_ranks_by_challenge() {
+    let score = |challenge, score, achieved_ms| HighScore {
+      challenge,
+      mode: TrainerMode::Intervals,
+      settings: "Standard".to_string(),
+      score,
+      correct: 20,
+      incorrect: 0,
+      average_response_ms: None,
+      achieved_ms,
+    };
+    let mut leaderboard = Leaderboard::default();
+    assert_eq!(
+      leaderboard.add(score(Challenge::FirstTo20, 30_000, 1.0)),
+      Some(1)
+    );
+    // Faster races are better, more correct answers are better
+    assert_eq!(
+      leaderboard.add(score(Challenge::FirstTo20, 20_000, 2.0)),
+      Some(1)
+    );
+    assert_eq!(leaderboard.add(score(Challenge::Sprint, 12, 3.0)), Some(1));
+    assert_eq!(leaderboard.add(score(Challenge::Sprint, 15, 4.0)), Some(1));
+    assert_eq!(leaderboard.add(score(Challenge::Sprint, 15, 5.0)), Some(2));
+
+    for achieved_ms in 0..LEADERBOARD_SIZE {
+      leaderboard.add(score(Challenge::Sprint, 20, 10.0 + achieved_ms as f64));
+    }
+    assert_eq!(leaderboard.add(score(Challenge::Sprint, 16, 30.0)), None);
+    let sprints = leaderboard.top(Challenge::Sprint, TrainerMode::Intervals, "Standard");
+    assert_eq!(sprints.len(), LEADERBOARD_SIZE);
+    assert!(sprints.iter().all(|high_score| high_score.score == 20));
+    // Other settings have their own board
+    assert!(leaderboard
+      .top(Challenge::Sprint, TrainerMode::Intervals, "Drop D")
+      .is_empty());
+    assert_eq!(
+      leaderboard
+        .top(Challenge::FirstTo20, TrainerMode::Intervals, "Standard")
+        .len(),
+      2
+    );
+  }
+}
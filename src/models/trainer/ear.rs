@@ -0,0 +1,107 @@
+use strum_macros::EnumIter;
+
+use crate::audio::Tone;
+use crate::music::intervals::Interval;
+use crate::music::notes::Note;
+
+/// MIDI number of C3, reference notes are played in the octave above it
+const REFERENCE_OCTAVE_MIDI: u8 = 48;
+/// Length of a melodic tone in seconds
+const MELODIC_TONE_SECONDS: f64 = 0.7;
+/// Time between the starts of melodic tones in seconds
+const MELODIC_GAP_SECONDS: f64 = 0.8;
+/// Length of a harmonic interval in seconds
+const HARMONIC_SECONDS: f64 = 1.5;
+
+/// How the two notes of an interval are played in ear training. The reference note
+/// is always the lower one, so the answer is the same interval above it.
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum EarPlayback {
+  /// The reference note, then the upper note
+  #[default]
+  Ascending,
+  /// The upper note, then the reference note
+  Descending,
+  /// Both notes at once
+  Harmonic,
+}
+
+impl std::fmt::Display for EarPlayback {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      EarPlayback::Ascending => write!(f, "Ascending"),
+      EarPlayback::Descending => write!(f, "Descending"),
+      EarPlayback::Harmonic => write!(f, "Harmonic"),
+    }
+  }
+}
+
+impl EarPlayback {
+  /// Tones of `interval` above `reference`
+  pub fn tones(self, reference: Note, interval: Interval) -> Vec<Tone> {
+    let lower = reference_midi(reference);
+    let upper = lower + interval.half_tone_steps() as u8;
+    let melodic = |first, second| {
+      vec![
+        Tone {
+          midi: first,
+          start: 0.0,
+          duration: MELODIC_TONE_SECONDS,
+        },
+        Tone {
+          midi: second,
+          start: MELODIC_GAP_SECONDS,
+          duration: MELODIC_TONE_SECONDS,
+        },
+      ]
+    };
+    match self {
+      EarPlayback::Ascending => melodic(lower, upper),
+      EarPlayback::Descending => melodic(upper, lower),
+      EarPlayback::Harmonic => [lower, upper]
+        .map(|midi| Tone {
+          midi,
+          start: 0.0,
+          duration: HARMONIC_SECONDS,
+        })
+        .to_vec(),
+    }
+  }
+}
+
+/// MIDI number of the note in the octave from C3, a comfortable range on the guitar
+fn reference_midi(note: Note) -> u8 {
+  let idx = Note::all_notes()
+    .iter()
+    .position(|other| *other == note)
+    .unwrap_or_default();
+  REFERENCE_OCTAVE_MIDI + idx as u8
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_playback_tones() {
+    let midis = |playback: EarPlayback| -> Vec<(u8, f64)> {
+      playback
+        .tones(Note::A, Interval::PerfectFifth)
+        .iter()
+        .map(|tone| (tone.midi, tone.start))
+        .collect()
+    };
+    // A3 and E4
+    assert_eq!(
+      midis(EarPlayback::Ascending),
+      vec![(57, 0.0), (64, MELODIC_GAP_SECONDS)]
+    );
+    assert_eq!(
+      midis(EarPlayback::Descending),
+      vec![(64, 0.0), (57, MELODIC_GAP_SECONDS)]
+    );
+    assert_eq!(midis(EarPlayback::Harmonic), vec![(57, 0.0), (64, 0.0)]);
+  }
+}
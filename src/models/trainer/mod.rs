@@ -0,0 +1,11 @@
+pub mod challenge;
+pub mod ear;
+pub mod review;
+pub mod settings;
+pub mod theory;
+
+pub use challenge::{Challenge, ChallengeRun, HighScore, Leaderboard};
+pub use ear::EarPlayback;
+pub use review::{HeatMetric, LearningState, ReviewItem, ReviewState};
+pub use settings::{TrainerMode, TrainerSettings};
+pub use theory::TheoryQuestion;
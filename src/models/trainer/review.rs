@@ -0,0 +1,253 @@
+use strum_macros::EnumIter;
+
+use crate::components::fretboard::definitions::HEAT_LEVELS;
+use crate::components::fretboard::FretCoord;
+use crate::music::intervals::Interval;
+
+/// Ease of an item that was never asked, as in SM-2
+const INITIAL_EASE: f64 = 2.5;
+/// Items never get easier to forget than this
+const MIN_EASE: f64 = 1.3;
+/// Chance of a question that isn't due yet, relative to a due one
+const NOT_DUE_WEIGHT: f64 = 0.05;
+/// Average answer times in milliseconds from which a position counts as one heat level
+/// slower, the first one is the fastest
+const RESPONSE_TIME_STEPS: [f64; HEAT_LEVELS as usize - 1] = [1500.0, 2500.0, 4000.0, 6000.0];
+
+/// Something the trainer asks for and schedules on its own
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ReviewItem {
+  /// The note at a position on the fretboard
+  Cell(FretCoord),
+  Interval(Interval),
+}
+
+/// SM-2 state of an item. Intervals count answered questions instead of days, so
+/// positions come back within the same practice session.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReviewState {
+  pub ease: f64,
+  /// Questions until the item is due again
+  pub interval: u64,
+  /// Correct answers in a row
+  pub repetitions: u32,
+  /// Question number at which the item is due
+  pub due: u64,
+  pub correct: u32,
+  pub incorrect: u32,
+  /// Summed answer time of the timed answers
+  #[serde(default)]
+  pub total_response_ms: u64,
+  #[serde(default)]
+  pub timed_answers: u32,
+}
+
+impl Default for ReviewState {
+  fn default() -> Self {
+    Self {
+      ease: INITIAL_EASE,
+      interval: 0,
+      repetitions: 0,
+      due: 0,
+      correct: 0,
+      incorrect: 0,
+      total_response_ms: 0,
+      timed_answers: 0,
+    }
+  }
+}
+
+impl ReviewState {
+  /// Updates the schedule with an answer of the SM-2 quality `quality` (0 to 5),
+  /// given at question number `step`
+  pub fn review(&mut self, quality: u8, step: u64) {
+    let quality = quality.min(5);
+    if quality >= 3 {
+      self.interval = match self.repetitions {
+        0 => 1,
+        1 => 6,
+        _ => (self.interval as f64 * self.ease).round() as u64,
+      };
+      self.repetitions += 1;
+      self.correct += 1;
+    } else {
+      self.interval = 1;
+      self.repetitions = 0;
+      self.incorrect += 1;
+    }
+    let missing = f64::from(5 - quality);
+    self.ease = (self.ease + 0.1 - missing * (0.08 + missing * 0.02)).max(MIN_EASE);
+    self.due = step + self.interval;
+  }
+
+  /// Share of correct answers, `None` before the first answer
+  pub fn accuracy(&self) -> Option<f64> {
+    let answers = self.correct + self.incorrect;
+    (answers > 0).then(|| f64::from(self.correct) / f64::from(answers))
+  }
+
+  pub fn average_response_ms(&self) -> Option<f64> {
+    (self.timed_answers > 0).then(|| self.total_response_ms as f64 / f64::from(self.timed_answers))
+  }
+}
+
+/// What a trainer heatmap shows for each position
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter)]
+pub enum HeatMetric {
+  #[default]
+  Accuracy,
+  ResponseTime,
+}
+
+impl std::fmt::Display for HeatMetric {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      HeatMetric::Accuracy => write!(f, "Accuracy"),
+      HeatMetric::ResponseTime => write!(f, "Response Time"),
+    }
+  }
+}
+
+impl HeatMetric {
+  /// Heat level from 0 (weak) to `HEAT_LEVELS - 1` (strong) and a label, `None` if there
+  /// is nothing to show yet
+  pub fn heat(self, state: &ReviewState) -> Option<(u8, String)> {
+    match self {
+      HeatMetric::Accuracy => state.accuracy().map(|accuracy| {
+        let level = (accuracy * f64::from(HEAT_LEVELS - 1)).round() as u8;
+        (level, format!("{:.0}%", accuracy * 100.0))
+      }),
+      HeatMetric::ResponseTime => state.average_response_ms().map(|ms| {
+        let slower_steps = RESPONSE_TIME_STEPS
+          .iter()
+          .filter(|step| ms >= **step)
+          .count();
+        (
+          HEAT_LEVELS - 1 - slower_steps as u8,
+          format!("{:.1}s", ms / 1000.0),
+        )
+      }),
+    }
+  }
+}
+
+/// Answer history of the trainer and the schedule of every item asked so far
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LearningState {
+  /// Number of answered questions
+  pub step: u64,
+  pub items: Vec<(ReviewItem, ReviewState)>,
+}
+
+impl LearningState {
+  pub fn get(&self, item: ReviewItem) -> Option<&ReviewState> {
+    self
+      .items
+      .iter()
+      .find_map(|(other, state)| (*other == item).then_some(state))
+  }
+
+  /// Records an answer for an item of the current question, correct answers without a
+  /// mistake are easy, the others are reviewed again soon. Answers that weren't given,
+  /// e.g. missed positions, have no response time.
+  pub fn record(&mut self, item: ReviewItem, correct: bool, response_ms: Option<u64>) {
+    let quality = if correct { 5 } else { 1 };
+    let step = self.step;
+    let state = match self.items.iter().position(|(other, _)| *other == item) {
+      Some(idx) => &mut self.items[idx].1,
+      None => {
+        self.items.push((item, ReviewState::default()));
+        &mut self.items.last_mut().unwrap().1
+      }
+    };
+    state.review(quality, step);
+    if let Some(response_ms) = response_ms {
+      state.total_response_ms += response_ms;
+      state.timed_answers += 1;
+    }
+  }
+
+  /// Moves on to the next question once all its items are recorded
+  pub fn finish_question(&mut self) {
+    self.step += 1;
+  }
+
+  /// How likely the item is asked next. New items are as likely as due ones, overdue
+  /// and hard items are more likely and items that aren't due are rarely asked.
+  pub fn weight(&self, item: ReviewItem) -> f64 {
+    match self.get(item) {
+      None => 1.0,
+      Some(state) if state.due <= self.step => {
+        let overdue = (self.step - state.due) as f64 / state.interval.max(1) as f64;
+        1.0 + overdue.min(3.0) + (INITIAL_EASE - state.ease).max(0.0)
+      }
+      Some(_) => NOT_DUE_WEIGHT,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_sm2_intervals() {
+    let mut state = ReviewState::default();
+    state.review(5, 0);
+    assert_eq!((state.interval, state.due), (1, 1));
+    state.review(5, 1);
+    assert_eq!((state.interval, state.due), (6, 7));
+    let ease = state.ease;
+    state.review(5, 7);
+    assert_eq!(state.interval, (6.0 * ease).round() as u64);
+    assert!(state.ease > INITIAL_EASE);
+
+    state.review(1, 30);
+    assert_eq!((state.interval, state.repetitions, state.due), (1, 0, 31));
+    assert_eq!((state.correct, state.incorrect), (3, 1));
+    assert!(state.ease < INITIAL_EASE);
+  }
+
+  #[test]
+  fn test_weak_items_are_preferred() {
+    let cell = |fret_idx| {
+      ReviewItem::Cell(FretCoord {
+        string_idx: 0,
+        fret_idx,
+      })
+    };
+    let mut learning = LearningState::default();
+    learning.record(cell(1), true, Some(1000));
+    learning.record(cell(2), false, None);
+    learning.finish_question();
+    learning.record(cell(1), true, Some(1000));
+    learning.record(cell(2), false, None);
+    learning.finish_question();
+
+    // The known cell waits 6 questions, the missed one is due and harder than a new one
+    assert_eq!(learning.weight(cell(1)), NOT_DUE_WEIGHT);
+    assert!(learning.weight(cell(2)) > learning.weight(cell(3)));
+    assert_eq!(learning.weight(cell(3)), 1.0);
+    assert_eq!(learning.get(cell(2)).unwrap().incorrect, 2);
+
+    let known = learning.get(cell(1)).unwrap();
+    assert_eq!(known.average_response_ms(), Some(1000.0));
+    assert_eq!(
+      HeatMetric::Accuracy.heat(known),
+      Some((HEAT_LEVELS - 1, "100%".into()))
+    );
+    assert_eq!(
+      HeatMetric::ResponseTime.heat(known),
+      Some((HEAT_LEVELS - 1, "1.0s".into()))
+    );
+    let missed = learning.get(cell(2)).unwrap();
+    assert_eq!(HeatMetric::Accuracy.heat(missed), Some((0, "0%".into())));
+    assert_eq!(HeatMetric::ResponseTime.heat(missed), None);
+
+    let saved = serde_json::to_string(&learning).unwrap();
+    let loaded: LearningState = serde_json::from_str(&saved).unwrap();
+    assert_eq!(loaded.step, 2);
+    assert_eq!(loaded.get(cell(1)).unwrap().due, 7);
+  }
+}
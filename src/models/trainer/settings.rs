@@ -0,0 +1,433 @@
+use rand::seq::IndexedRandom;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::components::fretboard::definitions::MAX_FRETS;
+use crate::components::fretboard::FretCoord;
+use crate::models::fretboard::{FretboardModel, FretboardModelExt};
+use crate::models::trainer::{EarPlayback, LearningState, ReviewItem, TheoryQuestion};
+use crate::music::heptatonic_scales::{HeptaScaleImpl, HeptaScaleType};
+use crate::music::intervals::Interval;
+use crate::music::notes::Note;
+use crate::music::{Chord, ChordQuality, Scale, ScaleType};
+
+/// What the trainer asks for
+#[derive(
+  Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum TrainerMode {
+  /// Find an interval of the shown note
+  #[default]
+  Intervals,
+  /// Name the note of the shown fret
+  NoteNames,
+  /// Find every position of a note within a few frets
+  FindNotes,
+  /// Play a degree of a scale, e.g. the b7 of G Mixolydian
+  ScaleDegrees,
+  /// Find a tone of the chord whose root is shown
+  ChordTones,
+  /// Name or find an interval that is played above the shown note
+  EarTraining,
+}
+
+impl std::fmt::Display for TrainerMode {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TrainerMode::Intervals => write!(f, "Intervals"),
+      TrainerMode::NoteNames => write!(f, "Name the Note"),
+      TrainerMode::FindNotes => write!(f, "Find the Notes"),
+      TrainerMode::ScaleDegrees => write!(f, "Scale Degrees"),
+      TrainerMode::ChordTones => write!(f, "Chord Tones"),
+      TrainerMode::EarTraining => write!(f, "Ear Training"),
+    }
+  }
+}
+
+/// Which questions the fretboard trainer asks
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct TrainerSettings {
+  pub start_fret: usize,
+  pub end_fret: usize,
+  /// Strings without questions, so a new tuning with more strings has them all enabled
+  pub disabled_strings: Vec<u8>,
+  /// Intervals that are asked for, all but unison if empty
+  pub intervals: Vec<Interval>,
+  /// Major key the shown and the asked notes are taken from
+  pub key: Option<Note>,
+  /// How ear training plays the intervals
+  pub playback: EarPlayback,
+}
+
+impl Default for TrainerSettings {
+  fn default() -> Self {
+    Self {
+      start_fret: 0,
+      end_fret: 12,
+      disabled_strings: vec![],
+      intervals: Self::all_intervals(),
+      key: None,
+      playback: EarPlayback::default(),
+    }
+  }
+}
+
+impl TrainerSettings {
+  /// Every interval except unison
+  pub fn all_intervals() -> Vec<Interval> {
+    Interval::iter()
+      .filter(|interval| interval != &Interval::Unison)
+      .collect()
+  }
+
+  /// Intervals that are easy to hear and find, without tritones, sixths and sevenths
+  pub fn beginner_intervals() -> Vec<Interval> {
+    vec![
+      Interval::MajorThird,
+      Interval::PerfectFourth,
+      Interval::PerfectFifth,
+      Interval::Octave,
+    ]
+  }
+
+  /// Short description to tell apart leaderboards, e.g.
+  /// "Frets 0–12, all strings, all intervals, any key"
+  pub fn summary(&self) -> String {
+    let strings = if self.disabled_strings.is_empty() {
+      "all strings".to_string()
+    } else {
+      let mut disabled = self.disabled_strings.clone();
+      disabled.sort_unstable();
+      let numbers: Vec<String> = disabled.iter().map(|idx| (idx + 1).to_string()).collect();
+      format!("without strings {}", numbers.join(" "))
+    };
+    let pool = self.interval_pool();
+    let intervals = if pool.len() == Self::all_intervals().len() {
+      "all intervals".to_string()
+    } else {
+      let names: Vec<&str> = Interval::iter()
+        .filter(|interval| pool.contains(interval))
+        .map(Interval::short_name)
+        .collect();
+      format!("intervals {}", names.join(" "))
+    };
+    let key = self
+      .key
+      .map_or("any key".to_string(), |key| format!("{key} major"));
+    format!(
+      "Frets {}–{}, {strings}, {intervals}, {key}",
+      self.start_fret, self.end_fret
+    )
+  }
+
+  /// Whether the settings fit on a fretboard, e.g. after loading them from storage
+  pub fn is_valid(&self) -> bool {
+    self.start_fret <= self.end_fret && self.end_fret < MAX_FRETS
+  }
+
+  pub fn is_string_enabled(&self, string_idx: u8) -> bool {
+    !self.disabled_strings.contains(&string_idx)
+  }
+
+  pub fn toggle_string(&mut self, string_idx: u8) {
+    if self.is_string_enabled(string_idx) {
+      self.disabled_strings.push(string_idx);
+    } else {
+      self.disabled_strings.retain(|idx| *idx != string_idx);
+    }
+  }
+
+  pub fn toggle_interval(&mut self, interval: Interval) {
+    if self.intervals.contains(&interval) {
+      self.intervals.retain(|i| *i != interval);
+    } else {
+      self.intervals.push(interval);
+    }
+  }
+
+  /// Intervals to draw the questions from
+  pub fn interval_pool(&self) -> Vec<Interval> {
+    if self.intervals.is_empty() {
+      Self::all_intervals()
+    } else {
+      self.intervals.clone()
+    }
+  }
+
+  /// Whether the note is in the selected key, every note is without a key
+  pub fn is_note_allowed(&self, note: Note) -> bool {
+    self.key.is_none_or(|key| {
+      Scale::new(key, ScaleType::Hepatonic(HeptaScaleType::Major)).contains_note(note)
+    })
+  }
+
+  /// Playable frets of the enabled strings with a note of the key
+  pub fn question_frets(&self, model: &FretboardModel) -> Vec<FretCoord> {
+    model
+      .playable_frets()
+      .into_iter()
+      .filter(|coord| self.is_string_enabled(coord.string_idx))
+      .filter(|coord| {
+        model
+          .note_from_fret(*coord)
+          .is_some_and(|note| self.is_note_allowed(note))
+      })
+      .collect()
+  }
+
+  /// The next fret to ask for, favouring cells that are due or often answered wrong
+  pub fn next_question_fret(
+    &self,
+    model: &FretboardModel,
+    learning: &LearningState,
+  ) -> Option<FretCoord> {
+    self
+      .question_frets(model)
+      .choose_weighted(&mut rand::rng(), |coord| {
+        learning.weight(ReviewItem::Cell(*coord))
+      })
+      .ok()
+      .copied()
+  }
+
+  /// The next reference fret and an interval whose target note can be found on the
+  /// enabled strings and stays in the key, favouring weak cells and intervals
+  pub fn next_interval_question(
+    &self,
+    model: &FretboardModel,
+    learning: &LearningState,
+  ) -> Option<(FretCoord, Interval)> {
+    let frets = self.question_frets(model);
+    let notes: Vec<Note> = frets
+      .iter()
+      .filter_map(|coord| model.note_from_fret(*coord))
+      .collect();
+    let pool = self.interval_pool();
+    let questions: Vec<(FretCoord, Interval)> = frets
+      .iter()
+      .zip(&notes)
+      .flat_map(|(coord, note)| {
+        pool
+          .iter()
+          .filter(|interval| notes.contains(&interval.of(*note)))
+          .map(|interval| (*coord, *interval))
+      })
+      .collect();
+    questions
+      .choose_weighted(&mut rand::rng(), |(coord, interval)| {
+        learning.weight(ReviewItem::Cell(*coord)) * learning.weight(ReviewItem::Interval(*interval))
+      })
+      .ok()
+      .copied()
+  }
+
+  /// The next scale degree to play, anywhere or on one string, favouring weak intervals.
+  /// With a key only its modes are asked, e.g. D Dorian in C major.
+  pub fn next_scale_degree_question(
+    &self,
+    model: &FretboardModel,
+    learning: &LearningState,
+  ) -> Option<TheoryQuestion> {
+    let notes = self.enabled_notes(model);
+    let questions: Vec<TheoryQuestion> = Note::all_notes()
+      .iter()
+      .flat_map(|root| {
+        HeptaScaleType::all_scale_types()
+          .into_iter()
+          .map(|scale_type| HeptaScaleImpl::new(*root, scale_type))
+      })
+      .filter(|scale| {
+        Note::all_notes()
+          .iter()
+          .filter(|note| scale.contains_note(**note))
+          .all(|note| self.is_note_allowed(*note))
+      })
+      .flat_map(|scale| (2..=7).map(move |degree| (scale, degree)))
+      .flat_map(|(scale, degree)| {
+        let anywhere = TheoryQuestion::ScaleDegree {
+          scale,
+          degree,
+          string_idx: None,
+        };
+        let mut strings: Vec<u8> = notes
+          .iter()
+          .filter(|(_, note)| *note == anywhere.target_note())
+          .map(|(coord, _)| coord.string_idx)
+          .collect();
+        strings.sort_unstable();
+        strings.dedup();
+        let on_string = strings
+          .iter()
+          .map(|string_idx| TheoryQuestion::ScaleDegree {
+            scale,
+            degree,
+            string_idx: Some(*string_idx),
+          });
+        // Degrees without a position in the frets can't be played anywhere
+        let questions: Vec<TheoryQuestion> = on_string.collect();
+        (!questions.is_empty())
+          .then_some(anywhere)
+          .into_iter()
+          .chain(questions)
+      })
+      .collect();
+    questions
+      .choose_weighted(&mut rand::rng(), |question| {
+        learning.weight(ReviewItem::Interval(question.interval()))
+      })
+      .ok()
+      .copied()
+  }
+
+  /// The next chord root to show and a chord tone to find on the enabled strings,
+  /// favouring weak cells and intervals. With a key only its chords are asked.
+  pub fn next_chord_tone_question(
+    &self,
+    model: &FretboardModel,
+    learning: &LearningState,
+  ) -> Option<TheoryQuestion> {
+    let notes = self.enabled_notes(model);
+    let questions: Vec<TheoryQuestion> = self
+      .question_frets(model)
+      .into_iter()
+      .filter_map(|coord| Some((coord, model.note_from_fret(coord)?)))
+      .flat_map(|(root, note)| {
+        ChordQuality::iter()
+          .map(move |quality| Chord::new(note, quality))
+          .filter(|chord| chord.notes().iter().all(|note| self.is_note_allowed(*note)))
+          .flat_map(move |chord| {
+            chord
+              .quality
+              .intervals()
+              .iter()
+              .filter(|interval| **interval != Interval::Unison)
+              .map(move |interval| TheoryQuestion::ChordTone {
+                chord,
+                root,
+                interval: *interval,
+              })
+          })
+      })
+      .filter(|question| {
+        notes
+          .iter()
+          .any(|(_, note)| *note == question.target_note())
+      })
+      .collect();
+    questions
+      .choose_weighted(&mut rand::rng(), |question| {
+        let root = question.shown_fret().map(|(coord, _)| coord);
+        root.map_or(1.0, |root| learning.weight(ReviewItem::Cell(root)))
+          * learning.weight(ReviewItem::Interval(question.interval()))
+      })
+      .ok()
+      .copied()
+  }
+
+  /// Playable frets of the enabled strings and their notes, the possible answers
+  fn enabled_notes(&self, model: &FretboardModel) -> Vec<(FretCoord, Note)> {
+    model
+      .playable_frets()
+      .into_iter()
+      .filter(|coord| self.is_string_enabled(coord.string_idx))
+      .filter_map(|coord| Some((coord, model.note_from_fret(coord)?)))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::fretboard::FretboardModelBuilder;
+  use leptos::prelude::*;
+
+  #[test]
+  fn test_questions_follow_settings() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let settings = TrainerSettings {
+        start_fret: 0,
+        end_fret: 3,
+        disabled_strings: vec![0, 1, 2, 3],
+        intervals: vec![Interval::PerfectFifth],
+        key: Some(Note::C),
+        ..TrainerSettings::default()
+      };
+      let model = FretboardModelBuilder::new()
+        .start_fret_val(settings.start_fret)
+        .end_fret_val(settings.end_fret)
+        .build()
+        .unwrap();
+
+      // Only the natural notes on the A and low E strings
+      let frets = settings.question_frets(&model);
+      assert!(frets.iter().all(|coord| coord.string_idx >= 4));
+      let notes: Vec<Note> = frets
+        .iter()
+        .filter_map(|coord| model.note_from_fret(*coord))
+        .collect();
+      assert_eq!(
+        notes,
+        vec![Note::A, Note::B, Note::C, Note::E, Note::F, Note::G]
+      );
+
+      for _ in 0..20 {
+        let (coord, interval) = settings
+          .next_interval_question(&model, &LearningState::default())
+          .unwrap();
+        let target = interval.of(model.note_from_fret(coord).unwrap());
+        assert_eq!(interval, Interval::PerfectFifth);
+        assert!(notes.contains(&target));
+
+        // Scales and chords of the key, their answers on the enabled strings
+        for question in [
+          settings.next_scale_degree_question(&model, &LearningState::default()),
+          settings.next_chord_tone_question(&model, &LearningState::default()),
+        ] {
+          let question = question.unwrap();
+          assert!(settings.is_note_allowed(question.target_note()));
+          assert!(!question
+            .answers(&model, |string_idx| settings.is_string_enabled(string_idx))
+            .is_empty());
+        }
+      }
+    });
+  }
+
+  #[test]
+  fn test_saved_settings() {
+    let mut settings: TrainerSettings = serde_json::from_str(r#"{"end_fret": 7}"#).unwrap();
+    assert_eq!(settings.end_fret, 7);
+    assert_eq!(settings.interval_pool(), TrainerSettings::all_intervals());
+    assert!(settings.is_valid());
+
+    settings.toggle_string(2);
+    assert!(!settings.is_string_enabled(2));
+    settings.toggle_string(2);
+    assert!(settings.is_string_enabled(2));
+
+    settings.end_fret = MAX_FRETS;
+    assert!(!settings.is_valid());
+  }
+
+  #[test]
+  fn test_summary() {
+    assert_eq!(
+      TrainerSettings::default().summary(),
+      "Frets 0–12, all strings, all intervals, any key"
+    );
+    let settings = TrainerSettings {
+      start_fret: 5,
+      end_fret: 9,
+      disabled_strings: vec![5, 0],
+      intervals: vec![Interval::PerfectFifth, Interval::MajorThird],
+      key: Some(Note::G),
+      ..TrainerSettings::default()
+    };
+    assert_eq!(
+      settings.summary(),
+      "Frets 5–9, without strings 1 6, intervals 3 5, G major"
+    );
+  }
+}
@@ -0,0 +1,235 @@
+use crate::components::fretboard::FretCoord;
+use crate::models::fretboard::{FretboardModel, FretboardModelExt};
+use crate::music::heptatonic_scales::HeptaScaleImpl;
+use crate::music::intervals::Interval;
+use crate::music::notes::{Note, NoteExt};
+use crate::music::Chord;
+
+/// A question about the notes of a scale or a chord
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TheoryQuestion {
+  /// A degree of a scale, on any enabled string or on one string
+  ScaleDegree {
+    scale: HeptaScaleImpl,
+    /// Degree counted from 1
+    degree: usize,
+    string_idx: Option<u8>,
+  },
+  /// A tone of the chord whose root is shown on the fretboard
+  ChordTone {
+    chord: Chord,
+    root: FretCoord,
+    interval: Interval,
+  },
+}
+
+impl TheoryQuestion {
+  /// Interval of the asked note above the scale or chord root
+  pub fn interval(&self) -> Interval {
+    match self {
+      TheoryQuestion::ScaleDegree { scale, degree, .. } => {
+        scale.scale_type().intervals()[degree - 1]
+      }
+      TheoryQuestion::ChordTone { interval, .. } => *interval,
+    }
+  }
+
+  /// Label of the asked note spelled from the scale or chord formula, e.g. "#4" in Lydian
+  pub fn degree_name(&self) -> String {
+    let degree = match self {
+      TheoryQuestion::ScaleDegree { degree, .. } => *degree,
+      TheoryQuestion::ChordTone {
+        chord, interval, ..
+      } => chord
+        .quality
+        .intervals()
+        .iter()
+        .position(|chord_interval| chord_interval == interval)
+        .map_or(0, |idx| chord.quality.degrees()[idx]),
+    };
+    self.interval().degree_name(degree)
+  }
+
+  pub fn target_note(&self) -> Note {
+    match self {
+      TheoryQuestion::ScaleDegree { scale, .. } => self.interval().of(scale.root_note()),
+      TheoryQuestion::ChordTone {
+        chord, interval, ..
+      } => interval.of(chord.root),
+    }
+  }
+
+  /// Fret shown with the question, the root of a chord
+  pub fn shown_fret(&self) -> Option<(FretCoord, Note)> {
+    match self {
+      TheoryQuestion::ScaleDegree { .. } => None,
+      TheoryQuestion::ChordTone { chord, root, .. } => Some((*root, chord.root)),
+    }
+  }
+
+  /// Whether the fret answers the question. Chord tones are checked against the shown
+  /// root, scale degrees against the scale.
+  pub fn is_answer(&self, model: &FretboardModel, coord: FretCoord) -> bool {
+    match self {
+      TheoryQuestion::ScaleDegree { string_idx, .. } => {
+        string_idx.is_none_or(|string_idx| string_idx == coord.string_idx)
+          && model.note_from_fret(coord) == Some(self.target_note())
+      }
+      TheoryQuestion::ChordTone { root, interval, .. } => {
+        model.is_interval_of(*root, coord, *interval)
+      }
+    }
+  }
+
+  /// Every answer within the frets of the model on the strings that pass `is_enabled`
+  pub fn answers(&self, model: &FretboardModel, is_enabled: impl Fn(u8) -> bool) -> Vec<FretCoord> {
+    model
+      .playable_frets()
+      .into_iter()
+      .filter(|coord| is_enabled(coord.string_idx) && self.is_answer(model, *coord))
+      .collect()
+  }
+
+  /// The question for the player, e.g. "Play the b7 of G Mixolydian on the 4th string (D)".
+  /// Strings are named by number since tunings can have several strings with the same note.
+  pub fn prompt(&self, tuning: &[Note]) -> String {
+    let degree = self.degree_name();
+    match self {
+      TheoryQuestion::ScaleDegree {
+        scale, string_idx, ..
+      } => {
+        let string = string_idx
+          .and_then(|idx| Some((idx as usize + 1, tuning.get(idx as usize)?)))
+          .map_or(String::new(), |(number, note)| {
+            format!(
+              " on the {} string ({})",
+              ordinal(number),
+              note.to_short_string()
+            )
+          });
+        format!("Play the {degree} of {scale}{string}")
+      }
+      TheoryQuestion::ChordTone { chord, .. } => {
+        format!("Find the {degree} of this {chord} chord")
+      }
+    }
+  }
+}
+
+/// "1st", "2nd", "3rd", "4th", ...
+fn ordinal(number: usize) -> String {
+  let suffix = match (number % 10, number % 100) {
+    (_, 11..=13) => "th",
+    (1, _) => "st",
+    (2, _) => "nd",
+    (3, _) => "rd",
+    _ => "th",
+  };
+  format!("{number}{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::fretboard::FretboardModelBuilder;
+  use crate::music::heptatonic_scales::HeptaScaleType;
+  use crate::music::ChordQuality;
+  use leptos::prelude::*;
+
+  #[test]
+  fn test_theory_answers() {
+    let owner = Owner::new();
+    owner.with(|| {
+      let model = FretboardModelBuilder::new()
+        .start_fret_val(0)
+        .end_fret_val(5)
+        .build()
+        .unwrap();
+
+      // The F on the D string, standard tuning has it at fret 3
+      let question = TheoryQuestion::ScaleDegree {
+        scale: HeptaScaleImpl::new(Note::G, HeptaScaleType::Mixolydian),
+        degree: 7,
+        string_idx: Some(3),
+      };
+      assert_eq!(question.target_note(), Note::F);
+      assert_eq!(
+        question.prompt(&model.get_tuning().get_untracked()),
+        "Play the b7 of G Mixolydian on the 4th string (D)"
+      );
+      assert_eq!(
+        question.answers(&model, |_| true),
+        vec![FretCoord {
+          string_idx: 3,
+          fret_idx: 3
+        }]
+      );
+
+      // The C major root on the A string, its thirds are on the low E, D and B strings
+      let question = TheoryQuestion::ChordTone {
+        chord: Chord::new(Note::C, ChordQuality::Major),
+        root: FretCoord {
+          string_idx: 4,
+          fret_idx: 3,
+        },
+        interval: Interval::MajorThird,
+      };
+      assert_eq!(question.prompt(&[]), "Find the 3 of this C chord");
+      let answers = question.answers(&model, |string_idx| string_idx != 0);
+      assert_eq!(answers.len(), 3);
+      assert!(answers
+        .iter()
+        .all(|coord| { coord.string_idx != 0 && model.note_from_fret(*coord) == Some(Note::E) }));
+    });
+  }
+
+  #[test]
+  fn test_prompt_names_the_string_by_number() {
+    // Both E strings of standard tuning
+    let question = |string_idx| TheoryQuestion::ScaleDegree {
+      scale: HeptaScaleImpl::new(Note::E, HeptaScaleType::Minor),
+      degree: 1,
+      string_idx: Some(string_idx),
+    };
+    let tuning = [Note::E, Note::B, Note::G, Note::D, Note::A, Note::E];
+    assert_eq!(
+      question(0).prompt(&tuning),
+      "Play the R of E Minor on the 1st string (E)"
+    );
+    assert_eq!(
+      question(5).prompt(&tuning),
+      "Play the R of E Minor on the 6th string (E)"
+    );
+  }
+
+  #[test]
+  fn test_degrees_are_spelled_from_the_formula() {
+    let lydian_fourth = TheoryQuestion::ScaleDegree {
+      scale: HeptaScaleImpl::new(Note::C, HeptaScaleType::Lydian),
+      degree: 4,
+      string_idx: None,
+    };
+    assert_eq!(lydian_fourth.prompt(&[]), "Play the #4 of C Lydian");
+
+    let chord_tone = |quality, interval| TheoryQuestion::ChordTone {
+      chord: Chord::new(Note::C, quality),
+      root: FretCoord {
+        string_idx: 4,
+        fret_idx: 3,
+      },
+      interval,
+    };
+    assert_eq!(
+      chord_tone(ChordQuality::Augmented, Interval::MinorSixth).degree_name(),
+      "#5"
+    );
+    assert_eq!(
+      chord_tone(ChordQuality::Diminished7, Interval::MajorSixth).degree_name(),
+      "bb7"
+    );
+    assert_eq!(
+      chord_tone(ChordQuality::Diminished, Interval::Tritone).degree_name(),
+      "b5"
+    );
+  }
+}
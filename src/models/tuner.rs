@@ -0,0 +1,119 @@
+use crate::audio::tones::{frequency_to_midi, A4_FREQUENCY};
+
+/// Lowest A4 reference the tuner accepts in Hz, a semitone below concert pitch
+pub const MIN_REFERENCE_HZ: f64 = 415.0;
+/// Highest A4 reference the tuner accepts in Hz, a semitone above concert pitch
+pub const MAX_REFERENCE_HZ: f64 = 466.0;
+/// Strings closer than this to their pitch count as tuned
+pub const IN_TUNE_CENTS: f64 = 5.0;
+
+/// Whether a string has to go up or down to its pitch
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TuningStatus {
+  Flat,
+  InTune,
+  Sharp,
+}
+
+impl TuningStatus {
+  pub fn from_cents(cents: f64) -> Self {
+    if cents.abs() < IN_TUNE_CENTS {
+      TuningStatus::InTune
+    } else if cents < 0.0 {
+      TuningStatus::Flat
+    } else {
+      TuningStatus::Sharp
+    }
+  }
+}
+
+impl std::fmt::Display for TuningStatus {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TuningStatus::Flat => write!(f, "Tune up"),
+      TuningStatus::InTune => write!(f, "In tune"),
+      TuningStatus::Sharp => write!(f, "Tune down"),
+    }
+  }
+}
+
+/// The open string a played frequency is closest to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StringTarget {
+  /// Index in the tuning, 0 is the highest string
+  pub string_idx: usize,
+  pub midi: u8,
+  /// Distance of the played frequency to the string, negative when it is flat
+  pub cents: f64,
+}
+
+impl StringTarget {
+  pub fn status(&self) -> TuningStatus {
+    TuningStatus::from_cents(self.cents)
+  }
+}
+
+/// Clamps an A4 reference into the range the tuner accepts, e.g. after typing it in
+pub fn clamp_reference(reference_hz: f64) -> f64 {
+  if reference_hz.is_finite() {
+    reference_hz.clamp(MIN_REFERENCE_HZ, MAX_REFERENCE_HZ)
+  } else {
+    A4_FREQUENCY
+  }
+}
+
+/// The open string closest to `frequency`, with A4 tuned to `reference_hz`. Strings are
+/// given by the MIDI numbers of their open pitch, see `InstrumentProfile::open_string_midis`.
+pub fn nearest_string(
+  frequency: f64,
+  open_midis: &[u8],
+  reference_hz: f64,
+) -> Option<StringTarget> {
+  let played = frequency_to_midi(frequency * A4_FREQUENCY / reference_hz);
+  open_midis
+    .iter()
+    .enumerate()
+    .map(|(string_idx, midi)| StringTarget {
+      string_idx,
+      midi: *midi,
+      cents: (played - f64::from(*midi)) * 100.0,
+    })
+    .min_by(|left, right| left.cents.abs().total_cmp(&right.cents.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::audio::tones::midi_to_frequency;
+
+  #[test]
+  fn test_nearest_string() {
+    // Standard guitar tuning, highest string first
+    let open_midis = [64, 59, 55, 50, 45, 40];
+
+    let target = nearest_string(midi_to_frequency(50), &open_midis, A4_FREQUENCY).unwrap();
+    assert_eq!((target.string_idx, target.midi), (3, 50));
+    assert!(target.cents.abs() < 1e-6);
+    assert_eq!(target.status(), TuningStatus::InTune);
+
+    // A low E a quarter tone flat still goes to the low E
+    let target = nearest_string(midi_to_frequency(40) * 0.986, &open_midis, A4_FREQUENCY).unwrap();
+    assert_eq!(target.string_idx, 5);
+    assert!((target.cents + 24.4).abs() < 0.1);
+    assert_eq!(target.status(), TuningStatus::Flat);
+
+    // 440 Hz is sharp with A4 at 432 Hz
+    let target = nearest_string(440.0, &[69], 432.0).unwrap();
+    assert!((target.cents - 31.8).abs() < 0.1);
+    assert_eq!(target.status(), TuningStatus::Sharp);
+
+    assert_eq!(nearest_string(440.0, &[], A4_FREQUENCY), None);
+  }
+
+  #[test]
+  fn test_clamp_reference() {
+    assert_eq!(clamp_reference(442.0), 442.0);
+    assert_eq!(clamp_reference(100.0), MIN_REFERENCE_HZ);
+    assert_eq!(clamp_reference(f64::NAN), A4_FREQUENCY);
+  }
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+use std::str::FromStr;
+
+use strum_macros::EnumIter;
+
+use super::intervals::Interval;
+use super::notes::{parse_note_prefix, Note, NoteExt};
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, serde::Serialize, serde::Deserialize)]
+pub enum ChordQuality {
+  Major,
+  Minor,
+  Diminished,
+  Augmented,
+  Sus2,
+  Sus4,
+  Dominant7,
+  Major7,
+  Minor7,
+  HalfDiminished7,
+  Diminished7,
+}
+
+impl ChordQuality {
+  pub fn intervals(self) -> &'static [Interval] {
+    use Interval::*;
+    match self {
+      ChordQuality::Major => &[Unison, MajorThird, PerfectFifth],
+      ChordQuality::Minor => &[Unison, MinorThird, PerfectFifth],
+      ChordQuality::Diminished => &[Unison, MinorThird, Tritone],
+      ChordQuality::Augmented => &[Unison, MajorThird, MinorSixth],
+      ChordQuality::Sus2 => &[Unison, MajorSecond, PerfectFifth],
+      ChordQuality::Sus4 => &[Unison, PerfectFourth, PerfectFifth],
+      ChordQuality::Dominant7 => &[Unison, MajorThird, PerfectFifth, MinorSeventh],
+      ChordQuality::Major7 => &[Unison, MajorThird, PerfectFifth, MajorSeventh],
+      ChordQuality::Minor7 => &[Unison, MinorThird, PerfectFifth, MinorSeventh],
+      ChordQuality::HalfDiminished7 => &[Unison, MinorThird, Tritone, MinorSeventh],
+      ChordQuality::Diminished7 => &[Unison, MinorThird, Tritone, MajorSixth],
+    }
+  }
+
+  /// Degrees of the chord formula, in the order of `intervals`, e.g. 1 2 5 for sus2
+  pub fn degrees(self) -> &'static [usize] {
+    match self {
+      ChordQuality::Sus2 => &[1, 2, 5],
+      ChordQuality::Sus4 => &[1, 4, 5],
+      ChordQuality::Major
+      | ChordQuality::Minor
+      | ChordQuality::Diminished
+      | ChordQuality::Augmented => &[1, 3, 5],
+      ChordQuality::Dominant7
+      | ChordQuality::Major7
+      | ChordQuality::Minor7
+      | ChordQuality::HalfDiminished7
+      | ChordQuality::Diminished7 => &[1, 3, 5, 7],
+    }
+  }
+
+  /// Suffix used in chord symbols, e.g. "m7" for a minor seventh chord
+  pub fn symbol(self) -> &'static str {
+    match self {
+      ChordQuality::Major => "",
+      ChordQuality::Minor => "m",
+      ChordQuality::Diminished => "dim",
+      ChordQuality::Augmented => "aug",
+      ChordQuality::Sus2 => "sus2",
+      ChordQuality::Sus4 => "sus4",
+      ChordQuality::Dominant7 => "7",
+      ChordQuality::Major7 => "maj7",
+      ChordQuality::Minor7 => "m7",
+      ChordQuality::HalfDiminished7 => "m7b5",
+      ChordQuality::Diminished7 => "dim7",
+    }
+  }
+
+  fn from_symbol(symbol: &str) -> Option<ChordQuality> {
+    let quality = match symbol {
+      "" | "maj" | "M" => ChordQuality::Major,
+      "m" | "min" | "-" => ChordQuality::Minor,
+      "dim" | "°" => ChordQuality::Diminished,
+      "aug" | "+" => ChordQuality::Augmented,
+      "sus2" => ChordQuality::Sus2,
+      "sus4" | "sus" => ChordQuality::Sus4,
+      "7" => ChordQuality::Dominant7,
+      "maj7" | "M7" | "Δ7" => ChordQuality::Major7,
+      "m7" | "min7" | "-7" => ChordQuality::Minor7,
+      "m7b5" | "ø" | "ø7" => ChordQuality::HalfDiminished7,
+      "dim7" | "°7" => ChordQuality::Diminished7,
+      _ => return None,
+    };
+    Some(quality)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Chord {
+  pub root: Note,
+  pub quality: ChordQuality,
+}
+
+impl Chord {
+  pub fn new(root: Note, quality: ChordQuality) -> Self {
+    Self { root, quality }
+  }
+
+  /// Chord tones starting with the root
+  pub fn notes(&self) -> Vec<Note> {
+    self
+      .quality
+      .intervals()
+      .iter()
+      .map(|interval| interval.of(self.root))
+      .collect()
+  }
+
+  /// The chord moved up by `steps` half-tone steps
+  pub fn transposed(&self, steps: usize) -> Chord {
+    Chord::new(self.root.add_steps(steps), self.quality)
+  }
+
+  pub fn contains_note(&self, note: Note) -> bool {
+    self.notes().contains(&note)
+  }
+
+  /// The interval of `note` above the chord root, if the note is a chord tone
+  pub fn interval_of(&self, note: Note) -> Option<Interval> {
+    self
+      .quality
+      .intervals()
+      .iter()
+      .copied()
+      .find(|interval| interval.of(self.root) == note)
+  }
+}
+
+impl fmt::Display for Chord {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}{}",
+      self.root.to_short_string(),
+      self.quality.symbol()
+    )
+  }
+}
+
+impl FromStr for Chord {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let symbol = s.trim();
+    let (root, rest) =
+      parse_note_prefix(symbol).ok_or_else(|| format!("Unknown chord root: {s}"))?;
+    let quality =
+      ChordQuality::from_symbol(rest).ok_or_else(|| format!("Unknown chord quality: {s}"))?;
+    Ok(Chord::new(root, quality))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_chord_notes() {
+    assert_eq!(
+      Chord::new(Note::C, ChordQuality::Major).notes(),
+      vec![Note::C, Note::E, Note::G]
+    );
+    assert_eq!(
+      Chord::new(Note::A, ChordQuality::Minor7).notes(),
+      vec![Note::A, Note::C, Note::E, Note::G]
+    );
+  }
+
+  #[test]
+  fn test_parse_chord_symbols() {
+    assert_eq!(
+      "Am".parse::<Chord>(),
+      Ok(Chord::new(Note::A, ChordQuality::Minor))
+    );
+    assert_eq!(
+      "F#7".parse::<Chord>(),
+      Ok(Chord::new(Note::FSharpOrGFlat, ChordQuality::Dominant7))
+    );
+    assert_eq!(
+      "Bbmaj7".parse::<Chord>(),
+      Ok(Chord::new(Note::ASharpOrBFlat, ChordQuality::Major7))
+    );
+    assert_eq!(
+      "G".parse::<Chord>(),
+      Ok(Chord::new(Note::G, ChordQuality::Major))
+    );
+    assert!("H".parse::<Chord>().is_err());
+    assert!("Cfoo".parse::<Chord>().is_err());
+  }
+
+  #[test]
+  fn test_transposed() {
+    let c_major = Chord::new(Note::C, ChordQuality::Major);
+    assert_eq!(
+      c_major.transposed(2),
+      Chord::new(Note::D, ChordQuality::Major)
+    );
+    assert_eq!(
+      Chord::new(Note::A, ChordQuality::Minor).transposed(3),
+      Chord::new(Note::C, ChordQuality::Minor)
+    );
+  }
+
+  #[test]
+  fn test_display_round_trip() {
+    for symbol in ["C", "Am", "C♯m7", "Gsus4", "Bdim"] {
+      let chord: Chord = symbol.parse().unwrap();
+      assert_eq!(chord.to_string(), symbol);
+    }
+  }
+}
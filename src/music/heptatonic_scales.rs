@@ -21,21 +21,99 @@ pub enum HeptaScaleDegree {
 pub enum HeptaScaleType {
   Major,
   Minor,
+  Dorian,
+  Phrygian,
+  Lydian,
+  Mixolydian,
+  Locrian,
   // TODO add more scale types, the list below is not complete
   // MelodicMinor,
   // HarmonicMinor,
-  // Dorian,
-  // Phrygian,
-  // Lydian,
-  // Mixolydian,
-  // Locrian,
 }
 
 // Use the ToStr trait from the standard library
 
 impl HeptaScaleType {
   pub fn all_scale_types() -> Vec<HeptaScaleType> {
-    vec![HeptaScaleType::Major, HeptaScaleType::Minor]
+    vec![
+      HeptaScaleType::Major,
+      HeptaScaleType::Minor,
+      HeptaScaleType::Dorian,
+      HeptaScaleType::Phrygian,
+      HeptaScaleType::Lydian,
+      HeptaScaleType::Mixolydian,
+      HeptaScaleType::Locrian,
+    ]
+  }
+
+  /// Intervals of the seven degrees above the root
+  pub fn intervals(self) -> [Interval; 7] {
+    use Interval::*;
+    match self {
+      HeptaScaleType::Major => [
+        Unison,
+        MajorSecond,
+        MajorThird,
+        PerfectFourth,
+        PerfectFifth,
+        MajorSixth,
+        MajorSeventh,
+      ],
+      HeptaScaleType::Minor => [
+        Unison,
+        MajorSecond,
+        MinorThird,
+        PerfectFourth,
+        PerfectFifth,
+        MinorSixth,
+        MinorSeventh,
+      ],
+      HeptaScaleType::Dorian => [
+        Unison,
+        MajorSecond,
+        MinorThird,
+        PerfectFourth,
+        PerfectFifth,
+        MajorSixth,
+        MinorSeventh,
+      ],
+      HeptaScaleType::Phrygian => [
+        Unison,
+        MinorSecond,
+        MinorThird,
+        PerfectFourth,
+        PerfectFifth,
+        MinorSixth,
+        MinorSeventh,
+      ],
+      HeptaScaleType::Lydian => [
+        Unison,
+        MajorSecond,
+        MajorThird,
+        Tritone,
+        PerfectFifth,
+        MajorSixth,
+        MajorSeventh,
+      ],
+      HeptaScaleType::Mixolydian => [
+        Unison,
+        MajorSecond,
+        MajorThird,
+        PerfectFourth,
+        PerfectFifth,
+        MajorSixth,
+        MinorSeventh,
+      ],
+      HeptaScaleType::Locrian => [
+        Unison,
+        MinorSecond,
+        MinorThird,
+        PerfectFourth,
+        Tritone,
+        MinorSixth,
+        MinorSeventh,
+      ],
+    }
   }
 }
 
@@ -44,6 +122,11 @@ impl fmt::Display for HeptaScaleType {
     match self {
       HeptaScaleType::Major => write!(f, "Major"),
       HeptaScaleType::Minor => write!(f, "Minor"),
+      HeptaScaleType::Dorian => write!(f, "Dorian"),
+      HeptaScaleType::Phrygian => write!(f, "Phrygian"),
+      HeptaScaleType::Lydian => write!(f, "Lydian"),
+      HeptaScaleType::Mixolydian => write!(f, "Mixolydian"),
+      HeptaScaleType::Locrian => write!(f, "Locrian"),
     }
   }
 }
@@ -81,37 +164,7 @@ impl HeptaScaleImpl {
   }
 
   fn get_notes(root_note: Note, scale_type: HeptaScaleType) -> [Note; 7] {
-    match scale_type {
-      HeptaScaleType::Major => Self::generate_major_scale(root_note),
-      HeptaScaleType::Minor => Self::generate_minor_scale(root_note),
-    }
-  }
-
-  fn generate_major_scale(root_note: Note) -> [Note; 7] {
-    let intervals = [
-      Interval::Unison,
-      Interval::MajorSecond,
-      Interval::MajorThird,
-      Interval::PerfectFourth,
-      Interval::PerfectFifth,
-      Interval::MajorSixth,
-      Interval::MajorSeventh,
-    ];
-    Self::generate_scale(root_note, intervals)
-  }
-
-  fn generate_minor_scale(root_note: Note) -> [Note; 7] {
-    let intervals = [
-      Interval::Unison,
-      Interval::MajorSecond,
-      Interval::MinorThird,
-      Interval::PerfectFourth,
-      Interval::PerfectFifth,
-      Interval::MinorSixth,
-      Interval::MinorSeventh,
-    ];
-
-    Self::generate_scale(root_note, intervals)
+    Self::generate_scale(root_note, scale_type.intervals())
   }
 
   fn generate_scale(root_note: Note, intervals: [Interval; 7]) -> [Note; 7] {
@@ -131,14 +184,20 @@ impl HeptaScaleImpl {
   pub fn root_note(&self) -> Note {
     self.notes[0]
   }
+
+  /// Scale degree of the note counted from 1, if the note is part of the scale
+  pub fn degree_of(&self, note: Note) -> Option<usize> {
+    self
+      .notes
+      .iter()
+      .position(|&n| n == note)
+      .map(|idx| idx + 1)
+  }
 }
 
 impl fmt::Display for HeptaScaleImpl {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    match self.scale_type {
-      HeptaScaleType::Major => write!(f, "{} Major", self.root_note()),
-      HeptaScaleType::Minor => write!(f, "{} Minor", self.root_note()),
-    }
+    write!(f, "{} {}", self.root_note(), self.scale_type)
   }
 }
 
@@ -175,6 +234,37 @@ mod tests {
     assert_eq!(a_minor[HeptaScaleDegree::Third], Note::C);
   }
 
+  #[test]
+  fn test_degree_of() {
+    let g_major = HeptaScaleImpl::new(Note::G, HeptaScaleType::Major);
+    assert_eq!(g_major.degree_of(Note::G), Some(1));
+    assert_eq!(g_major.degree_of(Note::FSharpOrGFlat), Some(7));
+    assert_eq!(g_major.degree_of(Note::F), None);
+  }
+
+  #[test]
+  fn test_modes() {
+    let g_mixolydian = HeptaScaleImpl::new(Note::G, HeptaScaleType::Mixolydian);
+    assert_eq!(g_mixolydian[HeptaScaleDegree::Seventh], Note::F);
+    assert_eq!(g_mixolydian.to_string(), "G Mixolydian");
+    let f_lydian = HeptaScaleImpl::new(Note::F, HeptaScaleType::Lydian);
+    assert_eq!(f_lydian[HeptaScaleDegree::Fourth], Note::B);
+
+    // The modes of C major share its notes
+    let c_major = HeptaScaleImpl::new(Note::C, HeptaScaleType::Major);
+    for (root, scale_type) in [
+      (Note::D, HeptaScaleType::Dorian),
+      (Note::E, HeptaScaleType::Phrygian),
+      (Note::F, HeptaScaleType::Lydian),
+      (Note::G, HeptaScaleType::Mixolydian),
+      (Note::A, HeptaScaleType::Minor),
+      (Note::B, HeptaScaleType::Locrian),
+    ] {
+      let mode = HeptaScaleImpl::new(root, scale_type);
+      assert!(mode.notes.iter().all(|note| c_major.contains_note(*note)));
+    }
+  }
+
   #[test]
   fn test_a_major_scale() {
     let a_minor = HeptaScaleImpl::new(Note::A, HeptaScaleType::Major);
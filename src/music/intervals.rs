@@ -2,7 +2,9 @@ use crate::music::notes::Note;
 use std::fmt;
 use strum_macros::EnumIter;
 
-#[derive(Clone, Copy, PartialEq, EnumIter, Debug)]
+#[derive(
+  Clone, Copy, PartialEq, Eq, Hash, EnumIter, Debug, serde::Serialize, serde::Deserialize,
+)]
 pub enum Interval {
   Unison,
   MinorSecond,
@@ -72,6 +74,47 @@ impl Interval {
   }
 }
 
+impl Interval {
+  /// Short label relative to a root, e.g. "R", "b3" or "5"
+  pub fn short_name(self) -> &'static str {
+    match self {
+      Interval::Unison => "R",
+      Interval::MinorSecond => "b2",
+      Interval::MajorSecond => "2",
+      Interval::MinorThird => "b3",
+      Interval::MajorThird => "3",
+      Interval::PerfectFourth => "4",
+      Interval::Tritone => "b5",
+      Interval::PerfectFifth => "5",
+      Interval::MinorSixth => "b6",
+      Interval::MajorSixth => "6",
+      Interval::MinorSeventh => "b7",
+      Interval::MajorSeventh => "7",
+      Interval::Octave => "8",
+    }
+  }
+
+  /// Label of the interval spelled as `degree` of a scale or chord formula, e.g. "#4" for a
+  /// tritone in Lydian or "bb7" for the seventh of a diminished seventh chord. Falls back to
+  /// `short_name` if the interval is too far from the natural degree.
+  pub fn degree_name(self, degree: usize) -> String {
+    const NATURAL_STEPS: [isize; 7] = [0, 2, 4, 5, 7, 9, 11];
+    let Some(natural) = degree.checked_sub(1).and_then(|idx| NATURAL_STEPS.get(idx)) else {
+      return self.short_name().to_string();
+    };
+    let accidental = match self.half_tone_steps() as isize - natural {
+      0 if degree == 1 => return "R".to_string(),
+      -2 => "bb",
+      -1 => "b",
+      0 => "",
+      1 => "#",
+      2 => "##",
+      _ => return self.short_name().to_string(),
+    };
+    format!("{accidental}{degree}")
+  }
+}
+
 impl fmt::Display for Interval {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
@@ -113,6 +156,18 @@ mod tests {
     assert_eq!(Interval::Octave.of(Note::C), Note::C);
   }
 
+  #[test]
+  fn test_degree_names() {
+    assert_eq!(Interval::Unison.degree_name(1), "R");
+    assert_eq!(Interval::MinorSeventh.degree_name(7), "b7");
+    assert_eq!(Interval::Tritone.degree_name(4), "#4");
+    assert_eq!(Interval::Tritone.degree_name(5), "b5");
+    assert_eq!(Interval::MinorSixth.degree_name(5), "#5");
+    assert_eq!(Interval::MajorSixth.degree_name(7), "bb7");
+    // Too far from the natural degree
+    assert_eq!(Interval::MajorSeventh.degree_name(2), "7");
+  }
+
   #[test]
   fn test_intervals_from_notes() {
     assert_eq!(
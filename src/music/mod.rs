@@ -1,7 +1,14 @@
+pub mod chords;
 pub mod heptatonic_scales;
 pub mod intervals;
 pub mod notes;
+pub mod progressions;
 pub mod scales;
+pub mod tunings;
+pub mod voicings;
 
+pub use chords::{Chord, ChordQuality};
 pub use notes::{Note, NoteExt};
+pub use progressions::ChordProgression;
 pub use scales::{Scale, ScaleType};
+pub use tunings::Tuning;
@@ -57,7 +57,7 @@ impl Note {
   pub fn add_steps(&self, steps: usize) -> Note {
     let all_notes = Note::all_notes();
     let index = all_notes.iter().position(|&n| n == *self).unwrap();
-    let new_index = (index + steps) % all_notes.len();
+    let new_index = (index + steps % all_notes.len()) % all_notes.len();
     all_notes[new_index]
   }
 }
@@ -85,6 +85,29 @@ impl FromStr for Note {
   }
 }
 
+/// Parses a note name at the start of `s`, e.g. "C", "F#", "Bb" or "E♭".
+/// Returns the note and the remaining part of the string.
+pub(crate) fn parse_note_prefix(s: &str) -> Option<(Note, &str)> {
+  let mut chars = s.chars();
+  let natural = match chars.next()?.to_ascii_uppercase() {
+    'C' => Note::C,
+    'D' => Note::D,
+    'E' => Note::E,
+    'F' => Note::F,
+    'G' => Note::G,
+    'A' => Note::A,
+    'B' => Note::B,
+    _ => return None,
+  };
+  let rest = chars.as_str();
+  let mut accidental = rest.chars();
+  match accidental.next() {
+    Some('#') | Some('♯') => Some((natural.add_steps(1), accidental.as_str())),
+    Some('b') | Some('♭') => Some((natural.add_steps(11), accidental.as_str())),
+    _ => Some((natural, rest)),
+  }
+}
+
 pub trait NoteExt {
   /// Returns a short string representation of the note, e.g., "C", "D♯/E♭".
   fn to_short_string(&self) -> String;
@@ -0,0 +1,151 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::chords::Chord;
+
+pub const DEFAULT_BEATS_PER_CHORD: u32 = 4;
+
+/// A chord that is held for a number of beats
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProgressionStep {
+  pub chord: Chord,
+  pub beats: u32,
+}
+
+/// A looping sequence of chords with durations in beats
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChordProgression {
+  pub steps: Vec<ProgressionStep>,
+}
+
+impl ChordProgression {
+  pub fn new(steps: Vec<ProgressionStep>) -> Self {
+    Self { steps }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.steps.is_empty()
+  }
+
+  pub fn total_beats(&self) -> u64 {
+    self.steps.iter().map(|step| step.beats as u64).sum()
+  }
+
+  /// Index of the step that is current at `beat` (counted from 0), looping the progression
+  pub fn step_index_at_beat(&self, beat: u64) -> Option<usize> {
+    let total = self.total_beats();
+    if total == 0 {
+      return None;
+    }
+    let mut remaining = beat % total;
+    for (idx, step) in self.steps.iter().enumerate() {
+      if remaining < step.beats as u64 {
+        return Some(idx);
+      }
+      remaining -= step.beats as u64;
+    }
+    None
+  }
+
+  /// Beat within the current step (counted from 0)
+  pub fn beat_within_step(&self, beat: u64) -> Option<u32> {
+    let total = self.total_beats();
+    if total == 0 {
+      return None;
+    }
+    let mut remaining = beat % total;
+    for step in &self.steps {
+      if remaining < step.beats as u64 {
+        return Some(remaining as u32);
+      }
+      remaining -= step.beats as u64;
+    }
+    None
+  }
+
+  /// Index of the step following `idx`, wrapping around at the end
+  pub fn next_step_index(&self, idx: usize) -> Option<usize> {
+    if self.steps.is_empty() {
+      None
+    } else {
+      Some((idx + 1) % self.steps.len())
+    }
+  }
+}
+
+impl fmt::Display for ChordProgression {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let steps = self
+      .steps
+      .iter()
+      .map(|step| format!("{}:{}", step.chord, step.beats))
+      .collect::<Vec<_>>();
+    write!(f, "{}", steps.join(" "))
+  }
+}
+
+/// Parses whitespace separated chords with optional beat counts, e.g. "Am:4 F:4 C:2 G:2".
+/// Chords without a beat count are held for [`DEFAULT_BEATS_PER_CHORD`] beats.
+impl FromStr for ChordProgression {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let steps = s
+      .split_whitespace()
+      .map(|token| {
+        let (chord, beats) = match token.split_once(':') {
+          Some((chord, beats)) => {
+            let beats = beats
+              .parse::<u32>()
+              .map_err(|_| format!("Invalid beat count in '{token}'"))?;
+            (chord, beats)
+          }
+          None => (token, DEFAULT_BEATS_PER_CHORD),
+        };
+        if beats == 0 {
+          return Err(format!("Beat count must be greater than 0 in '{token}'"));
+        }
+        Ok(ProgressionStep {
+          chord: chord.parse()?,
+          beats,
+        })
+      })
+      .collect::<Result<Vec<_>, String>>()?;
+
+    if steps.is_empty() {
+      return Err("A chord progression needs at least one chord".to_string());
+    }
+    Ok(ChordProgression::new(steps))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_progression() {
+    let progression: ChordProgression = "Am:4 F C:2 G:2".parse().unwrap();
+    assert_eq!(progression.steps.len(), 4);
+    assert_eq!(progression.steps[1].beats, DEFAULT_BEATS_PER_CHORD);
+    assert_eq!(progression.total_beats(), 12);
+    assert_eq!(progression.to_string(), "Am:4 F:4 C:2 G:2");
+
+    assert!("".parse::<ChordProgression>().is_err());
+    assert!("Am:0".parse::<ChordProgression>().is_err());
+    assert!("Am:x".parse::<ChordProgression>().is_err());
+  }
+
+  #[test]
+  fn test_step_at_beat() {
+    let progression: ChordProgression = "Am:4 F:2 C:2".parse().unwrap();
+    assert_eq!(progression.step_index_at_beat(0), Some(0));
+    assert_eq!(progression.step_index_at_beat(3), Some(0));
+    assert_eq!(progression.step_index_at_beat(4), Some(1));
+    assert_eq!(progression.step_index_at_beat(6), Some(2));
+    assert_eq!(progression.step_index_at_beat(8), Some(0));
+    assert_eq!(progression.beat_within_step(5), Some(1));
+    assert_eq!(progression.next_step_index(2), Some(0));
+    assert_eq!(ChordProgression::default().step_index_at_beat(0), None);
+  }
+}
@@ -26,12 +26,10 @@ impl FromStr for ScaleType {
   type Err = String;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    match s {
-      "Major" => Ok(ScaleType::Hepatonic(HeptaScaleType::Major)),
-      "Minor" => Ok(ScaleType::Hepatonic(HeptaScaleType::Minor)),
-      "Chromatic" => Ok(ScaleType::Chromatic),
-      _ => Err(format!("Unknown scale type: {s}")),
-    }
+    ScaleType::all_scale_types()
+      .into_iter()
+      .find(|scale_type| scale_type.to_string() == s)
+      .ok_or_else(|| format!("Unknown scale type: {s}"))
   }
 }
 
@@ -101,6 +99,14 @@ impl Scale {
       Scale::Chromatic => ScaleType::Chromatic,
     }
   }
+
+  /// Scale degree of the note counted from 1, chromatic scales have no degrees
+  pub fn degree_of(&self, note: Note) -> Option<usize> {
+    match self {
+      Scale::Heptatonic(scale) => scale.degree_of(note),
+      Scale::Chromatic => None,
+    }
+  }
 }
 
 impl ScaleExt for Scale {
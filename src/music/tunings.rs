@@ -0,0 +1,213 @@
+use std::fmt;
+
+use super::notes::{parse_note_prefix, Note, NoteExt};
+
+/// Open string notes of a fretted instrument.
+///
+/// `notes` is ordered like the fretboard strings: the first note is the highest string.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Tuning {
+  pub name: String,
+  pub notes: Vec<Note>,
+}
+
+impl Default for Tuning {
+  fn default() -> Self {
+    Tuning::standard()
+  }
+}
+
+impl Tuning {
+  pub fn new(name: impl Into<String>, notes: Vec<Note>) -> Self {
+    Self {
+      name: name.into(),
+      notes,
+    }
+  }
+
+  /// Creates a tuning from notes written from the lowest to the highest string,
+  /// the way tunings are usually written down
+  pub fn from_low_to_high(name: impl Into<String>, notes: &[Note]) -> Self {
+    Self::new(name, notes.iter().rev().copied().collect())
+  }
+
+  pub fn standard() -> Self {
+    use Note::*;
+    Self::from_low_to_high("Standard", &[E, A, D, G, B, E])
+  }
+
+  /// Built-in tunings of all instruments
+  pub fn library() -> Vec<Tuning> {
+    [
+      Tuning::guitar_library(),
+      Tuning::bass_library(),
+      Tuning::ukulele_library(),
+      Tuning::mandolin_library(),
+      Tuning::banjo_library(),
+    ]
+    .concat()
+  }
+
+  pub fn guitar_library() -> Vec<Tuning> {
+    use Note::*;
+    let (c_sharp, d_sharp, f_sharp, g_sharp, a_sharp) = (
+      CSharpOrDFlat,
+      DSharpOrEFlat,
+      FSharpOrGFlat,
+      GSharpOrAFlat,
+      ASharpOrBFlat,
+    );
+    vec![
+      Tuning::standard(),
+      Tuning::from_low_to_high("Drop D", &[D, A, D, G, B, E]),
+      Tuning::from_low_to_high(
+        "Half-Step Down",
+        &[d_sharp, g_sharp, c_sharp, f_sharp, a_sharp, d_sharp],
+      ),
+      Tuning::from_low_to_high("Whole-Step Down", &[D, G, C, F, A, D]),
+      Tuning::from_low_to_high("Drop C", &[C, G, C, F, A, D]),
+      Tuning::from_low_to_high("DADGAD", &[D, A, D, G, A, D]),
+      Tuning::from_low_to_high("Open G", &[D, G, D, G, B, D]),
+      Tuning::from_low_to_high("Open D", &[D, A, D, f_sharp, A, D]),
+      Tuning::from_low_to_high("Open E", &[E, B, E, g_sharp, B, E]),
+      Tuning::from_low_to_high("Open C", &[C, G, C, G, C, E]),
+      Tuning::from_low_to_high("7-String B Standard", &[B, E, A, D, G, B, E]),
+      Tuning::from_low_to_high("8-String F♯ Standard", &[f_sharp, B, E, A, D, G, B, E]),
+    ]
+  }
+
+  pub fn bass_library() -> Vec<Tuning> {
+    use Note::*;
+    vec![
+      Tuning::from_low_to_high("Bass EADG", &[E, A, D, G]),
+      Tuning::from_low_to_high("Bass Drop D", &[D, A, D, G]),
+      Tuning::from_low_to_high("5-String Bass BEADG", &[B, E, A, D, G]),
+      Tuning::from_low_to_high("6-String Bass BEADGC", &[B, E, A, D, G, C]),
+    ]
+  }
+
+  /// Ukulele tunings are re-entrant, the 4th string is usually tuned above the 3rd
+  pub fn ukulele_library() -> Vec<Tuning> {
+    use Note::*;
+    vec![
+      Tuning::from_low_to_high("Ukulele GCEA", &[G, C, E, A]),
+      Tuning::from_low_to_high("Ukulele D ADF♯B", &[A, D, FSharpOrGFlat, B]),
+      Tuning::from_low_to_high("Baritone Ukulele DGBE", &[D, G, B, E]),
+    ]
+  }
+
+  /// Tunings of the four courses, both strings of a course share the note
+  pub fn mandolin_library() -> Vec<Tuning> {
+    use Note::*;
+    vec![
+      Tuning::from_low_to_high("Mandolin GDAE", &[G, D, A, E]),
+      Tuning::from_low_to_high("Mandolin Cross AEAE", &[A, E, A, E]),
+    ]
+  }
+
+  /// 5-string banjo tunings, the first note is the short fifth string
+  pub fn banjo_library() -> Vec<Tuning> {
+    use Note::*;
+    vec![
+      Tuning::from_low_to_high("Banjo Open G", &[G, D, G, B, D]),
+      Tuning::from_low_to_high("Banjo Double C", &[G, C, G, C, D]),
+      Tuning::from_low_to_high("Banjo Drop C", &[G, C, G, B, D]),
+      Tuning::from_low_to_high("Banjo Open D", &[FSharpOrGFlat, D, FSharpOrGFlat, A, D]),
+    ]
+  }
+
+  /// Finds a built-in tuning by name
+  pub fn from_library(name: &str) -> Option<Tuning> {
+    Tuning::library()
+      .into_iter()
+      .find(|tuning| tuning.name == name)
+  }
+
+  pub fn num_strings(&self) -> usize {
+    self.notes.len()
+  }
+
+  /// Parses whitespace separated notes written from the lowest to the highest string,
+  /// e.g. "D A D G B E"
+  pub fn parse_notes(s: &str) -> Result<Vec<Note>, String> {
+    let notes = s
+      .split_whitespace()
+      .map(|token| match parse_note_prefix(token) {
+        Some((note, "")) => Ok(note),
+        _ => Err(format!("Unknown note: {token}")),
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    if notes.len() < 2 {
+      return Err("A tuning needs at least two strings".to_string());
+    }
+    Ok(notes.into_iter().rev().collect())
+  }
+
+  /// The notes from the lowest to the highest string, e.g. "D A D G B E"
+  pub fn notes_string(&self) -> String {
+    self
+      .notes
+      .iter()
+      .rev()
+      .map(|note| note.to_short_string())
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+}
+
+impl fmt::Display for Tuning {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} ({})", self.name, self.notes_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_standard_tuning_starts_with_highest_string() {
+    let tuning = Tuning::standard();
+    assert_eq!(
+      tuning.notes,
+      vec![Note::E, Note::B, Note::G, Note::D, Note::A, Note::E]
+    );
+    assert_eq!(tuning.notes_string(), "E A D G B E");
+  }
+
+  #[test]
+  fn test_library_tunings() {
+    let library = Tuning::library();
+    assert!(library.iter().all(|tuning| tuning.num_strings() >= 4));
+    assert_eq!(Tuning::from_library("Drop D").unwrap().notes[5], Note::D);
+    assert_eq!(
+      Tuning::from_library("8-String F♯ Standard")
+        .unwrap()
+        .num_strings(),
+      8
+    );
+    // Tunings are looked up by name
+    let mut names: Vec<_> = library.iter().map(|tuning| tuning.name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+    assert_eq!(names.len(), library.len());
+    assert_eq!(
+      Tuning::from_library("Banjo Open G").unwrap().notes_string(),
+      "G D G B D"
+    );
+  }
+
+  #[test]
+  fn test_parse_notes() {
+    assert_eq!(
+      Tuning::parse_notes("D A D G B E"),
+      Ok(Tuning::from_library("Drop D").unwrap().notes)
+    );
+    assert_eq!(
+      Tuning::parse_notes("Eb Ab Db Gb Bb Eb"),
+      Ok(Tuning::from_library("Half-Step Down").unwrap().notes)
+    );
+    assert!(Tuning::parse_notes("E").is_err());
+    assert!(Tuning::parse_notes("E A X").is_err());
+  }
+}
@@ -0,0 +1,166 @@
+use super::chords::Chord;
+use super::intervals::Interval;
+use super::notes::Note;
+
+/// Number of frets above the lowest fret of a shape that can be reached without shifting
+const SHAPE_SPAN: usize = 3;
+/// Highest fret a voicing search starts at
+pub const MAX_SEARCH_POSITION: usize = 12;
+
+/// A chord shape on a fretted instrument.
+///
+/// `frets` is indexed like the tuning (first index is the highest string),
+/// `None` marks a muted string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChordVoicing {
+  pub frets: Vec<Option<usize>>,
+}
+
+impl ChordVoicing {
+  /// Finds the lowest playable voicing of `chord` with the root in the bass
+  pub fn find(chord: &Chord, tuning: &[Note]) -> Option<ChordVoicing> {
+    (0..=MAX_SEARCH_POSITION).find_map(|position| Self::find_at_position(chord, tuning, position))
+  }
+
+  /// Finds a voicing whose fretted notes lie within `position..=position + SHAPE_SPAN`.
+  /// Open strings are only used at position 0.
+  pub fn find_at_position(chord: &Chord, tuning: &[Note], position: usize) -> Option<ChordVoicing> {
+    let num_strings = tuning.len();
+    let min_played_strings = num_strings.min(4);
+    let window = |string_note: Note| {
+      let lowest_fret = position.max(1);
+      let open = (position == 0).then_some(0);
+      open
+        .into_iter()
+        .chain(lowest_fret..=position.saturating_add(SHAPE_SPAN))
+        .filter(move |&fret| chord.contains_note(string_note.add_steps(fret)))
+    };
+
+    let (bass_idx, bass_fret) = (0..num_strings)
+      .rev()
+      .take(num_strings + 1 - min_played_strings)
+      .find_map(|string_idx| {
+        window(tuning[string_idx])
+          .find(|&fret| tuning[string_idx].add_steps(fret) == chord.root)
+          .map(|fret| (string_idx, fret))
+      })?;
+
+    let mut frets = vec![None; num_strings];
+    frets[bass_idx] = Some(bass_fret);
+    let mut covered = vec![chord.root];
+
+    for string_idx in (0..bass_idx).rev() {
+      let string_note = tuning[string_idx];
+      let fret = window(string_note)
+        .find(|&fret| !covered.contains(&string_note.add_steps(fret)))
+        .or_else(|| window(string_note).next());
+      if let Some(fret) = fret {
+        covered.push(string_note.add_steps(fret));
+        frets[string_idx] = Some(fret);
+      }
+    }
+
+    let required_notes_covered = chord
+      .notes()
+      .iter()
+      .filter(|&&note| chord.interval_of(note) != Some(Interval::PerfectFifth))
+      .all(|note| covered.contains(note));
+
+    required_notes_covered.then_some(ChordVoicing { frets })
+  }
+
+  /// The same shape played with a capo on fret `capo`, open strings become the capo fret
+  pub fn with_capo(&self, capo: usize) -> ChordVoicing {
+    ChordVoicing {
+      frets: self
+        .frets
+        .iter()
+        .map(|fret| fret.map(|fret| fret + capo))
+        .collect(),
+    }
+  }
+
+  /// Played positions as `(string_idx, fret)` pairs
+  pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+    self
+      .frets
+      .iter()
+      .enumerate()
+      .filter_map(|(string_idx, fret)| fret.map(|fret| (string_idx, fret)))
+  }
+
+  pub fn max_fret(&self) -> usize {
+    self.positions().map(|(_, fret)| fret).max().unwrap_or(0)
+  }
+
+  pub fn min_fret(&self) -> usize {
+    self.positions().map(|(_, fret)| fret).min().unwrap_or(0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::music::chords::ChordQuality;
+
+  fn standard_tuning() -> Vec<Note> {
+    vec![Note::E, Note::B, Note::G, Note::D, Note::A, Note::E]
+  }
+
+  /// Formats a voicing in the usual low-to-high notation, e.g. "x32010"
+  fn shape(voicing: &ChordVoicing) -> String {
+    voicing
+      .frets
+      .iter()
+      .rev()
+      .map(|fret| fret.map_or("x".to_string(), |f| f.to_string()))
+      .collect()
+  }
+
+  #[test]
+  fn test_open_chords_in_standard_tuning() {
+    let tuning = standard_tuning();
+    let find =
+      |root, quality| shape(&ChordVoicing::find(&Chord::new(root, quality), &tuning).unwrap());
+
+    assert_eq!(find(Note::C, ChordQuality::Major), "x32010");
+    assert_eq!(find(Note::G, ChordQuality::Major), "320003");
+    assert_eq!(find(Note::A, ChordQuality::Minor), "x02210");
+    assert_eq!(find(Note::D, ChordQuality::Major), "xx0232");
+    assert_eq!(find(Note::E, ChordQuality::Major), "022100");
+  }
+
+  #[test]
+  fn test_search_far_up_the_neck_does_not_overflow() {
+    let chord = Chord::new(Note::C, ChordQuality::Major);
+    assert_eq!(
+      ChordVoicing::find_at_position(&chord, &standard_tuning(), usize::MAX),
+      None
+    );
+  }
+
+  #[test]
+  fn test_voicing_with_capo() {
+    let tuning = standard_tuning();
+    let c_shape = ChordVoicing::find(&Chord::new(Note::C, ChordQuality::Major), &tuning).unwrap();
+    let with_capo = c_shape.with_capo(2);
+    assert_eq!(shape(&with_capo), "x54232");
+
+    // A C shape with capo 2 sounds like a D major chord
+    let d_major = Chord::new(Note::D, ChordQuality::Major);
+    for (string_idx, fret) in with_capo.positions() {
+      assert!(d_major.contains_note(tuning[string_idx].add_steps(fret)));
+    }
+  }
+
+  #[test]
+  fn test_voicing_contains_only_chord_tones() {
+    let tuning = standard_tuning();
+    let chord = Chord::new(Note::FSharpOrGFlat, ChordQuality::Minor7);
+    let voicing = ChordVoicing::find(&chord, &tuning).unwrap();
+    for (string_idx, fret) in voicing.positions() {
+      assert!(chord.contains_note(tuning[string_idx].add_steps(fret)));
+    }
+    assert!(voicing.max_fret() - voicing.min_fret().max(1) <= SHAPE_SPAN);
+  }
+}
@@ -1,4 +1,4 @@
-use crate::music::Note;
+use crate::music::Tuning;
 /// Interactive fretboard configuration playground
 ///
 /// This component provides a single fretboard with all configuration parameters
@@ -8,9 +8,13 @@ use leptos::prelude::*;
 
 use crate::components::fretboard::FretboardModelAdapter;
 use crate::components::fretboard::{
-  definitions::MAX_FRETS, definitions::MAX_STRINGS, FretboardVisualConfigBuilder,
+  definitions::MAX_FRETS, FretboardOrientation, FretboardOrientationControls,
+  FretboardOrientationPreference, FretboardTheme, FretboardThemeControls, FretboardThemePreference,
+  FretboardVisualConfigBuilder,
 };
+use crate::components::tuning_selector::TuningSelector;
 use crate::models::fretboard::model_builder::FretboardModelBuilder;
+use crate::models::fretboard::{on_instrument_switch, Instrument, InstrumentPreference};
 
 #[component]
 pub fn FretboardConfigExamples() -> impl IntoView {
@@ -18,18 +22,42 @@ pub fn FretboardConfigExamples() -> impl IntoView {
   let start_fret = RwSignal::new(3_usize);
   let end_fret = RwSignal::new(7_usize);
 
+  // Edits the app wide instrument like the orientation and theme below
+  let instrument = use_context::<InstrumentPreference>()
+    .map(|InstrumentPreference(instrument)| instrument)
+    .unwrap_or_else(|| RwSignal::new(Instrument::default()));
+
   // Configuration controls
-  let num_strings = RwSignal::new(6_u8);
-  let tuning = Signal::derive(move || (0..=num_strings.get()).map(|_| Note::E).collect::<Vec<_>>());
+  let tuning = RwSignal::new(instrument.get_untracked().profile().default_tuning());
+  // Presets pick a tuning of the new instrument themselves
+  on_instrument_switch(move |profile| {
+    if !profile.tunings.contains(&tuning.get_untracked()) {
+      tuning.set(profile.default_tuning());
+    }
+  });
+  let num_strings = Memo::new(move |_| tuning.with(Tuning::num_strings));
   let svg_aspect_ratio = RwSignal::new(3.0_f64);
   let fret_margin_percentage = RwSignal::new(0.05_f64);
   let nut_width = RwSignal::new(14.0_f64);
   let extra_frets = RwSignal::new(1_usize);
+  let fanned_frets = RwSignal::new(false);
+  let multiscale = Signal::derive(move || {
+    fanned_frets
+      .get()
+      .then(|| instrument.get().profile().multiscale())
+  });
   let marker_preset = RwSignal::new("standard".to_string());
+  // Edits the app wide preferences, so the chosen orientation and theme are used by all fretboards
+  let orientation = use_context::<FretboardOrientationPreference>()
+    .map(|FretboardOrientationPreference(orientation)| orientation)
+    .unwrap_or_else(|| RwSignal::new(FretboardOrientation::default()));
+  let theme = use_context::<FretboardThemePreference>()
+    .map(|FretboardThemePreference(theme)| theme)
+    .unwrap_or_else(|| RwSignal::new(FretboardTheme::default()));
 
   // Convert marker preset to actual marker positions
   let marker_positions = Memo::new(move |_| match marker_preset.get().as_str() {
-    "standard" => vec![3, 5, 7, 9, 12, 15, 17, 19, 21, 24],
+    "standard" => instrument.get().profile().marker_positions,
     "octaves" => vec![12, 24],
     "pentatonic" => vec![3, 5, 7, 12, 15, 17, 24],
     "none" => vec![],
@@ -40,17 +68,26 @@ pub fn FretboardConfigExamples() -> impl IntoView {
     FretboardModelBuilder::new()
       .start_fret(start_fret.into())
       .end_fret(end_fret.into())
-      .tuning(tuning)
+      .tuning(Signal::derive(move || {
+        tuning.with(|tuning| tuning.notes.clone())
+      }))
+      .drone(Signal::derive(move || {
+        tuning.with(|tuning| instrument.get().profile().drone_for(&tuning.notes))
+      }))
       .config(Signal::derive(move || {
         FretboardVisualConfigBuilder::new()
           .svg_aspect_ratio(svg_aspect_ratio.into())
           .fret_margin_percentage(fret_margin_percentage.into())
           .nut_width(nut_width.into())
           .extra_frets(extra_frets.into())
+          .multiscale(multiscale)
           .marker_positions(marker_positions.into())
+          .orientation(orientation.into())
+          .theme(theme.into())
           .build()
       }))
-      .build(),
+      .build()
+      .expect("The initial configuration is valid"),
   );
 
   view! {
@@ -64,7 +101,8 @@ pub fn FretboardConfigExamples() -> impl IntoView {
           <button
             class="py-2 px-4 text-white bg-green-500 rounded hover:bg-green-600"
             on:click=move |_| {
-              num_strings.set(6);
+              instrument.set(Instrument::Guitar);
+              tuning.set(Tuning::standard());
               svg_aspect_ratio.set(3.0);
               marker_preset.set("standard".to_string());
             }
@@ -74,7 +112,7 @@ pub fn FretboardConfigExamples() -> impl IntoView {
           <button
             class="py-2 px-4 text-white bg-green-500 rounded hover:bg-green-600"
             on:click=move |_| {
-              num_strings.set(4);
+              instrument.set(Instrument::Bass);
               svg_aspect_ratio.set(4.0);
               marker_preset.set("standard".to_string());
             }
@@ -84,7 +122,28 @@ pub fn FretboardConfigExamples() -> impl IntoView {
           <button
             class="py-2 px-4 text-white bg-green-500 rounded hover:bg-green-600"
             on:click=move |_| {
-              num_strings.set(7);
+              instrument.set(Instrument::Ukulele);
+              svg_aspect_ratio.set(3.5);
+              marker_preset.set("standard".to_string());
+            }
+          >
+            "🌺 Ukulele"
+          </button>
+          <button
+            class="py-2 px-4 text-white bg-green-500 rounded hover:bg-green-600"
+            on:click=move |_| {
+              instrument.set(Instrument::Banjo);
+              svg_aspect_ratio.set(3.0);
+              marker_preset.set("standard".to_string());
+            }
+          >
+            "🪕 5-String Banjo"
+          </button>
+          <button
+            class="py-2 px-4 text-white bg-green-500 rounded hover:bg-green-600"
+            on:click=move |_| {
+              instrument.set(Instrument::Guitar);
+              tuning.set(Tuning::from_library("7-String B Standard").unwrap_or_default());
               svg_aspect_ratio.set(2.8);
               extra_frets.set(2);
               marker_preset.set("standard".to_string());
@@ -159,7 +218,7 @@ pub fn FretboardConfigExamples() -> impl IntoView {
                   <input
                     type="range"
                     min=move || start_fret.get() + 1
-                    max=MAX_FRETS
+                    max=MAX_FRETS - 1
                     class="w-full"
                     prop:value=end_fret
                     on:input=move |ev| {
@@ -178,21 +237,10 @@ pub fn FretboardConfigExamples() -> impl IntoView {
             <div class="p-1 rounded-lg border">
               <h3 class="mb-3 text-base font-semibold">"🎸 Instrument"</h3>
               <div class="grid grid-cols-2 gap-3">
-                <div>
-                  <label class="block mb-1 text-xs font-medium">
-                    "Strings: " <span class="font-bold">{num_strings}</span>
-                  </label>
-                  <input
-                    type="range"
-                    min="2"
-                    max=MAX_STRINGS
-                    class="w-full"
-                    prop:value=num_strings
-                    on:input=move |ev| {
-                      if let Ok(val) = event_target_value(&ev).parse::<u8>() {
-                        num_strings.set(val);
-                      }
-                    }
+                <div class="col-span-2">
+                  <TuningSelector
+                    value=tuning
+                    on_tuning_change=Callback::new(move |new_tuning| tuning.set(new_tuning))
                   />
                   <div class="mt-1 text-xs text-gray-600">
                     {move || match num_strings.get() {
@@ -207,6 +255,24 @@ pub fn FretboardConfigExamples() -> impl IntoView {
               </div>
             </div>
 
+            // Orientation
+            <div class="p-1 rounded-lg border">
+              <h3 class="mb-3 text-base font-semibold">"🔄 Orientation"</h3>
+              <FretboardOrientationControls
+                value=orientation
+                on_change=Callback::new(move |new_orientation| orientation.set(new_orientation))
+              />
+            </div>
+
+            // Colours
+            <div class="p-1 rounded-lg border">
+              <h3 class="mb-3 text-base font-semibold">"🎨 Theme"</h3>
+              <FretboardThemeControls
+                value=theme
+                on_change=Callback::new(move |new_theme| theme.set(new_theme))
+              />
+            </div>
+
             // Visual Layout & Fine Tuning - combined compact layout
             <div class="p-1 rounded-lg border">
               <h3 class="mb-3 text-base font-semibold">"📐 Layout & Tuning"</h3>
@@ -301,6 +367,16 @@ pub fn FretboardConfigExamples() -> impl IntoView {
                     }
                   />
                 </div>
+                <div>
+                  <label class="flex gap-2 items-center text-xs font-medium">
+                    <input
+                      type="checkbox"
+                      prop:checked=fanned_frets
+                      on:change=move |ev| fanned_frets.set(event_target_checked(&ev))
+                    />
+                    "Fanned Frets (27\" to 25.5\")"
+                  </label>
+                </div>
               </div>
             </div>
 
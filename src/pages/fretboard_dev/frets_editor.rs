@@ -1,13 +1,15 @@
 use leptos::prelude::*;
+use strum::IntoEnumIterator;
 
-use super::helper::get_fret_positions;
-use crate::components::fretboard::{FretStateColor, FretStateSignals};
+use super::helper::{get_fret_positions, NoteShape};
+use crate::components::fretboard::{FretRole, FretStateGrid};
 
 #[component]
 pub(super) fn FretsEditor(
-  frets: RwSignal<FretStateSignals>,
+  frets: RwSignal<FretStateGrid>,
   #[prop(into)] label: RwSignal<String>,
-  #[prop(into)] color: RwSignal<FretStateColor>,
+  #[prop(into)] role: RwSignal<FretRole>,
+  #[prop(into)] shape: RwSignal<NoteShape>,
   #[prop(into)] hidden: RwSignal<bool>,
 ) -> impl IntoView {
   let reset_sample = move |_| frets.set(get_fret_positions());
@@ -26,26 +28,47 @@ pub(super) fn FretsEditor(
           />
         </label>
         <label class="flex flex-col text-sm">
-          <span>"Color"</span>
+          <span>"Role"</span>
           <select
             class="p-1 rounded border"
             on:change=move |ev| {
-              match event_target_value(&ev).as_str() {
-                "Red" => color.set(FretStateColor::Red),
-                "Blue" => color.set(FretStateColor::Blue),
-                _ => color.set(FretStateColor::Green),
+              let value = event_target_value(&ev);
+              if let Some(new_role) = FretRole::iter().find(|r| r.to_string() == value) {
+                role.set(new_role);
               }
             }
           >
-            <option value="Green" selected=move || color.get() == FretStateColor::Green>
-              "Green"
-            </option>
-            <option value="Red" selected=move || color.get() == FretStateColor::Red>
-              "Red"
-            </option>
-            <option value="Blue" selected=move || color.get() == FretStateColor::Blue>
-              "Blue"
-            </option>
+            {FretRole::iter()
+              .map(|r| {
+                view! {
+                  <option value=r.to_string() selected=move || role.get() == r>
+                    {r.to_string()}
+                  </option>
+                }
+              })
+              .collect_view()}
+          </select>
+        </label>
+        <label class="flex flex-col text-sm">
+          <span>"Shape"</span>
+          <select
+            class="p-1 rounded border"
+            on:change=move |ev| {
+              let value = event_target_value(&ev);
+              if let Some(new_shape) = NoteShape::iter().find(|s| s.to_string() == value) {
+                shape.set(new_shape);
+              }
+            }
+          >
+            {NoteShape::iter()
+              .map(|s| {
+                view! {
+                  <option value=s.to_string() selected=move || shape.get() == s>
+                    {s.to_string()}
+                  </option>
+                }
+              })
+              .collect_view()}
           </select>
         </label>
         <label class="flex gap-2 items-center mt-4 text-sm">
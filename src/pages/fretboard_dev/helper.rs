@@ -1,56 +1,99 @@
-use leptos::prelude::*;
+use strum_macros::{Display, EnumIter};
 
-use crate::components::fretboard::base::get_preallocated_fret_states;
-use crate::components::fretboard::{FretCoord, FretState, FretStateColor, FretStateSignals};
+use crate::components::fretboard::{FretCoord, FretRole, FretState, FretStateGrid};
 
-pub(super) fn get_fret_positions() -> FretStateSignals {
+/// Shapes offered by the fret editor
+#[derive(Clone, Copy, PartialEq, Eq, Debug, EnumIter, Display)]
+pub enum NoteShape {
+  Filled,
+  Hollow,
+  Square,
+  Diamond,
+  Ghost,
+  Muted,
+}
+
+impl NoteShape {
+  pub fn to_state(self, role: FretRole, label: String) -> FretState {
+    match self {
+      NoteShape::Filled => FretState::Normal(role, label),
+      NoteShape::Hollow => FretState::Hollow(role, label),
+      NoteShape::Square => FretState::Square(role, label),
+      NoteShape::Diamond => FretState::Diamond(role, label),
+      NoteShape::Ghost => FretState::Ghost(role, label),
+      NoteShape::Muted => FretState::Muted,
+    }
+  }
+}
+
+pub(super) fn get_fret_positions() -> FretStateGrid {
   // Build a sample set of fret states to visualize different cases
-  let mut fret_positions: FretStateSignals = get_preallocated_fret_states();
+  let fret_positions = FretStateGrid::new();
 
   // Normal notes across several strings/frets
   for (s, f) in [(0, 5), (1, 3), (2, 7), (3, 2)] {
-    fret_positions.insert(
+    fret_positions.set(
       FretCoord {
         string_idx: s,
         fret_idx: f,
       },
-      RwSignal::new(FretState::Normal(FretStateColor::Green, format!("{f}-{s}"))),
+      FretState::Normal(FretRole::Root, format!("{f}-{s}")),
     );
   }
 
   // Colored examples
-  fret_positions.insert(
+  fret_positions.set(
     FretCoord {
       string_idx: 4,
       fret_idx: 8,
     },
-    RwSignal::new(FretState::Normal(FretStateColor::Blue, "foo".into())),
+    FretState::Normal(FretRole::ScaleTone, "foo".into()),
   );
-  fret_positions.insert(
+  fret_positions.set(
     FretCoord {
       string_idx: 5,
       fret_idx: 0,
     },
-    RwSignal::new(FretState::Normal(FretStateColor::Red, "foo".into())),
+    FretState::Normal(FretRole::Wrong, "foo".into()),
   );
-  fret_positions.insert(
+  fret_positions.set(
     FretCoord {
       string_idx: 5,
       fret_idx: 4,
     },
-    RwSignal::new(FretState::Normal(
-      FretStateColor::Red,
-      "loooooooong text".into(),
-    )),
+    FretState::Normal(FretRole::Wrong, "loooooooong text".into()),
+  );
+
+  // Shapes
+  for (f, state) in [
+    (10, FretState::Square(FretRole::Root, "R".into())),
+    (11, FretState::Hollow(FretRole::ChordTone, "3".into())),
+    (12, FretState::Diamond(FretRole::Target, "H".into())),
+    (1, FretState::Ghost(FretRole::Ghost, "5".into())),
+  ] {
+    fret_positions.set(
+      FretCoord {
+        string_idx: 1,
+        fret_idx: f,
+      },
+      state,
+    );
+  }
+  fret_positions.set(
+    FretCoord {
+      string_idx: 0,
+      fret_idx: 0,
+    },
+    FretState::Muted,
   );
 
   // A hidden example (should not render) - included to ensure Hidden is ignored
-  fret_positions.insert(
+  fret_positions.set(
     FretCoord {
       string_idx: 2,
       fret_idx: 9,
     },
-    RwSignal::new(FretState::Hidden),
+    FretState::Hidden,
   );
 
   fret_positions
@@ -3,33 +3,32 @@ use leptos::prelude::*;
 
 use crate::{
   components::fretboard::{
-    base::Fretboard, FretClickEvent, FretCoord, FretState, FretStateColor, FretboardModelAdapter,
+    base::Fretboard, FretClickEvent, FretCoord, FretRole, FretState, FretboardModelAdapter,
     FretboardVisualConfig,
   },
   models::fretboard::{FretboardModel, FretboardModelBuilder},
 };
 
 use super::frets_editor::FretsEditor;
-use super::helper::get_fret_positions;
+use super::helper::{get_fret_positions, NoteShape};
 
 #[component]
 pub fn SharedModelDemo() -> impl IntoView {
   let frets = RwSignal::new(get_fret_positions());
 
   let label = RwSignal::new(String::from("foobar"));
-  let color = RwSignal::new(FretStateColor::Green);
+  let role = RwSignal::new(FretRole::Root);
+  let shape = RwSignal::new(NoteShape::Filled);
   let hidden = RwSignal::new(false);
 
   let update_fret = Callback::new(move |coord: FretCoord| {
-    frets.with(|map| {
+    frets.with(|grid| {
       let state = if hidden.get() {
         FretState::Hidden
       } else {
-        FretState::Normal(color.get(), label.get())
+        shape.get().to_state(role.get(), label.get())
       };
-      if let Some(sig) = map.get(&coord) {
-        sig.set(state);
-      }
+      grid.set(coord, state);
     });
   });
 
@@ -47,13 +46,14 @@ pub fn SharedModelDemo() -> impl IntoView {
       }))
       .config(Signal::derive(FretboardVisualConfig::default))
       .fret_states(frets.into())
-      .build(),
+      .build()
+      .expect("The demo configuration is valid"),
   );
 
   // Update model when demo fret states change (merge into model's internal signals)
   Effect::new(move || {
     model.with(|m| {
-      m.update_fret_states(frets.get());
+      frets.with(|grid| m.update_fret_states(grid));
     });
   });
 
@@ -62,7 +62,7 @@ pub fn SharedModelDemo() -> impl IntoView {
     <p class="mb-4 text-sm text-gray-600">
       Test page showing a variety of FretState values (Normal, Colored, Hidden).
     </p>
-    <FretsEditor frets label color hidden />
+    <FretsEditor frets label role shape hidden />
     <div>
       <Fretboard
         fret_states=Signal::derive(move || frets.get())
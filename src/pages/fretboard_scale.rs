@@ -1,22 +1,28 @@
+use crate::audio::PlayedNote;
 use crate::music::notes::Note;
 use crate::music::scales::ScaleType;
-use crate::music::{heptatonic_scales::HeptaScaleType, Scale};
+use crate::music::{heptatonic_scales::HeptaScaleType, Scale, Tuning};
 use crate::{
   components::{
     fret_range_selector::FretRangeSelector,
-    fretboard::{FretboardModelAdapter, FretboardVisualConfigBuilder},
-    music_selectors::{NoteSelector, ScaleTypeSelector},
+    fretboard::{
+      FretboardModelAdapter, FretboardOrientationPreference, FretboardThemePreference,
+      FretboardVisualConfigBuilder,
+    },
+    music_selectors::{FretLabelModeSelector, NoteSelector, ScaleTypeSelector},
+    pitch_listener::PitchListener,
+    tuning_selector::TuningSelector,
+  },
+  models::fretboard::{
+    on_instrument_switch, use_instrument, DiagramRequest, FretLabelMode, FretboardModelBuilder,
+    FretboardModelExt,
   },
-  models::fretboard::{default_tuning, FretboardModelBuilder, FretboardModelExt},
 };
 use leptos::{prelude::*, wasm_bindgen::JsCast};
 
 /// Page demonstrating the SVG fretboard with scale display functionality
 #[component]
 pub fn FretboardScalePage() -> impl IntoView {
-  let start_fret = RwSignal::new(2_usize);
-  let end_fret = RwSignal::new(7_usize);
-
   // Extra frets for visual context
   let extra_frets = RwSignal::new(2_usize);
 
@@ -25,31 +31,120 @@ pub fn FretboardScalePage() -> impl IntoView {
     ScaleType::Hepatonic(HeptaScaleType::Major),
   ));
 
+  let instrument = use_instrument();
+  let tuning = RwSignal::new(instrument.get_untracked().profile().default_tuning());
+  let label_mode = RwSignal::new(FretLabelMode::default());
+
   let root_note = Memo::new(move |_| scale.get().root_note().unwrap_or(Note::C));
 
+  // Fret range, tuning and drone string are owned by the model and changed in place
   let model = RwSignal::new(
     FretboardModelBuilder::new()
-      .start_fret(start_fret.into())
-      .end_fret(end_fret.into())
-      .tuning(default_tuning())
+      .start_fret_val(2)
+      .end_fret_val(7)
       .config(Signal::derive(move || {
         FretboardVisualConfigBuilder::new()
           .extra_frets(extra_frets.into())
           .build()
       }))
-      .build(),
+      .build()
+      .expect("The initial fret range is valid"),
   );
 
   model.with_untracked(move |model| {
     model.update_from_scale(scale.get_untracked());
   });
+  let start_fret = Signal::derive(move || model.with(|model| model.get_start_fret().get()));
+  let end_fret = Signal::derive(move || model.with(|model| model.get_end_fret().get()));
+
+  // Export links render the current diagram on the server with the app wide theme and orientation
+  let theme = use_context::<FretboardThemePreference>();
+  let orientation = use_context::<FretboardOrientationPreference>();
+  let diagram_query = move || {
+    DiagramRequest {
+      instrument: instrument.get(),
+      ..DiagramRequest::new(
+        scale.get(),
+        start_fret.get(),
+        end_fret.get(),
+        &tuning.get(),
+        label_mode.get(),
+        theme
+          .map(|FretboardThemePreference(theme)| theme.get())
+          .unwrap_or_default(),
+        orientation
+          .map(|FretboardOrientationPreference(orientation)| orientation.get())
+          .unwrap_or_default(),
+      )
+    }
+    .to_query_string()
+  };
+
+  // Notes played on the instrument while practising the scale
+  let (last_played, set_last_played) = signal(None::<PlayedNote>);
+  let (played_scale_notes, set_played_scale_notes) = signal(Vec::<Note>::new());
+  let on_note_played = Callback::new(move |played: PlayedNote| {
+    let note = played.note();
+    if scale.with_untracked(|scale| scale.contains_note(note)) {
+      set_played_scale_notes.update(|notes| {
+        if !notes.contains(&note) {
+          notes.push(note);
+        }
+      });
+    }
+    set_last_played.set(Some(played));
+  });
+  let played_feedback = move || {
+    last_played.get().map(|played| {
+      let note = played.note();
+      scale.with(|scale| match scale.degree_of(note) {
+        Some(degree) => view! {
+          <span class="text-green-700">{format!("{played} is degree {degree} of the scale")}</span>
+        }
+        .into_any(),
+        None if scale.contains_note(note) => view! {
+          <span class="text-green-700">{format!("{played} is in the scale")}</span>
+        }
+        .into_any(),
+        None => view! {
+          <span class="text-red-600">{format!("{played} is not in the scale")}</span>
+        }
+        .into_any(),
+      })
+    })
+  };
 
   let update_scale = move |scale: Scale| {
     model.with_untracked(move |model| {
-      model.update_from_scale(scale);
+      model.update_from_scale_with_labels(scale, label_mode.get_untracked());
     });
     set_scale.set(scale);
+    set_last_played.set(None);
+    set_played_scale_notes.set(vec![]);
   };
+  let set_fret_range = move |start: usize, end: usize| {
+    if let Err(err) = model.with_untracked(|model| model.set_fret_range(start, end)) {
+      leptos::logging::warn!("Ignoring fret range {}-{}: {}", start, end, err);
+    }
+  };
+  let set_tuning = move |new_tuning: Tuning| {
+    let drone = instrument
+      .get_untracked()
+      .profile()
+      .drone_for(&new_tuning.notes);
+    let changed = model.with_untracked(|model| {
+      model.set_tuning(new_tuning.notes.clone())?;
+      model.set_drone(drone)
+    });
+    match changed {
+      Ok(()) => {
+        tuning.set(new_tuning);
+        update_scale(scale.get_untracked());
+      }
+      Err(err) => leptos::logging::warn!("Ignoring tuning {}: {}", new_tuning.name, err),
+    }
+  };
+  on_instrument_switch(move |profile| set_tuning(profile.default_tuning()));
 
   view! {
     <div class="p-6 space-y-6">
@@ -82,8 +177,7 @@ pub fn FretboardScalePage() -> impl IntoView {
           <button
             class="py-2 px-4 text-white bg-blue-500 rounded hover:bg-blue-600"
             on:click=move |_| {
-              start_fret.set(3);
-              end_fret.set(7);
+              set_fret_range(3, 7);
               update_scale(Scale::new(Note::G, ScaleType::Hepatonic(HeptaScaleType::Major)));
             }
           >
@@ -92,8 +186,7 @@ pub fn FretboardScalePage() -> impl IntoView {
           <button
             class="py-2 px-4 text-white bg-blue-500 rounded hover:bg-blue-600"
             on:click=move |_| {
-              start_fret.set(5);
-              end_fret.set(8);
+              set_fret_range(5, 8);
               update_scale(Scale::new(Note::A, ScaleType::Hepatonic(HeptaScaleType::Minor)));
             }
           >
@@ -102,8 +195,7 @@ pub fn FretboardScalePage() -> impl IntoView {
           <button
             class="py-2 px-4 text-white bg-blue-500 rounded hover:bg-blue-600"
             on:click=move |_| {
-              start_fret.set(0);
-              end_fret.set(5);
+              set_fret_range(0, 5);
               update_scale(Scale::new(Note::E, ScaleType::Hepatonic(HeptaScaleType::Minor)));
             }
           >
@@ -112,8 +204,7 @@ pub fn FretboardScalePage() -> impl IntoView {
           <button
             class="py-2 px-4 text-white bg-blue-500 rounded hover:bg-blue-600"
             on:click=move |_| {
-              start_fret.set(7);
-              end_fret.set(10);
+              set_fret_range(7, 10);
               update_scale(Scale::new(Note::C, ScaleType::Hepatonic(HeptaScaleType::Major)));
             }
           >
@@ -122,8 +213,7 @@ pub fn FretboardScalePage() -> impl IntoView {
           <button
             class="py-2 px-4 text-white bg-green-500 rounded hover:bg-green-600"
             on:click=move |_| {
-              start_fret.set(1);
-              end_fret.set(4);
+              set_fret_range(1, 4);
               update_scale(Scale::new(Note::D, ScaleType::Hepatonic(HeptaScaleType::Minor)));
             }
           >
@@ -132,8 +222,7 @@ pub fn FretboardScalePage() -> impl IntoView {
           <button
             class="py-2 px-4 text-white bg-purple-500 rounded hover:bg-purple-600"
             on:click=move |_| {
-              start_fret.set(0);
-              end_fret.set(10);
+              set_fret_range(0, 10);
               update_scale(Scale::new(Note::E, ScaleType::Hepatonic(HeptaScaleType::Major)));
             }
           >
@@ -147,7 +236,38 @@ pub fn FretboardScalePage() -> impl IntoView {
       <FretboardModelAdapter model />
       // />
       // Show 2 extra frets beyond the end fret
-      <div class="p-4 bg-gray-50 rounded-lg border-2 border-gray-200"></div>
+      <div class="p-4 space-y-2 text-center bg-green-50 rounded-lg border-2 border-green-200">
+        <h3 class="text-lg font-semibold">"Play Along"</h3>
+        <PitchListener on_note_played />
+        <p class="text-sm">{played_feedback}</p>
+        <p class="text-sm text-gray-600">
+          {move || {
+            let played = played_scale_notes.with(Vec::len);
+            let total = Note::all_notes()
+              .iter()
+              .filter(|note| scale.with(|scale| scale.contains_note(**note)))
+              .count();
+            format!("{played} of {total} scale notes played")
+          }}
+        </p>
+      </div>
+      <div class="flex gap-3 items-center p-4 bg-gray-50 rounded-lg border-2 border-gray-200">
+        <span class="text-sm font-medium text-gray-700">"Export diagram:"</span>
+        <a
+          class="py-1 px-3 text-sm text-white bg-gray-700 rounded hover:bg-gray-800"
+          href=move || format!("/diagram.svg?{}", diagram_query())
+          download="scale.svg"
+        >
+          "SVG"
+        </a>
+        <a
+          class="py-1 px-3 text-sm text-white bg-gray-700 rounded hover:bg-gray-800"
+          href=move || format!("/diagram.png?{}", diagram_query())
+          download="scale.png"
+        >
+          "PNG"
+        </a>
+      </div>
 
       // Scale configuration controls
       <div class="grid grid-cols-1 gap-6 md:grid-cols-2 lg:grid-cols-4">
@@ -172,11 +292,11 @@ pub fn FretboardScalePage() -> impl IntoView {
         // Fret range control with dual sliders
         <FretRangeSelector
           on_start_fret_change=Callback::new(move |new_start| {
-            start_fret.set(new_start);
+            set_fret_range(new_start, end_fret.get_untracked());
             update_scale(scale.get_untracked());
           })
           on_end_fret_change=Callback::new(move |new_end| {
-            end_fret.set(new_end);
+            set_fret_range(start_fret.get_untracked(), new_end);
             update_scale(scale.get_untracked());
           })
           start_fret
@@ -184,6 +304,19 @@ pub fn FretboardScalePage() -> impl IntoView {
           label="Playable Range"
         />
 
+        <FretLabelModeSelector
+          value=label_mode
+          on_label_mode_changed=Callback::new(move |mode| {
+            label_mode.set(mode);
+            update_scale(scale.get_untracked());
+          })
+        />
+
+        <TuningSelector
+          value=tuning
+          on_tuning_change=Callback::new(set_tuning)
+        />
+
         // Extra frets control
         <div class="space-y-2">
           <label class="block text-sm font-medium text-gray-700">"Extra Frets"</label>
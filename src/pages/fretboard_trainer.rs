@@ -1,114 +1,214 @@
 use leptos::prelude::*;
-use rand::seq::IteratorRandom;
+use leptos_router::hooks::use_query_map;
+use leptos_use::use_interval_fn;
 use strum::IntoEnumIterator;
 
-use crate::components::fretboard::{
-  FretClickEvent, FretCoord, FretState, FretStateColor, FretboardModelAdapter,
+use crate::components::fretboard::FretboardModelAdapter;
+use crate::components::pitch_listener::PitchListener;
+use crate::components::trainer_leaderboard::TrainerLeaderboard;
+use crate::components::trainer_modes::{
+  now_ms, FindNotesTrainer, IntervalTrainer, NoteNameTrainer, TheoryTrainer, TrainerSession,
 };
-use crate::models::fretboard::model::FretboardModel;
-use crate::models::fretboard::FretboardModelExt;
-use crate::music::intervals::Interval;
-use crate::music::notes::Note;
-
-/// Random interval except Unison
-fn random_interval() -> Interval {
-  Interval::iter()
-    .filter(|i| i != &Interval::Unison)
-    .choose(&mut rand::rng())
-    .unwrap()
-}
+use crate::components::trainer_settings::TrainerSettingsPanel;
+use crate::components::trainer_stats::TrainerStats;
+use crate::components::tuning_selector::TuningSelector;
+use crate::models::fretboard::{
+  on_instrument_switch, use_instrument, FretboardModel, FretboardModelBuilder,
+};
+use crate::models::repository::{get_exercise_repository, ExerciseRepository};
+use crate::models::storage::{
+  load_leaderboard, load_learning_state, load_trainer_settings, persist_signal,
+  persist_signal_by_key, save_leaderboard, save_learning_state, save_trainer_settings,
+};
+use crate::models::trainer::challenge::RACE_TARGET;
+use crate::models::trainer::{
+  Challenge, ChallengeRun, Leaderboard, LearningState, TrainerMode, TrainerSettings,
+};
+use crate::music::Tuning;
 
-/// Fretboard trainer page for interval training using SVG components.
+/// Fretboard trainer page for interval and note training using SVG components.
 ///
-/// This trainer helps users learn intervals by showing a reference note and asking
-/// them to find the specified interval. Uses the modern SVG overlay approach for
-/// clean separation between game logic and visual presentation.
+/// The trainer asks, depending on the mode, for an interval of a shown note, for the name
+/// of a shown fret, for all positions of a note within a few frets, for a degree of a
+/// scale or a tone of a chord, or for an interval that is played as tones. The questions can
+/// be played as timed challenges with a local leaderboard. Each mode is its own component
+/// that shares the scores and the fretboard through a [`TrainerSession`].
 #[component]
 pub fn FretboardTrainerPage() -> impl IntoView {
+  let instrument = use_instrument();
+  let tuning = RwSignal::new(instrument.get_untracked().profile().default_tuning());
+  let mode = RwSignal::new(TrainerMode::default());
+  let settings = RwSignal::new(TrainerSettings::default());
+  persist_signal(
+    "trainer settings",
+    settings,
+    load_trainer_settings,
+    save_trainer_settings,
+  );
+
+  // Answer history that picks the next questions, kept per tuning since the positions
+  // hold other notes in another tuning
+  let learning = RwSignal::new(LearningState::default());
+  let learning_tuning = Memo::new(move |_| (instrument.get(), tuning.get()));
+  persist_signal_by_key(
+    "trainer progress",
+    learning,
+    move || learning_tuning.get(),
+    |(instrument, tuning)| Some(load_learning_state(*instrument, tuning)),
+    |(instrument, tuning), learning| save_learning_state(*instrument, tuning, learning),
+  );
+
+  // Best challenge results
+  let leaderboard = RwSignal::new(Leaderboard::default());
+  persist_signal(
+    "leaderboard",
+    leaderboard,
+    || Some(load_leaderboard()),
+    save_leaderboard,
+  );
+
+  let tuning_notes = Signal::derive(move || tuning.with(|tuning| tuning.notes.clone()));
+  let drone = Signal::derive(move || {
+    tuning.with(|tuning| instrument.get().profile().drone_for(&tuning.notes))
+  });
+  let start_fret = Signal::derive(move || settings.with(|s| s.start_fret));
+  let end_fret = Signal::derive(move || settings.with(|s| s.end_fret));
+
   // Initialize fretboard model for note calculations (pure data, no callbacks)
-  let fretboard_model = RwSignal::new(FretboardModel::default());
-
-  // Game state
-  let (num_correct, set_num_correct) = signal(0);
-  let (num_incorrect, set_num_incorrect) = signal(0);
-  let (current_note, set_current_note) = signal(Note::C);
-  let (current_interval, set_current_interval) = signal(random_interval());
-  let (error_text, set_error_text) = signal("".to_string());
-
-  // Visual state for SVG overlays
-  let (error_coords, set_error_coords) = signal(Vec::<FretCoord>::new());
-
-  // Handle fret clicks - this is pure UI logic, not mixed with data model
-  let on_note_clicked = Callback::new(move |evt: FretClickEvent| {
-    fretboard_model.with_untracked(move |model| {
-      let clicked_note = model.note_from_fret(evt.coord);
-      let target_note = current_interval
-        .get_untracked()
-        .of(current_note.get_untracked());
-
-      leptos::logging::log!(
-        "coord {:?} - target note: {:?} - clicked note: {} - expected interval: {}, got interval: {:?}",
-        evt.coord,
-        target_note,
-        clicked_note,
-        current_interval.get_untracked(),
-        Interval::from_notes(current_note.get_untracked(), clicked_note)
-      );
-
-      if clicked_note == target_note {
-        // Correct answer!
-        set_num_correct.update(|n| *n += 1);
-        set_current_interval.set(random_interval());
-        set_error_text.set("".to_string());
-
-        // Clear error highlights and set new reference note
-        set_error_coords.set(vec![]);
-        let new_fret = model.get_random_fret();
-        let new_note = model.note_from_fret(new_fret);
-        set_current_note.set(new_note);
-        model.hide_all_frets();
-        model.set_fret_state(
-          new_fret,
-          FretState::Normal(FretStateColor::Green, new_note.to_string()),
-        );
-      } else {
-        // Incorrect answer
-        if error_text.get_untracked().is_empty() {
-          set_error_text.set("Incorrect!".to_string());
-        }
-        set_num_incorrect.update(|n| *n += 1);
+  let fretboard_model = RwSignal::new(
+    FretboardModelBuilder::new()
+      .start_fret(start_fret)
+      .end_fret(end_fret)
+      .tuning(tuning_notes)
+      .drone(drone)
+      .build()
+      .unwrap_or_else(|err| {
+        leptos::logging::warn!("Can't train with these settings: {}", err);
+        FretboardModel::default()
+      }),
+  );
 
-        if !error_coords.get_untracked().contains(&evt.coord) {
-          // Add to error highlights
-          set_error_coords.update(|coords| {
-            coords.push(evt.coord);
-          });
-        }
+  let settings_summary = Signal::derive(move || {
+    format!(
+      "{}, {}",
+      tuning.with(|tuning| tuning.name.clone()),
+      settings.with(TrainerSettings::summary)
+    )
+  });
+  let session = TrainerSession::new(
+    mode,
+    settings,
+    learning,
+    leaderboard,
+    fretboard_model,
+    tuning_notes,
+    settings_summary,
+  );
+  let TrainerSession {
+    challenge,
+    run,
+    result_text,
+    num_correct,
+    num_incorrect,
+    error_text,
+    ..
+  } = session;
 
-        model.set_fret_state(
-          evt.coord,
-          FretState::Normal(FretStateColor::Red, clicked_note.to_string()),
-        );
+  // The clock ticks while a challenge runs
+  let clock = RwSignal::new(0.0);
+  use_interval_fn(
+    move || {
+      if !session.is_running() {
+        return;
       }
-    });
+      let now = now_ms();
+      clock.set(now);
+      session.tick(now);
+    },
+    100,
+  );
+
+  // Starts a new run of the selected challenge with fresh scores
+  let start_challenge = move || {
+    let now = now_ms();
+    num_correct.set(0);
+    num_incorrect.set(0);
+    result_text.set("".to_string());
+    clock.set(now);
+    run.set(Some(ChallengeRun::new(challenge.get_untracked(), now)));
+    session.ask_next_question();
+  };
+  // Changing what is asked ends a challenge without a score
+  let abandon_challenge = move || {
+    if session.is_running() {
+      run.set(None);
+      result_text.set("Challenge stopped".to_string());
+    }
+  };
+
+  let on_tuning_change = Callback::new(move |new_tuning: Tuning| {
+    abandon_challenge();
+    tuning.set(new_tuning);
+    session.ask_next_question();
+  });
+
+  // Opened from an exercise with `?exercise=<id>`, the trainer practices in its tuning
+  let query = use_query_map();
+  let exercise_tuning = Memo::new(move |_| {
+    query
+      .with(|query| query.get("exercise"))
+      .and_then(|id| get_exercise_repository().find_by_id(&id).ok().flatten())
+      .map(|exercise| exercise.tuning)
+  });
+  Effect::new(move |_| {
+    if let Some(exercise_tuning) = exercise_tuning.get() {
+      on_tuning_change.run(exercise_tuning);
+    }
+  });
+  on_instrument_switch(move |profile| {
+    // The exercise tuning stays as long as the instrument has its strings
+    let new_tuning = exercise_tuning
+      .get_untracked()
+      .filter(|tuning| tuning.num_strings() == profile.num_strings())
+      .unwrap_or_else(|| profile.default_tuning());
+    on_tuning_change.run(new_tuning);
   });
 
-  // Initialize the first question
-  fretboard_model.with_untracked(move |model| {
-    let random_fret = model.get_random_fret();
-    let note = model.note_from_fret(random_fret);
-    set_current_note.set(note);
-    set_error_coords.set(vec![]);
-    leptos::logging::log!("Initial note: {} at {:?}", note, random_fret);
-    model.set_fret_state(
-      random_fret,
-      FretState::Normal(FretStateColor::Green, note.to_string()),
-    );
+  // Ask a question that fits the new settings
+  Effect::new(move |prev: Option<()>| {
+    settings.track();
+    if prev.is_some() {
+      abandon_challenge();
+      session.ask_next_question();
+    }
   });
 
-  // Computed strings for display
-  let interval_str = move || current_interval.get().to_string();
-  let note_str = move || current_note.get().to_string();
+  // The mode component asks its first question when it is shown
+  let on_mode_change = move |new_mode: TrainerMode| {
+    abandon_challenge();
+    mode.set(new_mode);
+  };
+
+  // Countdown of a sprint, running time of the other challenges
+  let clock_text = move || {
+    let now = clock.get();
+    run.with(|run| {
+      run.as_ref().map(|run| match run.remaining_ms(now) {
+        Some(remaining) => format!("{:.1}s left", remaining / 1000.0),
+        None => format!("{:.1}s", run.elapsed_ms(now) / 1000.0),
+      })
+    })
+  };
+  let progress_text = move || {
+    run.with(|run| {
+      run.as_ref().map(|run| match run.challenge {
+        Challenge::FirstTo20 => format!("{} / {RACE_TARGET}", run.correct),
+        Challenge::SuddenDeath => format!("Streak: {}", run.correct),
+        Challenge::Sprint | Challenge::Practice => format!("{} correct", run.correct),
+      })
+    })
+  };
+
   let success_rate = move || {
     let correct = num_correct.get();
     let incorrect = num_incorrect.get();
@@ -124,18 +224,113 @@ pub fn FretboardTrainerPage() -> impl IntoView {
     <div class="flex flex-col space-y-4">
       <div class="flex flex-col items-center space-y-4">
         <h1 class="text-2xl font-bold">"Fretboard Trainer"</h1>
-        <p>"Train intervals of notes"</p>
+        <p>"Train intervals, notes, scales, chords and your ear"</p>
       </div>
 
-      <FretboardModelAdapter model=fretboard_model on_note_clicked=on_note_clicked />
+      <div class="flex gap-4 mx-auto w-full max-w-md">
+        <div class="space-y-2">
+          <label class="block text-sm font-medium">"Mode"</label>
+          <select
+            class="p-2 w-full rounded-md border"
+            on:change=move |ev| {
+              let value = event_target_value(&ev);
+              if let Some(selected) = TrainerMode::iter().find(|m| m.to_string() == value) {
+                on_mode_change(selected);
+              }
+            }
+          >
+            {TrainerMode::iter()
+              .map(|option| {
+                view! {
+                  <option value=option.to_string() selected=move || mode.get() == option>
+                    {option.to_string()}
+                  </option>
+                }
+              })
+              .collect_view()}
+          </select>
+        </div>
+        <div class="space-y-2">
+          <label class="block text-sm font-medium">"Challenge"</label>
+          <select
+            class="p-2 w-full rounded-md border"
+            on:change=move |ev| {
+              let value = event_target_value(&ev);
+              if let Some(selected) = Challenge::iter().find(|c| c.to_string() == value) {
+                abandon_challenge();
+                challenge.set(selected);
+              }
+            }
+          >
+            {Challenge::iter()
+              .map(|option| {
+                view! {
+                  <option value=option.to_string() selected=move || challenge.get() == option>
+                    {option.to_string()}
+                  </option>
+                }
+              })
+              .collect_view()}
+          </select>
+        </div>
+        <div class="grow">
+          <TuningSelector value=tuning on_tuning_change />
+        </div>
+      </div>
 
-      <div class="text-center">
-        <p class="text-lg">
-          "Looking for " <b>{move || format!("{} ", interval_str())}</b> "of "
-          <b>{move || format!("{} ", note_str())}</b>
-        </p>
+      <div class="mx-auto w-full max-w-3xl">
+        <TrainerSettingsPanel
+          settings
+          tuning=tuning_notes
+        />
       </div>
 
+      <Show when=move || challenge.get().is_timed()>
+        <div class="flex gap-4 justify-center items-center">
+          <button
+            class="py-2 px-4 text-white bg-purple-500 rounded hover:bg-purple-600"
+            on:click=move |_| start_challenge()
+          >
+            {move || {
+              if run.with(|run| run.as_ref().is_some_and(|run| !run.is_finished())) {
+                "Restart"
+              } else {
+                "Start"
+              }
+            }}
+          </button>
+          <p class="font-mono text-lg">{clock_text}</p>
+          <p class="font-semibold">{progress_text}</p>
+        </div>
+        <p class="text-center font-semibold text-purple-700">{result_text}</p>
+      </Show>
+
+      <FretboardModelAdapter
+        model=fretboard_model
+        on_note_clicked=Callback::new(move |evt| session.fret_clicked(evt))
+      />
+
+      {move || match mode.get() {
+        TrainerMode::Intervals => {
+          view! { <IntervalTrainer session ear_training=false /> }.into_any()
+        }
+        TrainerMode::EarTraining => {
+          view! { <IntervalTrainer session ear_training=true /> }.into_any()
+        }
+        TrainerMode::NoteNames => view! { <NoteNameTrainer session /> }.into_any(),
+        TrainerMode::FindNotes => view! { <FindNotesTrainer session /> }.into_any(),
+        TrainerMode::ScaleDegrees => {
+          view! { <TheoryTrainer session chord_tones=false /> }.into_any()
+        }
+        TrainerMode::ChordTones => {
+          view! { <TheoryTrainer session chord_tones=true /> }.into_any()
+        }
+      }}
+
+      <Show when=move || mode.get() != TrainerMode::FindNotes>
+        <PitchListener on_note_played=Callback::new(move |played| session.note_played(played)) />
+      </Show>
+
       <div class="flex flex-col items-center space-y-2">
         <div class="grid grid-cols-2 gap-4 text-center">
           <div>
@@ -174,8 +369,29 @@ pub fn FretboardTrainerPage() -> impl IntoView {
       </div>
 
       <div class="text-center">
-        <p class="text-sm text-gray-500">"Click on the fretboard to answer"</p>
+        <p class="text-sm text-gray-500">
+          {move || match mode.get() {
+            TrainerMode::NoteNames => "Click on the note buttons to answer",
+            TrainerMode::EarTraining => "Click on an interval or on the fretboard to answer",
+            _ => "Click on the fretboard to answer",
+          }}
+        </p>
+      </div>
+
+      <div class="mx-auto w-full max-w-3xl">
+        <TrainerStats learning tuning=tuning_notes drone start_fret end_fret />
       </div>
+
+      <Show when=move || challenge.get().is_timed()>
+        <div class="mx-auto w-full max-w-3xl">
+          <TrainerLeaderboard
+            leaderboard
+            challenge
+            mode
+            settings=settings_summary
+          />
+        </div>
+      </Show>
     </div>
   }
 }
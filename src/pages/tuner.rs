@@ -0,0 +1,175 @@
+use leptos::prelude::*;
+
+use crate::audio::tones::A4_FREQUENCY;
+use crate::audio::PlayedNote;
+use crate::components::pitch_listener::PitchListener;
+use crate::components::tuning_selector::TuningSelector;
+use crate::models::fretboard::{on_instrument_switch, use_instrument};
+use crate::models::storage::{load_tuner_reference, persist_signal, save_tuner_reference};
+use crate::models::tuner::{
+  clamp_reference, nearest_string, TuningStatus, MAX_REFERENCE_HZ, MIN_REFERENCE_HZ,
+};
+use crate::music::Tuning;
+
+/// Cents shown on each side of the needle scale
+const SCALE_CENTS: f64 = 50.0;
+
+fn status_class(status: TuningStatus) -> &'static str {
+  match status {
+    TuningStatus::InTune => "text-green-600",
+    TuningStatus::Flat | TuningStatus::Sharp => "text-orange-500",
+  }
+}
+
+/// Chromatic tuner that listens to the microphone and shows the nearest note, how many
+/// cents it is off and which string of the selected tuning is being tuned
+#[component]
+pub fn TunerPage() -> impl IntoView {
+  let instrument = use_instrument();
+  let tuning = RwSignal::new(instrument.get_untracked().profile().default_tuning());
+  let on_tuning_change = Callback::new(move |new_tuning: Tuning| tuning.set(new_tuning));
+  on_instrument_switch(move |profile| tuning.set(profile.default_tuning()));
+
+  let reference = RwSignal::new(A4_FREQUENCY);
+  persist_signal(
+    "tuner reference",
+    reference,
+    load_tuner_reference,
+    |reference| save_tuner_reference(*reference),
+  );
+
+  let (frequency, set_frequency) = signal(None::<f64>);
+  let on_frequency = Callback::new(move |heard: Option<f64>| set_frequency.set(heard));
+
+  let open_midis = Memo::new(move |_| {
+    tuning.with(|tuning| instrument.get().profile().open_string_midis(&tuning.notes))
+  });
+  let played = Memo::new(move |_| {
+    frequency
+      .get()
+      .and_then(|frequency| PlayedNote::from_frequency_with_reference(frequency, reference.get()))
+  });
+  let target = Memo::new(move |_| {
+    frequency.get().and_then(|frequency| {
+      open_midis.with(|open_midis| nearest_string(frequency, open_midis, reference.get()))
+    })
+  });
+
+  // Needle position in percent of the scale width, centered when in tune
+  let needle_left = move || {
+    let cents = played.get().map_or(0.0, |played| played.cents);
+    50.0 + 50.0 * cents.clamp(-SCALE_CENTS, SCALE_CENTS) / SCALE_CENTS
+  };
+
+  view! {
+    <div class="flex flex-col gap-6 items-center p-4 mx-auto max-w-xl">
+      <h1 class="text-2xl font-bold">"Tuner"</h1>
+
+      <div class="grid grid-cols-1 gap-4 w-full sm:grid-cols-2">
+        <TuningSelector value=tuning on_tuning_change />
+        <div class="space-y-2">
+          <label class="block text-sm font-medium">"A4 reference (Hz)"</label>
+          <input
+            type="number"
+            class="p-2 w-full rounded-md border"
+            min=MIN_REFERENCE_HZ
+            max=MAX_REFERENCE_HZ
+            step="0.5"
+            prop:value=move || reference.get().to_string()
+            on:change=move |e| {
+              if let Ok(value) = event_target_value(&e).parse::<f64>() {
+                reference.set(clamp_reference(value));
+              }
+            }
+          />
+        </div>
+      </div>
+
+      <PitchListener on_frequency label="🎤 Start Tuner" />
+
+      <div class="flex flex-col gap-2 items-center w-full">
+        <div class="font-mono text-6xl font-bold">
+          {move || played.get().map_or("–".to_string(), |played| played.to_string())}
+        </div>
+        <div class="font-mono text-sm text-gray-600">
+          {move || {
+            match (frequency.get(), played.get()) {
+              (Some(frequency), Some(played)) => {
+                format!("{frequency:.1} Hz  {:+.0}¢", played.cents)
+              }
+              _ => "Play a string…".to_string(),
+            }
+          }}
+        </div>
+        <div class="relative w-full h-8 bg-gray-100 rounded">
+          <div class="absolute top-0 w-0.5 h-full bg-gray-400" style="left: 50%"></div>
+          <Show when=move || played.get().is_some()>
+            <div
+              class=move || {
+                let status = played
+                  .get()
+                  .map_or(TuningStatus::InTune, |played| TuningStatus::from_cents(played.cents));
+                if status == TuningStatus::InTune {
+                  "absolute top-0 w-1 h-full bg-green-500 rounded"
+                } else {
+                  "absolute top-0 w-1 h-full bg-orange-500 rounded"
+                }
+              }
+              style=move || format!("left: calc({:.1}% - 2px)", needle_left())
+            ></div>
+          </Show>
+        </div>
+        <div class="flex justify-between w-full text-xs text-gray-500">
+          <span>{format!("-{SCALE_CENTS:.0}¢")}</span>
+          <span>"0"</span>
+          <span>{format!("+{SCALE_CENTS:.0}¢")}</span>
+        </div>
+      </div>
+
+      <div class="w-full">
+        <h2 class="mb-2 text-lg font-semibold">"Strings"</h2>
+        <ul class="space-y-1">
+          {move || {
+            let notes = tuning.with(|tuning| tuning.notes.clone());
+            open_midis
+              .get()
+              .into_iter()
+              .zip(notes)
+              .enumerate()
+              .map(|(string_idx, (midi, note))| {
+                let is_target = move || {
+                  target.get().is_some_and(|target| target.string_idx == string_idx)
+                };
+                let octave = i32::from(midi) / 12 - 1;
+                view! {
+                  <li class=move || {
+                    if is_target() {
+                      "flex justify-between py-1 px-3 font-semibold bg-blue-50 rounded border border-blue-300"
+                    } else {
+                      "flex justify-between py-1 px-3 rounded border border-gray-200"
+                    }
+                  }>
+                    <span>{format!("String {}: {note}{octave}", string_idx + 1)}</span>
+                    {move || {
+                      target
+                        .get()
+                        .filter(|_| is_target())
+                        .map(|target| {
+                          let status = target.status();
+                          view! {
+                            <span class=status_class(status)>
+                              {format!("{status} {:+.0}¢", target.cents)}
+                            </span>
+                          }
+                        })
+                    }}
+                  </li>
+                }
+              })
+              .collect_view()
+          }}
+        </ul>
+      </div>
+    </div>
+  }
+}
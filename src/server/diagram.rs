@@ -0,0 +1,249 @@
+use std::sync::{Arc, OnceLock};
+
+use axum::{
+  extract::{Path, Query},
+  http::{header, StatusCode},
+  response::{IntoResponse, Response},
+};
+use resvg::{tiny_skia, usvg};
+
+use crate::models::fretboard::{parse_root, ChordDiagramRequest, DiagramRequest};
+
+/// PNGs are rendered at twice the SVG size, sharp enough for printed handouts
+const PNG_SCALE: f32 = 2.0;
+
+fn svg_response(svg: Result<String, String>) -> Response {
+  match svg {
+    Ok(svg) => ([(header::CONTENT_TYPE, "image/svg+xml")], svg).into_response(),
+    Err(err) => (StatusCode::BAD_REQUEST, err).into_response(),
+  }
+}
+
+/// Embeds in wikis and chats often need a file extension, so a trailing ".svg" is ignored
+fn strip_svg_extension(segment: &str) -> &str {
+  segment.strip_suffix(".svg").unwrap_or(segment)
+}
+
+/// `GET /diagram.svg`
+pub async fn diagram_svg(Query(request): Query<DiagramRequest>) -> Response {
+  svg_response(request.to_svg())
+}
+
+/// `GET /diagram/scale/{root}/{type}`, e.g. `/diagram/scale/A/Minor?frets=5-8&labels=intervals`
+/// or `/diagram/scale/F%23/Major`
+pub async fn scale_diagram(
+  Path((root, scale)): Path<(String, String)>,
+  Query(request): Query<DiagramRequest>,
+) -> Response {
+  let svg = parse_root(&root).and_then(|root| {
+    DiagramRequest {
+      root,
+      scale: strip_svg_extension(&scale).to_string(),
+      ..request
+    }
+    .to_svg()
+  });
+  svg_response(svg)
+}
+
+/// `GET /diagram/chord/{symbol}`, e.g. `/diagram/chord/F%23m7`
+pub async fn chord_diagram(
+  Path(symbol): Path<String>,
+  Query(request): Query<ChordDiagramRequest>,
+) -> Response {
+  svg_response(
+    ChordDiagramRequest {
+      symbol: strip_svg_extension(&symbol).to_string(),
+      ..request
+    }
+    .to_svg(),
+  )
+}
+
+/// `GET /diagram.png`
+pub async fn diagram_png(Query(request): Query<DiagramRequest>) -> Response {
+  // Rasterising takes a while, keep it off the async worker threads
+  let png = tokio::task::spawn_blocking(move || {
+    request.to_svg().and_then(|svg| {
+      svg_to_png(&svg).map_err(|err| {
+        leptos::logging::error!("Failed to render diagram PNG: {}", err);
+        err
+      })
+    })
+  })
+  .await;
+  match png {
+    Ok(Ok(png)) => ([(header::CONTENT_TYPE, "image/png")], png).into_response(),
+    Ok(Err(err)) => (StatusCode::BAD_REQUEST, err).into_response(),
+    Err(err) => {
+      leptos::logging::error!("Diagram PNG task failed: {}", err);
+      StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    }
+  }
+}
+
+/// System fonts are loaded once, loading them takes a while
+fn fontdb() -> Arc<usvg::fontdb::Database> {
+  static FONTDB: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+  FONTDB
+    .get_or_init(|| {
+      let mut fontdb = usvg::fontdb::Database::new();
+      fontdb.load_system_fonts();
+      // Labels use the generic sans-serif family, which fontdb maps to Arial. Fall back to
+      // any installed sans font on servers without Arial.
+      let families: Vec<String> = fontdb
+        .faces()
+        .flat_map(|face| face.families.iter().map(|(name, _)| name.clone()))
+        .collect();
+      if !families.iter().any(|name| name == "Arial") {
+        let fallback = ["DejaVu Sans", "Liberation Sans", "Noto Sans"]
+          .into_iter()
+          .find(|name| families.iter().any(|family| family == name))
+          .map(str::to_string)
+          .or_else(|| families.first().cloned());
+        if let Some(family) = fallback {
+          fontdb.set_sans_serif_family(family);
+        }
+      }
+      Arc::new(fontdb)
+    })
+    .clone()
+}
+
+pub fn svg_to_png(svg: &str) -> Result<Vec<u8>, String> {
+  let options = usvg::Options {
+    fontdb: fontdb(),
+    ..usvg::Options::default()
+  };
+  let tree = usvg::Tree::from_str(svg, &options).map_err(|err| err.to_string())?;
+  let size = tree
+    .size()
+    .to_int_size()
+    .scale_by(PNG_SCALE)
+    .ok_or("Invalid diagram size")?;
+  let mut pixmap =
+    tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("Invalid diagram size")?;
+  resvg::render(
+    &tree,
+    tiny_skia::Transform::from_scale(PNG_SCALE, PNG_SCALE),
+    &mut pixmap.as_mut(),
+  );
+  pixmap.encode_png().map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use axum::http::Uri;
+
+  /// Runs a handler and returns the status and the body of its response
+  fn call(handler: impl std::future::Future<Output = Response>) -> (StatusCode, String) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+      let response = handler.await;
+      let status = response.status();
+      let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+      (status, String::from_utf8(body.to_vec()).unwrap())
+    })
+  }
+
+  fn query<T: serde::de::DeserializeOwned>(uri: &str) -> Query<T> {
+    Query::try_from_uri(&uri.parse::<Uri>().unwrap()).unwrap()
+  }
+
+  /// Compares `svg` with `src/server/golden/{name}.svg`. Run the tests with
+  /// `UPDATE_GOLDEN=1` to write the files after an intended change of the drawing.
+  fn assert_golden(name: &str, svg: &str) {
+    let path = format!(
+      "{}/src/server/golden/{name}.svg",
+      env!("CARGO_MANIFEST_DIR")
+    );
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+      std::fs::write(&path, svg).unwrap();
+    }
+    let golden = std::fs::read_to_string(&path).unwrap();
+    assert!(
+      golden == svg,
+      "{name}.svg differs from the rendered diagram"
+    );
+  }
+
+  #[test]
+  fn test_scale_route_golden() {
+    let (status, svg) = call(scale_diagram(
+      Path(("A".to_string(), "Minor.svg".to_string())),
+      query("/diagram/scale/A/Minor.svg?frets=5-8&labels=intervals"),
+    ));
+    assert_eq!(status, StatusCode::OK);
+    assert_golden("scale_a_minor", &svg);
+  }
+
+  #[test]
+  fn test_chord_route_golden() {
+    let (status, svg) = call(chord_diagram(
+      Path("Am7".to_string()),
+      query("/diagram/chord/Am7?capo=2"),
+    ));
+    assert_eq!(status, StatusCode::OK);
+    assert_golden("chord_am7_capo_2", &svg);
+  }
+
+  #[test]
+  fn test_query_route_golden() {
+    let (status, svg) = call(diagram_svg(query(
+      "/diagram.svg?root=F%23&scale=Major&frets=2-5&tuning=D+A+D+G+B+E&dark=true",
+    )));
+    assert_eq!(status, StatusCode::OK);
+    assert_golden("query_f_sharp_major_drop_d", &svg);
+  }
+
+  #[test]
+  fn test_chord_route_rejects_positions_past_the_search() {
+    let (status, body) = call(chord_diagram(
+      Path("C".to_string()),
+      query("/diagram/chord/C?position=18446744073709551615"),
+    ));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body, "The position has to be at fret 12 or below");
+  }
+
+  #[test]
+  fn test_scale_route_accepts_sharp_and_flat_roots() {
+    // Axum percent-decodes the path, so `F%23` arrives as "F#"
+    let (status, sharp) = call(scale_diagram(
+      Path(("F#".to_string(), "Minor".to_string())),
+      query("/diagram/scale/F%23/Minor"),
+    ));
+    assert_eq!(status, StatusCode::OK);
+    let (status, flat) = call(scale_diagram(
+      Path(("Gb".to_string(), "Minor".to_string())),
+      query("/diagram/scale/Gb/Minor"),
+    ));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(sharp, flat);
+
+    let (status, _) = call(scale_diagram(
+      Path(("Bb".to_string(), "Major".to_string())),
+      query("/diagram/scale/Bb/Major"),
+    ));
+    assert_eq!(status, StatusCode::OK);
+
+    for root in ["H", "Bbb", "FSharpOrGFlat"] {
+      let (status, body) = call(scale_diagram(
+        Path((root.to_string(), "Major".to_string())),
+        query("/diagram/scale/root/Major"),
+      ));
+      assert_eq!(status, StatusCode::BAD_REQUEST);
+      assert_eq!(body, format!("Unknown root note: {root}"));
+    }
+  }
+
+  #[test]
+  fn test_svg_to_png() {
+    let svg = DiagramRequest::default().to_svg().unwrap();
+    let png = svg_to_png(&svg).unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+  }
+}
@@ -0,0 +1,3 @@
+//! Routes served next to the Leptos app, only available with the `ssr` feature
+
+pub mod diagram;